@@ -0,0 +1,141 @@
+use criterion::{black_box, criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use rand::{RngCore, SeedableRng};
+use rand::rngs::StdRng;
+
+use burnin::tests::memory::sharded_fill_verify;
+
+const THREAD_COUNTS: [usize; 4] = [1, 2, 4, 8];
+const ITERATIONS_PER_THREAD: u64 = 20_000;
+const BUFFER_SIZE: usize = 16 * 1024 * 1024;
+
+/// A small unit of work comparable to one stress worker iteration.
+fn work_unit(seed: u64) -> u64 {
+    (0..64u64).fold(seed, |acc, i| acc.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(i))
+}
+
+/// The per-shard work done by `sharded_fill_verify`, for the mutex baseline.
+fn fill_and_verify(shard: &mut [u8], seed: u64) -> usize {
+    StdRng::seed_from_u64(seed).fill_bytes(shard);
+
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut expected = [0u8; 4096];
+    let mut errors = 0;
+    for chunk in shard.chunks(expected.len()) {
+        let expected = &mut expected[..chunk.len()];
+        rng.fill_bytes(expected);
+        errors += chunk.iter().zip(expected.iter()).filter(|(a, b)| a != b).count();
+    }
+    errors
+}
+
+fn stop_flag_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("stop_flag");
+
+    for threads in THREAD_COUNTS {
+        group.throughput(Throughput::Elements(threads as u64 * ITERATIONS_PER_THREAD));
+
+        // The previous design: every iteration locks a shared flag and
+        // publishes its progress through a mutex.
+        group.bench_with_input(BenchmarkId::new("mutex", threads), &threads, |b, &threads| {
+            b.iter(|| {
+                let running = Arc::new(Mutex::new(true));
+                let counter = Arc::new(Mutex::new(0u64));
+                let handles: Vec<_> = (0..threads)
+                    .map(|id| {
+                        let running = running.clone();
+                        let counter = counter.clone();
+                        thread::spawn(move || {
+                            let mut acc = id as u64;
+                            for _ in 0..ITERATIONS_PER_THREAD {
+                                if !*running.lock().unwrap() {
+                                    break;
+                                }
+                                acc = work_unit(acc);
+                                *counter.lock().unwrap() += 1;
+                            }
+                            black_box(acc)
+                        })
+                    })
+                    .collect();
+                for handle in handles {
+                    handle.join().unwrap();
+                }
+                let total = *counter.lock().unwrap();
+                black_box(total)
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("atomic", threads), &threads, |b, &threads| {
+            b.iter(|| {
+                let running = Arc::new(AtomicBool::new(true));
+                let counter = Arc::new(AtomicU64::new(0));
+                let handles: Vec<_> = (0..threads)
+                    .map(|id| {
+                        let running = running.clone();
+                        let counter = counter.clone();
+                        thread::spawn(move || {
+                            let mut acc = id as u64;
+                            let mut local = 0;
+                            for _ in 0..ITERATIONS_PER_THREAD {
+                                if !running.load(Ordering::Relaxed) {
+                                    break;
+                                }
+                                acc = work_unit(acc);
+                                local += 1;
+                            }
+                            counter.fetch_add(local, Ordering::Relaxed);
+                            black_box(acc)
+                        })
+                    })
+                    .collect();
+                for handle in handles {
+                    handle.join().unwrap();
+                }
+                black_box(counter.load(Ordering::Relaxed))
+            });
+        });
+    }
+
+    group.finish();
+}
+
+fn memory_fill_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("memory_fill");
+    group.sample_size(20);
+    group.throughput(Throughput::Bytes(BUFFER_SIZE as u64));
+
+    for threads in THREAD_COUNTS {
+        // The previous design: all threads share one buffer behind a mutex
+        // and hold the lock while filling and verifying their slice of it.
+        group.bench_with_input(BenchmarkId::new("shared_mutex", threads), &threads, |b, &threads| {
+            let memory = Arc::new(Mutex::new(vec![0u8; BUFFER_SIZE]));
+            b.iter(|| {
+                let chunk = BUFFER_SIZE / threads;
+                let handles: Vec<_> = (0..threads)
+                    .map(|id| {
+                        let memory = memory.clone();
+                        thread::spawn(move || {
+                            let mut mem = memory.lock().unwrap();
+                            fill_and_verify(&mut mem[id * chunk..(id + 1) * chunk], (id as u64) << 32)
+                        })
+                    })
+                    .collect();
+                let errors: usize = handles.into_iter().map(|h| h.join().unwrap()).sum();
+                black_box(errors)
+            });
+        });
+
+        group.bench_with_input(BenchmarkId::new("sharded", threads), &threads, |b, &threads| {
+            let mut memory = vec![0u8; BUFFER_SIZE];
+            b.iter(|| black_box(sharded_fill_verify(&mut memory, threads, |pass| pass < 1)));
+        });
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, stop_flag_benchmark, memory_fill_benchmark);
+criterion_main!(benches);
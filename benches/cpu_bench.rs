@@ -1,80 +1,38 @@
 use criterion::{black_box, criterion_group, criterion_main, Criterion};
 use std::time::Duration;
 
+use burnin::tests::cpu;
+use burnin::tests::simd::{select_kernels, SimdWorkload};
+
 fn cpu_workload_benchmark(c: &mut Criterion) {
     let mut group = c.benchmark_group("cpu_workloads");
     group.measurement_time(Duration::from_secs(10));
     
-    group.bench_function("floating_point", |b| {
-        b.iter(|| {
-            let mut sum = 0.0f64;
-            for i in 0..1000 {
-                sum += black_box((i as f64).sqrt() * 2.718281828);
-            }
-            black_box(sum)
-        });
-    });
-    
-    group.bench_function("integer_arithmetic", |b| {
-        b.iter(|| {
-            let mut result = 1u64;
-            for i in 1..100 {
-                result = black_box(result.wrapping_mul(i).wrapping_add(i * i));
-            }
-            black_box(result)
-        });
-    });
-    
-    group.bench_function("matrix_multiply_small", |bench| {
-        let size = 10;
-        let a = vec![vec![1.0f64; size]; size];
-        let b = vec![vec![2.0f64; size]; size];
-        
-        bench.iter(|| {
-            let mut c = vec![vec![0.0f64; size]; size];
-            for i in 0..size {
-                for j in 0..size {
-                    for k in 0..size {
-                        c[i][j] += a[i][k] * b[k][j];
-                    }
-                }
-            }
-            black_box(c)
-        });
-    });
+    group.bench_function("prime_search", |b| b.iter(|| black_box(cpu::prime_search())));
+    group.bench_function("matrix_operations", |b| b.iter(|| black_box(cpu::matrix_operations())));
+    group.bench_function("floating_point", |b| b.iter(|| black_box(cpu::floating_point_ops())));
+    group.bench_function("integer_arithmetic", |b| b.iter(|| black_box(cpu::integer_arithmetic())));
+    group.bench_function("branch_prediction", |b| b.iter(|| black_box(cpu::branch_prediction())));
+    group.bench_function("mixed", |b| b.iter(|| black_box(cpu::mixed_workload())));
     
     group.finish();
 }
 
-fn memory_access_benchmark(c: &mut Criterion) {
-    let mut group = c.benchmark_group("memory_access");
-    
-    group.bench_function("sequential_read", |b| {
-        let data = vec![0u8; 1_000_000];
-        b.iter(|| {
-            let mut sum = 0u64;
-            for &byte in data.iter() {
-                sum += byte as u64;
-            }
-            black_box(sum)
-        });
-    });
+fn simd_workload_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("simd_workloads");
     
-    group.bench_function("random_access", |b| {
-        let data = vec![0u8; 1_000_000];
-        let indices: Vec<usize> = (0..1000).map(|i| (i * 997) % data.len()).collect();
-        
-        b.iter(|| {
-            let mut sum = 0u64;
-            for &idx in indices.iter() {
-                sum += data[idx] as u64;
-            }
-            black_box(sum)
-        });
-    });
+    // Only kernels the running CPU supports are benchmarked.
+    let (kernels, _) = select_kernels();
+    for kernel in kernels {
+        let mut workload = match SimdWorkload::new(kernel) {
+            Ok(workload) => workload,
+            Err(_) => continue,
+        };
+        group.bench_function(kernel.name(), |b| b.iter(|| black_box(workload.run())));
+    }
     
     group.finish();
 }
 
-criterion_group!(benches, cpu_workload_benchmark, memory_access_benchmark);
+criterion_group!(benches, cpu_workload_benchmark, simd_workload_benchmark);
 criterion_main!(benches);
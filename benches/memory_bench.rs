@@ -0,0 +1,37 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+
+use burnin::tests::memory::{pattern_pass, random_pattern_pass, sharded_fill_verify};
+
+const BUFFER_SIZE: usize = 64 * 1024 * 1024;
+
+fn memory_pattern_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("memory_patterns");
+    group.sample_size(10);
+    group.throughput(Throughput::Bytes(BUFFER_SIZE as u64));
+    
+    let mut memory = vec![0u8; BUFFER_SIZE];
+    
+    group.bench_function("sequential_pattern", |b| {
+        b.iter(|| black_box(pattern_pass(&mut memory, 0xAA)))
+    });
+    
+    group.bench_function("walking_bits", |b| {
+        b.iter(|| {
+            let errors: usize = (0..8).map(|bit| pattern_pass(&mut memory, 1 << bit)).sum();
+            black_box(errors)
+        })
+    });
+    
+    group.bench_function("random_pattern", |b| {
+        b.iter(|| black_box(random_pattern_pass(&mut memory, 42, 0x55)))
+    });
+    
+    group.bench_function("sharded_fill_verify", |b| {
+        b.iter(|| black_box(sharded_fill_verify(&mut memory, num_cpus::get(), |pass| pass < 1)))
+    });
+    
+    group.finish();
+}
+
+criterion_group!(benches, memory_pattern_benchmark);
+criterion_main!(benches);
@@ -0,0 +1,47 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use std::fs;
+use std::path::PathBuf;
+
+use burnin::tests::storage::{random_read, random_write, sequential_read, sequential_write};
+
+const FILE_SIZE: u64 = 64 * 1024 * 1024;
+
+/// Places the test file on tmpfs when available so the benchmark measures
+/// the tool's I/O path rather than the disk underneath it.
+fn bench_file() -> PathBuf {
+    let shm = PathBuf::from("/dev/shm");
+    let dir = if shm.is_dir() { shm } else { std::env::temp_dir() };
+    dir.join(format!("burnin-storage-bench-{}.tmp", std::process::id()))
+}
+
+fn storage_io_benchmark(c: &mut Criterion) {
+    let mut group = c.benchmark_group("storage_io");
+    group.sample_size(10);
+    
+    let path = bench_file();
+    sequential_write(&path, FILE_SIZE).unwrap();
+    
+    group.throughput(Throughput::Bytes(FILE_SIZE));
+    group.bench_function("sequential_write", |b| {
+        b.iter(|| black_box(sequential_write(&path, FILE_SIZE).unwrap()))
+    });
+    group.bench_function("sequential_read", |b| {
+        b.iter(|| black_box(sequential_read(&path, FILE_SIZE).unwrap()))
+    });
+    group.finish();
+    
+    let mut group = c.benchmark_group("storage_random_io");
+    group.sample_size(10);
+    group.bench_function("random_read", |b| {
+        b.iter(|| black_box(random_read(&path, FILE_SIZE).unwrap()))
+    });
+    group.bench_function("random_write", |b| {
+        b.iter(|| black_box(random_write(&path, FILE_SIZE).unwrap()))
+    });
+    
+    group.finish();
+    let _ = fs::remove_file(&path);
+}
+
+criterion_group!(benches, storage_io_benchmark);
+criterion_main!(benches);
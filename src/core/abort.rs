@@ -0,0 +1,132 @@
+use std::fmt;
+use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde::{Serialize, Deserialize};
+
+/// Why a run was stopped before its tests finished.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub enum AbortReason {
+    /// A sensor reached the critical temperature.
+    ThermalCutoff {
+        sensor: String,
+        temp_celsius: f32,
+        critical_celsius: f32,
+    },
+    /// The user pressed Ctrl-C.
+    Interrupted,
+}
+
+impl fmt::Display for AbortReason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AbortReason::ThermalCutoff { sensor, temp_celsius, critical_celsius } => write!(
+                f,
+                "Thermal cutoff: {} reached {:.1}°C (critical {:.1}°C)",
+                sensor, temp_celsius, critical_celsius,
+            ),
+            AbortReason::Interrupted => write!(f, "Interrupted by user"),
+        }
+    }
+}
+
+/// A flag shared by the runner, the thermal monitor and every stress loop
+/// that stops all workloads as soon as it is triggered.
+///
+/// Clones share the same state.
+#[derive(Debug, Clone, Default)]
+pub struct AbortSignal {
+    triggered: Arc<AtomicBool>,
+    reason: Arc<Mutex<Option<AbortReason>>>,
+}
+
+impl AbortSignal {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Triggers the signal. Only the first reason is kept.
+    pub fn trigger(&self, reason: AbortReason) {
+        let mut current = self.reason.lock().unwrap();
+        if current.is_none() {
+            *current = Some(reason);
+        }
+        self.triggered.store(true, Ordering::Relaxed);
+    }
+
+    /// Returns `true` once the signal has been triggered.
+    pub fn is_triggered(&self) -> bool {
+        self.triggered.load(Ordering::Relaxed)
+    }
+
+    /// Returns the reason the signal was triggered with.
+    pub fn reason(&self) -> Option<AbortReason> {
+        self.reason.lock().unwrap().clone()
+    }
+
+    /// Sleeps for `duration` or until the signal is triggered.
+    ///
+    /// Returns `true` if the full duration elapsed.
+    pub fn sleep(&self, duration: Duration) -> bool {
+        let deadline = Instant::now() + duration;
+        loop {
+            if self.is_triggered() {
+                return false;
+            }
+            let now = Instant::now();
+            if now >= deadline {
+                return true;
+            }
+            thread::sleep((deadline - now).min(Duration::from_millis(50)));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_trigger_keeps_first_reason() {
+        let signal = AbortSignal::new();
+        let clone = signal.clone();
+        assert!(!signal.is_triggered());
+        assert_eq!(signal.reason(), None);
+
+        clone.trigger(AbortReason::Interrupted);
+        signal.trigger(AbortReason::ThermalCutoff {
+            sensor: "cpu".to_string(),
+            temp_celsius: 99.0,
+            critical_celsius: 90.0,
+        });
+        assert!(signal.is_triggered());
+        assert_eq!(signal.reason(), Some(AbortReason::Interrupted));
+    }
+
+    #[test]
+    fn test_sleep_returns_early_when_triggered() {
+        let signal = AbortSignal::new();
+        assert!(signal.sleep(Duration::from_millis(10)));
+
+        let clone = signal.clone();
+        let trigger = thread::spawn(move || {
+            thread::sleep(Duration::from_millis(20));
+            clone.trigger(AbortReason::Interrupted);
+        });
+        let start = Instant::now();
+        assert!(!signal.sleep(Duration::from_secs(30)));
+        assert!(start.elapsed() < Duration::from_secs(5));
+        trigger.join().unwrap();
+    }
+
+    #[test]
+    fn test_thermal_cutoff_message() {
+        let reason = AbortReason::ThermalCutoff {
+            sensor: "Package id 0".to_string(),
+            temp_celsius: 101.0,
+            critical_celsius: 90.0,
+        };
+        assert_eq!(reason.to_string(), "Thermal cutoff: Package id 0 reached 101.0°C (critical 90.0°C)");
+    }
+}
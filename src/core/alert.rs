@@ -0,0 +1,763 @@
+use std::fmt;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::net::{TcpStream, ToSocketAddrs};
+use std::sync::{mpsc, Arc, OnceLock};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+use rustls::pki_types::ServerName;
+use rustls::{ClientConfig, ClientConnection, RootCertStore, StreamOwned};
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::core::abort::AbortReason;
+use crate::core::config::TestConfig;
+use crate::core::error::{Result, BurnInError};
+use crate::core::hardware;
+use crate::core::runner::TestSuite;
+use crate::core::test::{TestIssue, TestResult, TestStatus, IssueSeverity};
+
+/// SMTP relay used when `alert_email` is set without `alert_smtp_server`.
+pub const DEFAULT_SMTP_SERVER: &str = "localhost:25";
+
+/// Connect, read and write timeout for every delivery attempt.
+const NETWORK_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Wait before the first retry; doubled for every further one.
+const RETRY_DELAY: Duration = Duration::from_secs(2);
+
+/// What an alert is about.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertEvent {
+    CriticalIssue,
+    ThermalCutoff,
+    SuiteComplete,
+}
+
+/// How urgent an alert is.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum AlertLevel {
+    Info,
+    Warning,
+    Critical,
+}
+
+/// A notification sent to every configured channel.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Alert {
+    pub event: AlertEvent,
+    pub level: AlertLevel,
+    pub hostname: String,
+    pub timestamp: String,
+    pub summary: String,
+    pub test: Option<String>,
+    pub details: Value,
+}
+
+impl Alert {
+    fn new(event: AlertEvent, level: AlertLevel, summary: String, test: Option<String>, details: Value) -> Self {
+        Self {
+            event,
+            level,
+            hostname: hardware::detect().system_info.hostname,
+            timestamp: chrono::Utc::now().to_rfc3339(),
+            summary,
+            test,
+            details,
+        }
+    }
+
+    /// A critical issue reported by `test`, or by a health monitor on its
+    /// behalf.
+    pub fn critical_issue(test: &str, issue: &TestIssue) -> Self {
+        Self::new(
+            AlertEvent::CriticalIssue,
+            AlertLevel::Critical,
+            format!("{}: {}", test, issue.message),
+            Some(test.to_string()),
+            json!({
+                "component": issue.component,
+                "message": issue.message,
+                "action": issue.action,
+            }),
+        )
+    }
+
+    /// The run was stopped because a sensor reached its critical temperature.
+    pub fn thermal_cutoff(reason: &AbortReason) -> Self {
+        Self::new(
+            AlertEvent::ThermalCutoff,
+            AlertLevel::Critical,
+            reason.to_string(),
+            None,
+            serde_json::to_value(reason).unwrap_or(Value::Null),
+        )
+    }
+
+    /// The run finished. The alert is critical if the run failed and a
+    /// warning if the overall score is below `threshold`.
+    pub fn suite_complete(suite: &TestSuite, threshold: u8) -> Self {
+        let level = if suite.overall_status == TestStatus::Failed {
+            AlertLevel::Critical
+        } else if suite.overall_score < threshold {
+            AlertLevel::Warning
+        } else {
+            AlertLevel::Info
+        };
+        Self::new(
+            AlertEvent::SuiteComplete,
+            level,
+            format!("Burn-in finished: {:?} with score {}/100", suite.overall_status, suite.overall_score),
+            None,
+            json!({
+                "status": suite.overall_status,
+                "overall_score": suite.overall_score,
+                "score_threshold": threshold,
+                "duration_seconds": suite.duration.as_secs(),
+                "aborted": suite.aborted.as_ref().map(|reason| reason.to_string()),
+                "policy_passed": suite.policy.as_ref().map(|policy| policy.passed),
+                "tests": suite.results.iter().map(|result| json!({
+                    "name": result.name,
+                    "status": result.status,
+                    "score": result.score,
+                    "critical_issues": result.issues.iter()
+                        .filter(|issue| issue.severity == IssueSeverity::Critical)
+                        .count(),
+                })).collect::<Vec<_>>(),
+            }),
+        )
+    }
+
+    /// The JSON document posted to webhooks and attached to emails.
+    pub fn payload(&self) -> Value {
+        let mut payload = json!({ "source": "burnin" });
+        if let (Value::Object(payload), Ok(Value::Object(alert))) = (&mut payload, serde_json::to_value(self)) {
+            payload.extend(alert);
+        }
+        payload
+    }
+
+    fn subject(&self) -> String {
+        format!("[burnin] {} {}: {}", format!("{:?}", self.level).to_uppercase(), self.hostname, self.summary)
+    }
+}
+
+/// A destination for alerts.
+pub trait AlertChannel: Send + Sync {
+    /// Describes the destination in delivery reports.
+    fn describe(&self) -> String;
+
+    /// Delivers one alert, failing on any network or protocol error.
+    fn send(&self, alert: &Alert) -> io::Result<()>;
+}
+
+/// Posts alerts as JSON to an `http://` or `https://` URL.
+#[derive(Debug, Clone, PartialEq)]
+pub struct WebhookChannel {
+    tls: bool,
+    host: String,
+    port: u16,
+    path: String,
+}
+
+impl WebhookChannel {
+    /// Parses `http[s]://host[:port][/path]`. HTTPS servers are verified
+    /// against the Mozilla root certificates built into burnin.
+    pub fn new(url: &str) -> Result<Self> {
+        let invalid = |reason: &str| BurnInError::ConfigError(format!("Invalid alert webhook URL '{}': {}", url, reason));
+
+        let (tls, rest) = match (url.strip_prefix("https://"), url.strip_prefix("http://")) {
+            (Some(rest), _) => (true, rest),
+            (None, Some(rest)) => (false, rest),
+            (None, None) => return Err(invalid("expected an http:// or https:// URL")),
+        };
+        let (authority, path) = match rest.find('/') {
+            Some(slash) => (&rest[..slash], &rest[slash..]),
+            None => (rest, "/"),
+        };
+        let (host, port) = match authority.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| invalid("invalid port"))?),
+            None => (authority, if tls { 443 } else { 80 }),
+        };
+        if host.is_empty() {
+            return Err(invalid("missing host"));
+        }
+
+        Ok(Self { tls, host: host.to_string(), port, path: path.to_string() })
+    }
+
+    fn post(&self, mut stream: impl Read + Write, body: &str) -> io::Result<()> {
+        write!(
+            stream,
+            "POST {} HTTP/1.1\r\nHost: {}:{}\r\nUser-Agent: burnin/{}\r\nContent-Type: application/json\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            self.path, self.host, self.port, env!("CARGO_PKG_VERSION"), body.len(), body,
+        )?;
+        stream.flush()?;
+
+        let mut status_line = String::new();
+        BufReader::new(&mut stream).read_line(&mut status_line)?;
+        let status: u16 = status_line.split_whitespace()
+            .nth(1)
+            .and_then(|code| code.parse().ok())
+            .ok_or_else(|| io::Error::new(io::ErrorKind::InvalidData, format!("invalid HTTP response: {}", status_line.trim())))?;
+        if !(200..300).contains(&status) {
+            return Err(io::Error::other(format!("webhook returned HTTP {}", status)));
+        }
+        Ok(())
+    }
+}
+
+impl AlertChannel for WebhookChannel {
+    fn describe(&self) -> String {
+        let scheme = if self.tls { "https" } else { "http" };
+        format!("webhook {}://{}:{}{}", scheme, self.host, self.port, self.path)
+    }
+
+    fn send(&self, alert: &Alert) -> io::Result<()> {
+        let body = alert.payload().to_string();
+        let stream = connect(&self.host, self.port)?;
+        if !self.tls {
+            return self.post(stream, &body);
+        }
+
+        let name = ServerName::try_from(self.host.clone())
+            .map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))?;
+        let connection = ClientConnection::new(tls_config()?, name).map_err(io::Error::other)?;
+        self.post(StreamOwned::new(connection, stream), &body)
+    }
+}
+
+/// TLS client configuration shared by all webhook deliveries.
+fn tls_config() -> io::Result<Arc<ClientConfig>> {
+    static CONFIG: OnceLock<std::result::Result<Arc<ClientConfig>, String>> = OnceLock::new();
+    CONFIG.get_or_init(|| {
+        let roots = RootCertStore { roots: webpki_roots::TLS_SERVER_ROOTS.to_vec() };
+        ClientConfig::builder_with_provider(Arc::new(rustls::crypto::ring::default_provider()))
+            .with_safe_default_protocol_versions()
+            .map(|builder| Arc::new(builder.with_root_certificates(roots).with_no_client_auth()))
+            .map_err(|e| e.to_string())
+    })
+    .clone()
+    .map_err(io::Error::other)
+}
+
+/// Sends alerts through an SMTP relay that accepts mail without
+/// authentication.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EmailChannel {
+    server: String,
+    from: String,
+    to: Vec<String>,
+}
+
+impl EmailChannel {
+    /// `to` may list several addresses separated by commas.
+    pub fn new(server: &str, from: &str, to: &str) -> Result<Self> {
+        let to: Vec<String> = to.split(',')
+            .map(|address| address.trim().to_string())
+            .filter(|address| !address.is_empty())
+            .collect();
+        if to.is_empty() || to.iter().chain([&from.to_string()]).any(|address| !address.contains('@')) {
+            return Err(BurnInError::ConfigError(format!("Invalid alert email addresses: from '{}' to '{}'", from, to.join(", "))));
+        }
+        Ok(Self { server: server.to_string(), from: from.to_string(), to })
+    }
+}
+
+impl AlertChannel for EmailChannel {
+    fn describe(&self) -> String {
+        format!("email to {} via {}", self.to.join(", "), self.server)
+    }
+
+    fn send(&self, alert: &Alert) -> io::Result<()> {
+        let (host, port) = match self.server.rsplit_once(':') {
+            Some((host, port)) => (host, port.parse().map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, "invalid SMTP port"))?),
+            None => (self.server.as_str(), 25),
+        };
+        let stream = connect(host, port)?;
+        let mut smtp = Smtp { reader: BufReader::new(stream.try_clone()?), writer: stream };
+
+        smtp.expect(220)?;
+        smtp.command(&format!("EHLO {}", alert.hostname), 250)?;
+        smtp.command(&format!("MAIL FROM:<{}>", self.from), 250)?;
+        for to in &self.to {
+            smtp.command(&format!("RCPT TO:<{}>", to), 250)?;
+        }
+        smtp.command("DATA", 354)?;
+
+        let body = format!(
+            "{}\r\n\r\n{}",
+            alert.summary,
+            serde_json::to_string_pretty(&alert.payload()).unwrap_or_default().replace('\n', "\r\n"),
+        );
+        let message = format!(
+            "From: {}\r\nTo: {}\r\nSubject: {}\r\nDate: {}\r\nContent-Type: text/plain; charset=utf-8\r\n\r\n{}",
+            self.from,
+            self.to.join(", "),
+            alert.subject(),
+            chrono::Utc::now().to_rfc2822(),
+            body,
+        );
+        // Lines starting with a dot are escaped so they cannot end the message.
+        let stuffed: Vec<String> = message.split("\r\n")
+            .map(|line| if line.starts_with('.') { format!(".{}", line) } else { line.to_string() })
+            .collect();
+        smtp.command(&format!("{}\r\n.", stuffed.join("\r\n")), 250)?;
+        smtp.command("QUIT", 221)
+    }
+}
+
+struct Smtp {
+    reader: BufReader<TcpStream>,
+    writer: TcpStream,
+}
+
+impl Smtp {
+    fn command(&mut self, line: &str, code: u16) -> io::Result<()> {
+        write!(self.writer, "{}\r\n", line)?;
+        self.writer.flush()?;
+        self.expect(code)
+    }
+
+    /// Reads a possibly multi-line reply and checks its code. 251 is
+    /// accepted wherever 250 is expected.
+    fn expect(&mut self, code: u16) -> io::Result<()> {
+        loop {
+            let mut line = String::new();
+            if self.reader.read_line(&mut line)? == 0 {
+                return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "SMTP server closed the connection"));
+            }
+            let reply: u16 = line.get(..3).and_then(|c| c.parse().ok()).unwrap_or(0);
+            if line.as_bytes().get(3) == Some(&b'-') {
+                continue;
+            }
+            if reply == code || (code == 250 && reply == 251) {
+                return Ok(());
+            }
+            return Err(io::Error::other(format!("SMTP server replied {}", line.trim())));
+        }
+    }
+}
+
+fn connect(host: &str, port: u16) -> io::Result<TcpStream> {
+    let mut last_error = io::Error::new(io::ErrorKind::NotFound, format!("could not resolve {}", host));
+    for address in (host, port).to_socket_addrs()? {
+        match TcpStream::connect_timeout(&address, NETWORK_TIMEOUT) {
+            Ok(stream) => {
+                stream.set_read_timeout(Some(NETWORK_TIMEOUT))?;
+                stream.set_write_timeout(Some(NETWORK_TIMEOUT))?;
+                return Ok(stream);
+            }
+            Err(e) => last_error = e,
+        }
+    }
+    Err(last_error)
+}
+
+/// The outcome of sending an alert to one channel.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Delivery {
+    Sent { channel: String, attempts: u32 },
+    /// Dry-run mode: the payload that would have been sent
+    DryRun { channel: String, payload: String },
+    Failed { channel: String, error: String },
+}
+
+impl fmt::Display for Delivery {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Delivery::Sent { channel, attempts: 1 } => write!(f, "Alert sent to {}", channel),
+            Delivery::Sent { channel, attempts } => write!(f, "Alert sent to {} after {} attempts", channel, attempts),
+            Delivery::DryRun { channel, payload } => write!(f, "Dry run, not sending alert to {}: {}", channel, payload),
+            Delivery::Failed { channel, error } => write!(f, "Failed to send alert to {}: {}", channel, error),
+        }
+    }
+}
+
+/// Sends alerts to every configured channel, retrying failed deliveries.
+pub struct Alerter {
+    channels: Vec<Box<dyn AlertChannel>>,
+    retries: u32,
+    retry_delay: Duration,
+    dry_run: bool,
+    threshold: u8,
+}
+
+impl Alerter {
+    pub fn new(channels: Vec<Box<dyn AlertChannel>>) -> Self {
+        Self {
+            channels,
+            retries: 3,
+            retry_delay: RETRY_DELAY,
+            dry_run: false,
+            threshold: 95,
+        }
+    }
+
+    /// Builds the channels named by `alert_webhook_url` and `alert_email`.
+    /// Returns `None` if neither is set.
+    pub fn from_config(config: &TestConfig) -> Result<Option<Self>> {
+        let mut channels: Vec<Box<dyn AlertChannel>> = Vec::new();
+        if let Some(url) = &config.alert_webhook_url {
+            channels.push(Box::new(WebhookChannel::new(url)?));
+        }
+        if let Some(to) = &config.alert_email {
+            let from = config.alert_email_from.clone().unwrap_or_else(|| {
+                format!("burnin@{}", hardware::detect().system_info.hostname)
+            });
+            let server = config.alert_smtp_server.as_deref().unwrap_or(DEFAULT_SMTP_SERVER);
+            channels.push(Box::new(EmailChannel::new(server, &from, to)?));
+        }
+        if channels.is_empty() {
+            return Ok(None);
+        }
+
+        Ok(Some(Self {
+            retries: config.alert_retries,
+            dry_run: config.alert_dry_run,
+            threshold: config.alert_threshold,
+            ..Self::new(channels)
+        }))
+    }
+
+    /// Sets the number of retries after a failed delivery and the wait before
+    /// the first one.
+    pub fn with_retries(mut self, retries: u32, delay: Duration) -> Self {
+        self.retries = retries;
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Reports alerts instead of sending them.
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Overall score below which the completion alert is a warning.
+    pub fn threshold(&self) -> u8 {
+        self.threshold
+    }
+
+    /// Sends `alert` to every channel.
+    pub fn send(&self, alert: &Alert) -> Vec<Delivery> {
+        self.channels.iter()
+            .map(|channel| {
+                let name = channel.describe();
+                if self.dry_run {
+                    return Delivery::DryRun { channel: name, payload: alert.payload().to_string() };
+                }
+
+                let mut delay = self.retry_delay;
+                let mut attempts = 0;
+                loop {
+                    attempts += 1;
+                    match channel.send(alert) {
+                        Ok(()) => return Delivery::Sent { channel: name, attempts },
+                        Err(e) if attempts > self.retries => {
+                            return Delivery::Failed { channel: name, error: e.to_string() };
+                        }
+                        Err(_) => {
+                            thread::sleep(delay);
+                            delay *= 2;
+                        }
+                    }
+                }
+            })
+            .collect()
+    }
+
+    /// An alert for every critical issue `test` reported.
+    pub fn issue_alerts(test: &str, issues: &[TestIssue]) -> Vec<Alert> {
+        issues.iter()
+            .filter(|issue| issue.severity == IssueSeverity::Critical)
+            .map(|issue| Alert::critical_issue(test, issue))
+            .collect()
+    }
+
+    /// Alerts for a thermal cutoff, if the run had one, and for the end of
+    /// the run.
+    pub fn suite_alerts(&self, suite: &TestSuite) -> Vec<Alert> {
+        let mut alerts = Vec::new();
+        if let Some(reason @ AbortReason::ThermalCutoff { .. }) = &suite.aborted {
+            alerts.push(Alert::thermal_cutoff(reason));
+        }
+        alerts.push(Alert::suite_complete(suite, self.threshold));
+        alerts
+    }
+
+    /// Alerts for every critical issue of a finished test.
+    pub fn test_finished(&self, result: &TestResult) -> Vec<Delivery> {
+        Self::issue_alerts(&result.name, &result.issues).iter()
+            .flat_map(|alert| self.send(alert))
+            .collect()
+    }
+
+    /// Alerts for a thermal cutoff, if the run had one, and for the end of
+    /// the run.
+    pub fn suite_finished(&self, suite: &TestSuite) -> Vec<Delivery> {
+        self.suite_alerts(suite).iter()
+            .flat_map(|alert| self.send(alert))
+            .collect()
+    }
+}
+
+/// Sends alerts from a background thread, so that slow or unreachable
+/// channels and their retries never hold up the tests.
+pub struct AlertQueue {
+    alerter: Arc<Alerter>,
+    sender: mpsc::Sender<Alert>,
+    worker: JoinHandle<Vec<Delivery>>,
+}
+
+impl AlertQueue {
+    pub fn start(alerter: Arc<Alerter>) -> Self {
+        let (sender, receiver) = mpsc::channel::<Alert>();
+        let worker = {
+            let alerter = alerter.clone();
+            thread::spawn(move || receiver.iter().flat_map(|alert| alerter.send(&alert)).collect())
+        };
+        Self { alerter, sender, worker }
+    }
+
+    /// Queues an alert for every critical issue `test` reported.
+    pub fn issues_found(&self, test: &str, issues: &[TestIssue]) {
+        for alert in Alerter::issue_alerts(test, issues) {
+            let _ = self.sender.send(alert);
+        }
+    }
+
+    /// Queues alerts for the critical issues of a finished test.
+    pub fn test_finished(&self, result: &TestResult) {
+        self.issues_found(&result.name, &result.issues);
+    }
+
+    /// Queues the alerts for the end of the run.
+    pub fn suite_finished(&self, suite: &TestSuite) {
+        for alert in self.alerter.suite_alerts(suite) {
+            let _ = self.sender.send(alert);
+        }
+    }
+
+    /// Waits until every queued alert has been delivered or has failed, and
+    /// returns the outcomes in the order the alerts were queued.
+    pub fn finish(self) -> Vec<Delivery> {
+        drop(self.sender);
+        self.worker.join().unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use std::net::TcpListener;
+    use std::time::Duration;
+
+    /// Reads from a stand-in server's connection until `end` arrives.
+    fn read_until(stream: &mut TcpStream, end: &str) -> String {
+        let mut received = Vec::new();
+        let mut buffer = [0u8; 4096];
+        while !String::from_utf8_lossy(&received).contains(end) {
+            let n = stream.read(&mut buffer).unwrap();
+            if n == 0 {
+                break;
+            }
+            received.extend_from_slice(&buffer[..n]);
+        }
+        String::from_utf8_lossy(&received).into_owned()
+    }
+
+    /// Reads an HTTP request, headers and body.
+    fn read_request(stream: &mut TcpStream) -> String {
+        let mut request = read_until(stream, "\r\n\r\n");
+        let body_start = request.find("\r\n\r\n").unwrap() + 4;
+        let length: usize = request.lines()
+            .find_map(|line| line.strip_prefix("Content-Length: "))
+            .and_then(|length| length.parse().ok())
+            .unwrap();
+        let mut buffer = [0u8; 4096];
+        while request.len() < body_start + length {
+            let n = stream.read(&mut buffer).unwrap();
+            assert!(n > 0, "connection closed mid-request");
+            request += &String::from_utf8_lossy(&buffer[..n]);
+        }
+        request
+    }
+
+    fn failed_result() -> TestResult {
+        TestResult {
+            name: "memory_validation".to_string(),
+            status: TestStatus::Failed,
+            score: 0,
+            duration: Duration::from_secs(30),
+            metrics: json!({}),
+            issues: vec![TestIssue {
+                component: "memory".to_string(),
+                severity: IssueSeverity::Critical,
+                message: "Memory errors detected (3 errors)".to_string(),
+                action: None,
+            }],
+            deductions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_webhook_url() {
+        let channel = WebhookChannel::new("http://alerts.local:8080/hooks/burnin").unwrap();
+        assert_eq!(channel.describe(), "webhook http://alerts.local:8080/hooks/burnin");
+        assert_eq!(WebhookChannel::new("http://alerts.local").unwrap().describe(), "webhook http://alerts.local:80/");
+        assert_eq!(WebhookChannel::new("https://hooks.example.com/x").unwrap().describe(), "webhook https://hooks.example.com:443/x");
+        assert!(WebhookChannel::new("ftp://hooks.example.com/x").is_err());
+        assert!(WebhookChannel::new("http://:80/").is_err());
+        assert!(EmailChannel::new("localhost:25", "burnin@host", "ops").is_err());
+
+        let mut config = TestConfig::default();
+        assert!(Alerter::from_config(&config).unwrap().is_none());
+        config.alert_webhook_url = Some("https://hooks.example.com/x".to_string());
+        assert!(Alerter::from_config(&config).unwrap().is_some());
+        config.alert_webhook_url = Some("hooks.example.com/x".to_string());
+        assert!(Alerter::from_config(&config).is_err());
+    }
+
+    #[test]
+    fn test_webhook_retries_until_accepted() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let mut requests = Vec::new();
+            for status in ["503 Service Unavailable", "200 OK"] {
+                let (mut stream, _) = listener.accept().unwrap();
+                let request = read_request(&mut stream);
+                write!(stream, "HTTP/1.1 {}\r\nContent-Length: 0\r\n\r\n", status).unwrap();
+                requests.push(request);
+            }
+            requests
+        });
+
+        let channel = WebhookChannel::new(&format!("http://127.0.0.1:{}/hook", port)).unwrap();
+        let alerter = Alerter::new(vec![Box::new(channel)]).with_retries(2, Duration::from_millis(10));
+        let deliveries = alerter.test_finished(&failed_result());
+
+        assert_eq!(deliveries.len(), 1);
+        assert!(matches!(deliveries[0], Delivery::Sent { attempts: 2, .. }));
+        let requests = server.join().unwrap();
+        assert!(requests[1].starts_with("POST /hook HTTP/1.1\r\n"));
+        let body = &requests[1][requests[1].find("\r\n\r\n").unwrap() + 4..];
+        let payload: Value = serde_json::from_str(body).unwrap();
+        assert_eq!(payload["source"], "burnin");
+        assert_eq!(payload["event"], "critical_issue");
+        assert_eq!(payload["level"], "critical");
+        assert_eq!(payload["test"], "memory_validation");
+    }
+
+    #[test]
+    fn test_email_through_smtp_stand_in() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            let mut transcript = String::new();
+            write!(stream, "220 stand-in ESMTP\r\n").unwrap();
+            for reply in ["250-stand-in\r\n250 8BITMIME", "250 OK", "250 OK", "250 OK", "354 Go ahead"] {
+                transcript += &read_until(&mut stream, "\r\n");
+                write!(stream, "{}\r\n", reply).unwrap();
+            }
+            transcript += &read_until(&mut stream, "\r\n.\r\n");
+            write!(stream, "250 Queued\r\n").unwrap();
+            transcript += &read_until(&mut stream, "\r\n");
+            write!(stream, "221 Bye\r\n").unwrap();
+            transcript
+        });
+
+        let channel = EmailChannel::new(&format!("127.0.0.1:{}", port), "burnin@rack12", "ops@example.com, oncall@example.com").unwrap();
+        let deliveries = Alerter::new(vec![Box::new(channel)]).test_finished(&failed_result());
+
+        assert!(matches!(deliveries[0], Delivery::Sent { attempts: 1, .. }), "{:?}", deliveries);
+        let transcript = server.join().unwrap();
+        assert!(transcript.contains("MAIL FROM:<burnin@rack12>\r\n"));
+        assert!(transcript.contains("RCPT TO:<oncall@example.com>\r\n"));
+        assert!(transcript.contains("Subject: [burnin] CRITICAL "));
+        assert!(transcript.contains("\"event\": \"critical_issue\""));
+        assert!(transcript.ends_with("QUIT\r\n"));
+    }
+
+    #[test]
+    fn test_dry_run_and_failed_delivery() {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        drop(listener);
+        let url = format!("http://127.0.0.1:{}/", port);
+
+        let mut suite = TestSuite::new();
+        suite.results.push(failed_result());
+        suite.aborted = Some(AbortReason::ThermalCutoff {
+            sensor: "Package id 0".to_string(),
+            temp_celsius: 101.0,
+            critical_celsius: 100.0,
+        });
+        suite.finalize();
+
+        let dry_run = Alerter::new(vec![Box::new(WebhookChannel::new(&url).unwrap())]).with_dry_run(true);
+        let deliveries = dry_run.suite_finished(&suite);
+        let events: Vec<Value> = deliveries.iter()
+            .map(|delivery| match delivery {
+                Delivery::DryRun { payload, .. } => serde_json::from_str::<Value>(payload).unwrap()["event"].clone(),
+                other => panic!("unexpected delivery {:?}", other),
+            })
+            .collect();
+        assert_eq!(events, [json!("thermal_cutoff"), json!("suite_complete")]);
+
+        let failing = Alerter::new(vec![Box::new(WebhookChannel::new(&url).unwrap())]).with_retries(1, Duration::from_millis(1));
+        let deliveries = failing.send(&Alert::suite_complete(&suite, 95));
+        assert!(matches!(&deliveries[0], Delivery::Failed { .. }));
+        assert!(deliveries[0].to_string().starts_with("Failed to send alert to webhook"));
+    }
+
+    #[test]
+    fn test_alert_queue() {
+        let url = "http://127.0.0.1:9/";
+        let alerter = Alerter::new(vec![Box::new(WebhookChannel::new(url).unwrap())]).with_dry_run(true);
+        let queue = AlertQueue::start(Arc::new(alerter));
+
+        queue.test_finished(&failed_result());
+        let edac = TestIssue {
+            component: "memory".to_string(),
+            severity: IssueSeverity::Critical,
+            message: "1 uncorrectable memory error on DIMM_A1".to_string(),
+            action: None,
+        };
+        queue.issues_found("memory_validation", &[edac]);
+        let mut suite = TestSuite::new();
+        suite.finalize();
+        queue.suite_finished(&suite);
+
+        let summaries: Vec<String> = queue.finish().iter()
+            .map(|delivery| match delivery {
+                Delivery::DryRun { payload, .. } => serde_json::from_str::<Value>(payload).unwrap()["summary"].as_str().unwrap().to_string(),
+                other => panic!("unexpected delivery {:?}", other),
+            })
+            .collect();
+        assert_eq!(summaries.len(), 3);
+        assert_eq!(summaries[0], "memory_validation: Memory errors detected (3 errors)");
+        assert_eq!(summaries[1], "memory_validation: 1 uncorrectable memory error on DIMM_A1");
+        assert!(summaries[2].starts_with("Burn-in finished: Failed"));
+    }
+
+    #[test]
+    fn test_webhook_tls_handshake_failure() {
+        // A plain HTTP server cannot complete a TLS handshake.
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let port = listener.local_addr().unwrap().port();
+        let server = thread::spawn(move || {
+            let (mut stream, _) = listener.accept().unwrap();
+            write!(stream, "HTTP/1.1 200 OK\r\nContent-Length: 0\r\n\r\n").unwrap();
+        });
+
+        let channel = WebhookChannel::new(&format!("https://localhost:{}/hook", port)).unwrap();
+        let deliveries = Alerter::new(vec![Box::new(channel)]).with_retries(0, Duration::ZERO).test_finished(&failed_result());
+        assert!(matches!(&deliveries[0], Delivery::Failed { .. }), "{:?}", deliveries);
+        server.join().unwrap();
+    }
+}
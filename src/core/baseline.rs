@@ -0,0 +1,375 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+
+use crate::core::error::{Result, BurnInError};
+use crate::core::hardware::HardwareInfo;
+use crate::core::test::{TestResult, TestIssue, IssueSeverity};
+
+/// File the `baseline record` command writes to when no path is given.
+pub const DEFAULT_BASELINE_FILE: &str = "burnin-baselines.json";
+
+/// Default slack around a recorded range before a value counts as a deviation.
+pub const DEFAULT_TOLERANCE_PERCENT: f64 = 15.0;
+
+/// Whether larger or smaller values of a metric indicate better hardware.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Direction {
+    HigherIsBetter,
+    LowerIsBetter,
+}
+
+/// Performance metrics that are recorded into and scored against baselines,
+/// as `(test, metric, direction)`.
+pub const TRACKED_METRICS: &[(&str, &str, Direction)] = &[
+    ("cpu_stress", "instructions_per_second", Direction::HigherIsBetter),
+    ("memory_validation", "bandwidth_mbps", Direction::HigherIsBetter),
+    ("memory_validation", "latency_ns", Direction::LowerIsBetter),
+    ("storage_io", "sequential_read_mbps", Direction::HigherIsBetter),
+    ("storage_io", "sequential_write_mbps", Direction::HigherIsBetter),
+    ("storage_io", "random_read_iops", Direction::HigherIsBetter),
+    ("storage_io", "random_write_iops", Direction::HigherIsBetter),
+    ("network", "download_mbps", Direction::HigherIsBetter),
+    ("network", "upload_mbps", Direction::HigherIsBetter),
+    ("network", "latency_ms", Direction::LowerIsBetter),
+];
+
+/// Identifies a class of machine for baseline lookups.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HardwareFingerprint {
+    pub cpu_model: String,
+    pub logical_cores: u32,
+    pub memory_gb: u64,
+    pub disk_models: Vec<String>,
+}
+
+impl HardwareFingerprint {
+    /// Builds the fingerprint of the machine described by `hardware`.
+    ///
+    /// Disk models come from the detected storage devices, or from sysfs when
+    /// none were detected. Devices without a model, such as LVM, LUKS or
+    /// overlay mounts reported as "Unknown", are left out so that they do not
+    /// match unrelated machines.
+    pub fn from_hardware(hardware: &HardwareInfo) -> Self {
+        let mut disk_models: Vec<String> = hardware.storage_devices.iter()
+            .map(|device| device.model.trim().to_string())
+            .filter(|model| !model.is_empty() && model != "Unknown")
+            .collect();
+        if disk_models.is_empty() {
+            disk_models = read_disk_models(Path::new("/sys/block"));
+        }
+        disk_models.sort();
+        disk_models.dedup();
+
+        Self {
+            cpu_model: hardware.cpu_info.model_name.trim().to_string(),
+            logical_cores: hardware.cpu_info.logical_cores,
+            memory_gb: (hardware.memory_info.total_bytes as f64 / 1_073_741_824.0).round() as u64,
+            disk_models,
+        }
+    }
+}
+
+/// Reads the model of every block device under a `/sys/block`-style directory.
+pub fn read_disk_models(sys_block: &Path) -> Vec<String> {
+    let Ok(entries) = fs::read_dir(sys_block) else {
+        return Vec::new();
+    };
+
+    let mut models: Vec<String> = entries.flatten()
+        .filter_map(|entry| fs::read_to_string(entry.path().join("device/model")).ok())
+        .map(|model| model.trim().to_string())
+        .filter(|model| !model.is_empty())
+        .collect();
+    models.sort();
+    models.dedup();
+    models
+}
+
+/// The values a metric took across recorded golden runs.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MetricRange {
+    pub min: f64,
+    pub max: f64,
+    pub mean: f64,
+    pub samples: u32,
+}
+
+impl MetricRange {
+    fn new(value: f64) -> Self {
+        Self { min: value, max: value, mean: value, samples: 1 }
+    }
+
+    fn add(&mut self, value: f64) {
+        self.min = self.min.min(value);
+        self.max = self.max.max(value);
+        self.mean = (self.mean * self.samples as f64 + value) / (self.samples + 1) as f64;
+        self.samples += 1;
+    }
+
+    /// Returns the recorded range widened by `tolerance_percent` on each side.
+    pub fn expected(&self, tolerance_percent: f64) -> (f64, f64) {
+        let slack = tolerance_percent / 100.0;
+        (self.min * (1.0 - slack), self.max * (1.0 + slack))
+    }
+}
+
+/// Expected metric ranges for one class of machine.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Baseline {
+    pub fingerprint: HardwareFingerprint,
+    pub recorded_at: String,
+    /// Ranges keyed by `test.metric`.
+    pub metrics: BTreeMap<String, MetricRange>,
+}
+
+impl Baseline {
+    /// Returns the expected range of `metric` reported by `test`.
+    pub fn range(&self, test: &str, metric: &str) -> Option<&MetricRange> {
+        self.metrics.get(&metric_key(test, metric))
+    }
+
+    /// Compares the tracked metrics of `result` against this baseline.
+    ///
+    /// Every metric that is worse than its expected range adds an issue and
+    /// lowers the score in proportion to the deviation.
+    pub fn apply(&self, result: &mut TestResult, tolerance_percent: f64) {
+        for &(test, metric, direction) in TRACKED_METRICS {
+            if test != result.name {
+                continue;
+            }
+            let (Some(range), Some(value)) = (self.range(test, metric), result.metrics[metric].as_f64()) else {
+                continue;
+            };
+
+            let (low, high) = range.expected(tolerance_percent);
+            let deviation = match direction {
+                Direction::HigherIsBetter if value < low && low > 0.0 => (low - value) / low * 100.0,
+                Direction::LowerIsBetter if value > high && high > 0.0 => (value - high) / high * 100.0,
+                _ => continue,
+            };
+
+            result.deduct(
+                format!("{} is {:.0}% off baseline", metric, deviation),
+                (deviation / 2.0).clamp(1.0, 20.0) as u8,
+            );
+            result.issues.push(TestIssue {
+                component: result.name.clone(),
+                severity: if deviation >= 25.0 { IssueSeverity::High } else { IssueSeverity::Medium },
+                message: format!(
+                    "{} is {:.0}% {} baseline ({:.2}, expected {:.2} to {:.2})",
+                    metric,
+                    deviation,
+                    if direction == Direction::HigherIsBetter { "below" } else { "above" },
+                    value,
+                    low,
+                    high,
+                ),
+                action: Some("Compare against a known-good machine of the same model".to_string()),
+            });
+        }
+    }
+}
+
+fn metric_key(test: &str, metric: &str) -> String {
+    format!("{}.{}", test, metric)
+}
+
+fn is_storage_metric(key: &str) -> bool {
+    key.starts_with("storage_io.")
+}
+
+/// A collection of baselines stored as JSON.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct BaselineDb {
+    pub baselines: Vec<Baseline>,
+}
+
+impl BaselineDb {
+    /// Loads a database, returning an empty one if the file does not exist.
+    pub fn load(path: &Path) -> Result<Self> {
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+        let contents = fs::read_to_string(path)?;
+        serde_json::from_str(&contents)
+            .map_err(|e| BurnInError::ConfigError(format!("Failed to parse baseline file {}: {}", path.display(), e)))
+    }
+
+    /// Writes the database to `path`.
+    pub fn save(&self, path: &Path) -> Result<()> {
+        let contents = serde_json::to_string_pretty(self)
+            .map_err(|e| BurnInError::UnexpectedError(format!("Failed to serialize baselines: {}", e)))?;
+        fs::write(path, contents)?;
+        Ok(())
+    }
+
+    /// Adds the tracked metrics of a golden run to the baseline of `fingerprint`.
+    pub fn record(&mut self, fingerprint: &HardwareFingerprint, results: &[TestResult]) {
+        let recorded_at = chrono::Utc::now().to_rfc3339();
+        let index = match self.baselines.iter().position(|b| b.fingerprint == *fingerprint) {
+            Some(index) => index,
+            None => {
+                self.baselines.push(Baseline {
+                    fingerprint: fingerprint.clone(),
+                    recorded_at: recorded_at.clone(),
+                    metrics: BTreeMap::new(),
+                });
+                self.baselines.len() - 1
+            }
+        };
+
+        let baseline = &mut self.baselines[index];
+        baseline.recorded_at = recorded_at;
+
+        for result in results {
+            for &(test, metric, _) in TRACKED_METRICS {
+                if test != result.name {
+                    continue;
+                }
+                let Some(value) = result.metrics[metric].as_f64() else {
+                    continue;
+                };
+                baseline.metrics.entry(metric_key(test, metric))
+                    .and_modify(|range| range.add(value))
+                    .or_insert_with(|| MetricRange::new(value));
+            }
+        }
+    }
+
+    /// Finds the expected ranges for `fingerprint`.
+    ///
+    /// An exact fingerprint match wins. Otherwise CPU, memory and network
+    /// ranges come from a baseline with the same CPU model, and storage ranges
+    /// from a baseline sharing a disk model.
+    pub fn baseline_for(&self, fingerprint: &HardwareFingerprint) -> Option<Baseline> {
+        if let Some(baseline) = self.baselines.iter().find(|b| b.fingerprint == *fingerprint) {
+            return Some(baseline.clone());
+        }
+
+        let mut metrics = BTreeMap::new();
+        let mut recorded_at = String::new();
+
+        if let Some(baseline) = self.baselines.iter().find(|b| b.fingerprint.cpu_model == fingerprint.cpu_model) {
+            metrics.extend(baseline.metrics.iter()
+                .filter(|(key, _)| !is_storage_metric(key))
+                .map(|(key, range)| (key.clone(), range.clone())));
+            recorded_at = baseline.recorded_at.clone();
+        }
+
+        let shares_disk = |b: &&Baseline| b.fingerprint.disk_models.iter().any(|m| fingerprint.disk_models.contains(m));
+        if let Some(baseline) = self.baselines.iter().find(shares_disk) {
+            metrics.extend(baseline.metrics.iter()
+                .filter(|(key, _)| is_storage_metric(key))
+                .map(|(key, range)| (key.clone(), range.clone())));
+            recorded_at = recorded_at.max(baseline.recorded_at.clone());
+        }
+
+        if metrics.is_empty() {
+            return None;
+        }
+
+        Some(Baseline {
+            fingerprint: fingerprint.clone(),
+            recorded_at,
+            metrics,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use crate::core::test::TestStatus;
+
+    fn fingerprint(cpu: &str, disk: &str) -> HardwareFingerprint {
+        HardwareFingerprint {
+            cpu_model: cpu.to_string(),
+            logical_cores: 8,
+            memory_gb: 16,
+            disk_models: vec![disk.to_string()],
+        }
+    }
+
+    fn result(name: &str, metrics: serde_json::Value) -> TestResult {
+        TestResult {
+            name: name.to_string(),
+            status: TestStatus::Completed,
+            score: 100,
+            duration: Duration::from_secs(60),
+            metrics,
+            issues: Vec::new(),
+            deductions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_record_accumulates_ranges() {
+        let mut db = BaselineDb::default();
+        let fp = fingerprint("Xeon", "SSD");
+        db.record(&fp, &[result("memory_validation", serde_json::json!({"bandwidth_mbps": 10000.0, "latency_ns": 80.0}))]);
+        db.record(&fp, &[result("memory_validation", serde_json::json!({"bandwidth_mbps": 12000.0, "latency_ns": 90.0}))]);
+
+        assert_eq!(db.baselines.len(), 1);
+        let range = db.baselines[0].range("memory_validation", "bandwidth_mbps").unwrap();
+        assert_eq!((range.min, range.max, range.mean, range.samples), (10000.0, 12000.0, 11000.0, 2));
+        assert!(db.baselines[0].range("memory_validation", "memory_errors").is_none());
+    }
+
+    #[test]
+    fn test_apply_flags_slow_metrics() {
+        let mut db = BaselineDb::default();
+        let fp = fingerprint("Xeon", "SSD");
+        db.record(&fp, &[result("memory_validation", serde_json::json!({"bandwidth_mbps": 10000.0, "latency_ns": 80.0}))]);
+        let baseline = db.baseline_for(&fp).unwrap();
+
+        let mut healthy = result("memory_validation", serde_json::json!({"bandwidth_mbps": 9000.0, "latency_ns": 85.0}));
+        baseline.apply(&mut healthy, DEFAULT_TOLERANCE_PERCENT);
+        assert_eq!(healthy.score, 100);
+        assert!(healthy.issues.is_empty());
+
+        let mut slow = result("memory_validation", serde_json::json!({"bandwidth_mbps": 4250.0, "latency_ns": 184.0}));
+        baseline.apply(&mut slow, DEFAULT_TOLERANCE_PERCENT);
+        assert_eq!(slow.issues.len(), 2);
+        assert_eq!(slow.issues[0].severity, IssueSeverity::High);
+        assert!(slow.issues[0].message.starts_with("bandwidth_mbps is 50% below baseline"));
+        assert!(slow.issues[1].message.starts_with("latency_ns is 100% above baseline"));
+        assert_eq!(slow.score, 60);
+    }
+
+    #[test]
+    fn test_baseline_for_falls_back_per_component() {
+        let mut db = BaselineDb::default();
+        db.record(&fingerprint("Xeon", "SSD A"), &[
+            result("cpu_stress", serde_json::json!({"instructions_per_second": 5000})),
+            result("storage_io", serde_json::json!({"sequential_read_mbps": 500.0})),
+        ]);
+        db.record(&fingerprint("EPYC", "NVMe B"), &[
+            result("cpu_stress", serde_json::json!({"instructions_per_second": 9000})),
+            result("storage_io", serde_json::json!({"sequential_read_mbps": 3000.0})),
+        ]);
+
+        let baseline = db.baseline_for(&fingerprint("Xeon", "NVMe B")).unwrap();
+        assert_eq!(baseline.range("cpu_stress", "instructions_per_second").unwrap().max, 5000.0);
+        assert_eq!(baseline.range("storage_io", "sequential_read_mbps").unwrap().max, 3000.0);
+
+        assert!(db.baseline_for(&fingerprint("ARM", "eMMC")).is_none());
+    }
+
+    #[test]
+    fn test_save_and_load() {
+        let path = std::env::temp_dir().join(format!("burnin-baselines-{}.json", std::process::id()));
+        let mut db = BaselineDb::default();
+        db.record(&fingerprint("Xeon", "SSD"), &[result("network", serde_json::json!({"download_mbps": 900.0}))]);
+        db.save(&path).unwrap();
+
+        let loaded = BaselineDb::load(&path).unwrap();
+        assert_eq!(loaded.baselines.len(), 1);
+        assert_eq!(loaded.baselines[0].fingerprint, fingerprint("Xeon", "SSD"));
+        fs::remove_file(&path).unwrap();
+
+        assert!(BaselineDb::load(&path).unwrap().baselines.is_empty());
+    }
+}
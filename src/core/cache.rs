@@ -0,0 +1,122 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+/// Location of the cache topology of the first CPU in sysfs.
+pub const SYSFS_CPU0_CACHE: &str = "/sys/devices/system/cpu/cpu0/cache";
+
+/// Reads the cache sizes of the first CPU from sysfs.
+pub fn detect_cache_sizes() -> HashMap<String, u64> {
+    read_cache_sizes(Path::new(SYSFS_CPU0_CACHE))
+}
+
+/// Reads the cache sizes from a `cpuN/cache` directory.
+///
+/// Each `index*` entry becomes one map entry keyed by level and type, e.g.
+/// `L1d`, `L1i`, `L2` and `L3`, with the size in bytes.
+pub fn read_cache_sizes(cache_dir: &Path) -> HashMap<String, u64> {
+    let mut sizes = HashMap::new();
+
+    let entries = match fs::read_dir(cache_dir) {
+        Ok(entries) => entries,
+        Err(_) => return sizes,
+    };
+
+    for entry in entries.flatten() {
+        let path = entry.path();
+        let is_index = path.file_name()
+            .and_then(|name| name.to_str())
+            .is_some_and(|name| name.starts_with("index"));
+        if !is_index {
+            continue;
+        }
+
+        let read = |file: &str| fs::read_to_string(path.join(file)).ok().map(|s| s.trim().to_string());
+        let (Some(level), Some(cache_type), Some(size)) = (read("level"), read("type"), read("size")) else {
+            continue;
+        };
+        let Some(size_bytes) = parse_cache_size(&size) else {
+            continue;
+        };
+
+        let suffix = match cache_type.as_str() {
+            "Data" => "d",
+            "Instruction" => "i",
+            _ => "",
+        };
+        sizes.insert(format!("L{}{}", level, suffix), size_bytes);
+    }
+
+    sizes
+}
+
+/// Parses a sysfs cache size such as `48K`, `2048K` or `32M`.
+pub fn parse_cache_size(size: &str) -> Option<u64> {
+    let size = size.trim();
+    let (digits, multiplier) = match size.chars().last()? {
+        'K' | 'k' => (&size[..size.len() - 1], 1024),
+        'M' | 'm' => (&size[..size.len() - 1], 1024 * 1024),
+        'G' | 'g' => (&size[..size.len() - 1], 1024 * 1024 * 1024),
+        _ => (size, 1),
+    };
+    digits.parse::<u64>().ok().map(|value| value * multiplier)
+}
+
+/// Returns the data cache size of the given level, if known.
+///
+/// Level 1 is split into data and instruction caches; higher levels are
+/// usually unified.
+pub fn data_cache_size(sizes: &HashMap<String, u64>, level: u8) -> Option<u64> {
+    sizes.get(&format!("L{}d", level))
+        .or_else(|| sizes.get(&format!("L{}", level)))
+        .copied()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::fixture_dir;
+
+    fn write_index(root: &Path, index: usize, level: &str, cache_type: &str, size: &str) {
+        let dir = root.join(format!("index{}", index));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("level"), format!("{}\n", level)).unwrap();
+        fs::write(dir.join("type"), format!("{}\n", cache_type)).unwrap();
+        fs::write(dir.join("size"), format!("{}\n", size)).unwrap();
+    }
+
+    #[test]
+    fn test_parse_cache_size() {
+        assert_eq!(parse_cache_size("48K"), Some(48 * 1024));
+        assert_eq!(parse_cache_size("32M"), Some(32 * 1024 * 1024));
+        assert_eq!(parse_cache_size("512"), Some(512));
+        assert_eq!(parse_cache_size("K"), None);
+        assert_eq!(parse_cache_size(""), None);
+    }
+
+    #[test]
+    fn test_read_cache_sizes() {
+        let root = fixture_dir("cache-sysfs");
+        write_index(&root, 0, "1", "Data", "48K");
+        write_index(&root, 1, "1", "Instruction", "32K");
+        write_index(&root, 2, "2", "Unified", "2048K");
+        write_index(&root, 3, "3", "Unified", "32768K");
+        fs::write(root.join("uevent"), "").unwrap();
+
+        let sizes = read_cache_sizes(&root);
+        assert_eq!(sizes.len(), 4);
+        assert_eq!(sizes["L1d"], 48 * 1024);
+        assert_eq!(sizes["L1i"], 32 * 1024);
+        assert_eq!(sizes["L2"], 2 * 1024 * 1024);
+        assert_eq!(sizes["L3"], 32 * 1024 * 1024);
+        assert_eq!(data_cache_size(&sizes, 1), Some(48 * 1024));
+        assert_eq!(data_cache_size(&sizes, 3), Some(32 * 1024 * 1024));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_read_cache_sizes_missing_dir() {
+        assert!(read_cache_sizes(Path::new("/nonexistent/burnin/cache")).is_empty());
+    }
+}
@@ -0,0 +1,293 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::hardware::ResourceLimits;
+
+/// Mount point of the cgroup filesystems.
+pub const CGROUP_ROOT: &str = "/sys/fs/cgroup";
+
+/// The cgroup membership of the running process.
+pub const PROC_SELF_CGROUP: &str = "/proc/self/cgroup";
+
+/// The CPUs the kernel has brought online.
+pub const CPU_ONLINE: &str = "/sys/devices/system/cpu/online";
+
+/// Memory limits at or above this are cgroup v1's way of saying "unlimited".
+const V1_UNLIMITED: u64 = 1 << 62;
+
+/// Reads the CPU and memory limits of the running process's cgroup.
+///
+/// A cpuset covering every online CPU is not reported as a limit.
+pub fn read_limits() -> ResourceLimits {
+    let mut limits = read_limits_from(Path::new(PROC_SELF_CGROUP), Path::new(CGROUP_ROOT));
+    let online = read_trimmed(Path::new(CPU_ONLINE)).and_then(|list| parse_cpu_list(&list));
+    if let (Some(cpus), Some(online)) = (limits.cpuset_cpus, online) {
+        if cpus >= online {
+            limits.cpuset_cpus = None;
+        }
+    }
+    limits
+}
+
+/// Number of CPUs this process can keep busy: the online CPUs, reduced to
+/// the cgroup's cpuset and CPU quota.
+pub fn available_cpus() -> usize {
+    read_limits().effective_cpus(num_cpus::get())
+}
+
+/// Bytes this process can still allocate: the host's available memory,
+/// reduced to what is left below the cgroup's memory limit.
+pub fn available_memory(host_available: u64) -> u64 {
+    read_limits().available_memory(host_available)
+}
+
+/// Reads the limits of the cgroup listed in `proc_cgroup` from the
+/// hierarchies mounted under `cgroup_root`.
+///
+/// Controllers mounted as cgroup v1 hierarchies are preferred over the
+/// unified (v2) hierarchy, which covers hybrid setups. Each limit is the
+/// tightest one found between the process's cgroup and the hierarchy root.
+/// When the cgroup path does not exist under the mount, as inside a container
+/// without a cgroup namespace, the mount root is used instead.
+pub fn read_limits_from(proc_cgroup: &Path, cgroup_root: &Path) -> ResourceLimits {
+    let memberships = fs::read_to_string(proc_cgroup)
+        .map(|contents| parse_memberships(&contents))
+        .unwrap_or_default();
+    let mut limits = ResourceLimits::default();
+
+
+    if let Some(dirs) = controller_dirs(&memberships, cgroup_root, "cpu") {
+        limits.cpu_quota_cores = min_over(&dirs, |dir| {
+            if dirs.version == 2 {
+                parse_cpu_max(&read_trimmed(&dir.join("cpu.max"))?)
+            } else {
+                let quota: i64 = read_trimmed(&dir.join("cpu.cfs_quota_us"))?.parse().ok()?;
+                let period: i64 = read_trimmed(&dir.join("cpu.cfs_period_us"))?.parse().ok()?;
+                (quota > 0 && period > 0).then(|| quota as f64 / period as f64)
+            }
+        });
+    }
+
+    if let Some(dirs) = controller_dirs(&memberships, cgroup_root, "cpuset") {
+        let files: &[&str] = if dirs.version == 2 {
+            &["cpuset.cpus.effective", "cpuset.cpus"]
+        } else {
+            &["cpuset.effective_cpus", "cpuset.cpus"]
+        };
+        let count = |dir: &PathBuf| {
+            files.iter()
+                .find_map(|file| read_trimmed(&dir.join(file)))
+                .and_then(|list| parse_cpu_list(&list))
+        };
+        let cpus = dirs.paths.first().and_then(count);
+        let all = match dirs.paths.as_slice() {
+            [_, .., root] => count(root),
+            _ => None,
+        };
+        limits.cpuset_cpus = match (cpus, all) {
+            (Some(cpus), Some(all)) if cpus >= all => None,
+            (cpus, _) => cpus,
+        };
+    }
+
+    if let Some(dirs) = controller_dirs(&memberships, cgroup_root, "memory") {
+        let (limit_file, usage_file) = if dirs.version == 2 {
+            ("memory.max", "memory.current")
+        } else {
+            ("memory.limit_in_bytes", "memory.usage_in_bytes")
+        };
+        limits.memory_limit_bytes = min_over(&dirs, |dir| {
+            read_trimmed(&dir.join(limit_file))?
+                .parse::<u64>()
+                .ok()
+                .filter(|limit| *limit < V1_UNLIMITED)
+        });
+        if limits.memory_limit_bytes.is_some() {
+            limits.memory_usage_bytes = dirs.paths.first()
+                .and_then(|dir| read_trimmed(&dir.join(usage_file)))
+                .and_then(|usage| usage.parse().ok());
+        }
+    }
+
+    limits
+}
+
+/// One line of `/proc/self/cgroup`: the controllers of a hierarchy (empty
+/// for the unified hierarchy) and the process's path within it.
+#[derive(Debug, PartialEq)]
+struct Membership {
+    controllers: Vec<String>,
+    path: String,
+}
+
+fn parse_memberships(contents: &str) -> Vec<Membership> {
+    contents.lines()
+        .filter_map(|line| {
+            let mut fields = line.splitn(3, ':');
+            let _id = fields.next()?;
+            let controllers = fields.next()?;
+            let path = fields.next()?;
+            Some(Membership {
+                controllers: controllers.split(',')
+                    .filter(|c| !c.is_empty())
+                    .map(str::to_string)
+                    .collect(),
+                path: path.to_string(),
+            })
+        })
+        .collect()
+}
+
+/// The cgroup directories to read a controller's limits from, innermost first.
+struct ControllerDirs {
+    version: u8,
+    paths: Vec<PathBuf>,
+}
+
+fn controller_dirs(memberships: &[Membership], cgroup_root: &Path, controller: &str) -> Option<ControllerDirs> {
+    if let Some(membership) = memberships.iter().find(|m| m.controllers.iter().any(|c| c == controller)) {
+        let joined = cgroup_root.join(membership.controllers.join(","));
+        let mount = if joined.is_dir() { joined } else { cgroup_root.join(controller) };
+        return mount.is_dir().then(|| ControllerDirs {
+            version: 1,
+            paths: ancestors(&mount, &membership.path),
+        });
+    }
+
+    let unified = memberships.iter().find(|m| m.controllers.is_empty())?;
+    let enabled = read_trimmed(&cgroup_root.join("cgroup.controllers")).unwrap_or_default();
+    enabled.split_whitespace().any(|c| c == controller).then(|| ControllerDirs {
+        version: 2,
+        paths: ancestors(cgroup_root, &unified.path),
+    })
+}
+
+/// Returns `mount/path` and each of its parents up to `mount`, or just
+/// `mount` if the cgroup directory is not visible.
+fn ancestors(mount: &Path, path: &str) -> Vec<PathBuf> {
+    let leaf = mount.join(path.trim_start_matches('/'));
+    if !leaf.is_dir() {
+        return vec![mount.to_path_buf()];
+    }
+    leaf.ancestors()
+        .take_while(|dir| dir.starts_with(mount))
+        .map(Path::to_path_buf)
+        .collect()
+}
+
+fn min_over<T: PartialOrd>(dirs: &ControllerDirs, read: impl Fn(&Path) -> Option<T>) -> Option<T> {
+    dirs.paths.iter()
+        .filter_map(|dir| read(dir))
+        .fold(None, |min, value| match min {
+            Some(min) if min <= value => Some(min),
+            _ => Some(value),
+        })
+}
+
+/// Parses cgroup v2 `cpu.max` (`"<quota> <period>"` or `"max <period>"`)
+/// into a number of CPUs.
+fn parse_cpu_max(contents: &str) -> Option<f64> {
+    let mut fields = contents.split_whitespace();
+    let quota: f64 = fields.next()?.parse().ok()?;
+    let period: f64 = fields.next().unwrap_or("100000").parse().ok()?;
+    (quota > 0.0 && period > 0.0).then(|| quota / period)
+}
+
+/// Counts the CPUs in a list such as `0-3,6,8-9`.
+fn parse_cpu_list(list: &str) -> Option<u32> {
+    let mut count = 0;
+    for range in list.split(',').map(str::trim).filter(|r| !r.is_empty()) {
+        count += match range.split_once('-') {
+            Some((start, end)) => {
+                let start: u32 = start.parse().ok()?;
+                let end: u32 = end.parse().ok()?;
+                end.checked_sub(start)? + 1
+            }
+            None => {
+                range.parse::<u32>().ok()?;
+                1
+            }
+        };
+    }
+    (count > 0).then_some(count)
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+        .filter(|contents| !contents.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{fixture_dir, write};
+
+    #[test]
+    fn test_parse_helpers() {
+        assert_eq!(parse_cpu_list("0-3,6,8-9"), Some(7));
+        assert_eq!(parse_cpu_list("0"), Some(1));
+        assert_eq!(parse_cpu_list("3-1"), None);
+        assert_eq!(parse_cpu_max("150000 100000"), Some(1.5));
+        assert_eq!(parse_cpu_max("max 100000"), None);
+    }
+
+    #[test]
+    fn test_read_limits_v2() {
+        let root = fixture_dir("cgroup-v2");
+        let proc_cgroup = root.join("cgroup");
+        fs::write(&proc_cgroup, "0::/system.slice/burnin.service\n").unwrap();
+
+        let mount = root.join("sys/fs/cgroup");
+        write(&mount, "cgroup.controllers", "cpuset cpu io memory pids");
+        write(&mount, "cpu.max", "max 100000");
+        write(&mount, "memory.max", "max");
+        let slice = mount.join("system.slice");
+        write(&slice, "memory.max", "4294967296");
+        let service = slice.join("burnin.service");
+        write(&service, "cpu.max", "200000 100000");
+        write(&service, "cpuset.cpus.effective", "0-3");
+        write(&service, "memory.max", "8589934592");
+        write(&service, "memory.current", "1073741824");
+
+        let limits = read_limits_from(&proc_cgroup, &mount);
+        assert_eq!(limits.cpu_quota_cores, Some(2.0));
+        assert_eq!(limits.cpuset_cpus, Some(4));
+        assert_eq!(limits.memory_limit_bytes, Some(4 << 30));
+        assert_eq!(limits.memory_usage_bytes, Some(1 << 30));
+        assert_eq!(limits.available_memory(16 << 30), 3 << 30);
+        assert_eq!(limits.effective_cpus(64), 2);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_read_limits_v1_container() {
+        let root = fixture_dir("cgroup-v1");
+        let proc_cgroup = root.join("cgroup");
+        fs::write(&proc_cgroup, "4:memory:/docker/abc\n3:cpu,cpuacct:/docker/abc\n0::/\n").unwrap();
+
+        let mount = root.join("sys/fs/cgroup");
+        let cpu = mount.join("cpu,cpuacct");
+        write(&cpu, "cpu.cfs_quota_us", "50000");
+        write(&cpu, "cpu.cfs_period_us", "100000");
+        let memory = mount.join("memory");
+        write(&memory, "memory.limit_in_bytes", "536870912");
+        write(&memory, "memory.usage_in_bytes", "134217728");
+
+        let limits = read_limits_from(&proc_cgroup, &mount);
+        assert_eq!(limits.cpu_quota_cores, Some(0.5));
+        assert_eq!(limits.cpuset_cpus, None);
+        assert_eq!(limits.memory_limit_bytes, Some(512 << 20));
+        assert_eq!(limits.available_memory(16 << 30), 384 << 20);
+        assert_eq!(limits.effective_cpus(8), 1);
+        assert!(limits.is_limited());
+
+        write(&memory, "memory.limit_in_bytes", "9223372036854771712");
+        write(&cpu, "cpu.cfs_quota_us", "-1");
+        assert!(!read_limits_from(&proc_cgroup, &mount).is_limited());
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -2,10 +2,21 @@ use serde::{Serialize, Deserialize};
 use std::time::Duration;
 use std::path::PathBuf;
 
+use crate::core::abort::AbortSignal;
+use crate::core::hardware::ThermalSensor;
+use crate::core::baseline::{Baseline, MetricRange, DEFAULT_TOLERANCE_PERCENT};
+use crate::core::policy::Policy;
+use crate::core::scoring::ScoreWeights;
+
 /// Stress test configuration
+///
+/// Every field may be left out of a configuration file and keeps its
+/// default value.
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(default)]
 pub struct TestConfig {
     /// Duration of the test
+    #[serde(deserialize_with = "deserialize_duration")]
     pub duration: Duration,
     /// Stress level (1-10)
     pub stress_level: u8,
@@ -44,13 +55,125 @@ pub struct TestConfig {
     /// Thermal critical threshold
     pub thermal_critical_threshold: f32,
     /// Thermal monitor interval
+    #[serde(deserialize_with = "deserialize_duration")]
     pub thermal_monitor_interval: Duration,
+    /// Per-sensor threshold overrides, matched by sensor name glob
+    #[serde(default)]
+    pub thermal_sensor_overrides: Vec<SensorThresholdOverride>,
+    /// Allowed drift of a voltage rail from its first reading, in percent
+    #[serde(default = "default_voltage_tolerance")]
+    pub voltage_tolerance_percent: f32,
+    /// Run the thermal monitor in the background across all other tests
+    #[serde(default)]
+    pub thermal_background: bool,
+    /// Adapt stress level, memory size and threads to the detected hardware
+    #[serde(default)]
+    pub adaptive: bool,
+    /// Abort all stress workloads when a sensor reaches the critical threshold
+    #[serde(default = "default_thermal_cutoff")]
+    pub thermal_cutoff: bool,
     /// Alert threshold
     pub alert_threshold: u8,
     /// Alert webhook URL
     pub alert_webhook_url: Option<String>,
     /// Alert email
     pub alert_email: Option<String>,
+    /// SMTP relay for alert emails, as host:port
+    #[serde(default)]
+    pub alert_smtp_server: Option<String>,
+    /// Sender address of alert emails
+    #[serde(default)]
+    pub alert_email_from: Option<String>,
+    /// Delivery retries after a failed alert
+    #[serde(default = "default_alert_retries")]
+    pub alert_retries: u32,
+    /// Log alerts instead of sending them
+    #[serde(default)]
+    pub alert_dry_run: bool,
+    /// Baseline database file
+    #[serde(default)]
+    pub baseline_file: Option<PathBuf>,
+    /// Allowed deviation from a baseline range, in percent
+    #[serde(default = "default_baseline_tolerance")]
+    pub baseline_tolerance_percent: f64,
+    /// Expected ranges for this machine, resolved from the baseline file
+    #[serde(skip)]
+    pub baseline: Option<Baseline>,
+    /// Weight of each test in the overall score, keyed by test or component name
+    #[serde(default)]
+    pub score_weights: ScoreWeights,
+    /// Acceptance policy file
+    #[serde(default)]
+    pub policy_file: Option<PathBuf>,
+    /// Acceptance criteria loaded from the policy file
+    #[serde(skip)]
+    pub policy: Option<Policy>,
+    /// Address to serve OpenMetrics on during the run, such as 0.0.0.0:9100
+    #[serde(default)]
+    pub metrics_listen: Option<String>,
+    /// File to keep updated with OpenMetrics for node_exporter's textfile collector
+    #[serde(default)]
+    pub metrics_textfile: Option<PathBuf>,
+    /// Destination of the NDJSON event stream: a path, `-` or `fd:N`
+    #[serde(default)]
+    pub event_log: Option<String>,
+    /// Stops every running workload when triggered
+    #[serde(skip)]
+    pub abort: AbortSignal,
+}
+
+/// Thresholds for the temperature sensors whose names match a glob pattern
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct SensorThresholdOverride {
+    /// Glob pattern such as `nvme*`; `*` matches any run of characters and `?` one character
+    pub sensor: String,
+    pub warning: Option<f32>,
+    pub critical: Option<f32>,
+}
+
+/// Warning and critical temperatures that apply to one sensor
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+pub struct SensorThresholds {
+    pub warning: f32,
+    pub critical: f32,
+}
+
+/// Reads a duration written as text such as `"30m"`, as a number of
+/// seconds, or in the `secs`/`nanos` form it is serialized in.
+fn deserialize_duration<'de, D>(deserializer: D) -> Result<Duration, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Written {
+        Text(String),
+        Seconds(u64),
+        Serialized(Duration),
+    }
+    
+    match Written::deserialize(deserializer)? {
+        Written::Text(text) => humantime::parse_duration(&text)
+            .map_err(|e| serde::de::Error::custom(format!("invalid duration '{}': {}", text, e))),
+        Written::Seconds(secs) => Ok(Duration::from_secs(secs)),
+        Written::Serialized(duration) => Ok(duration),
+    }
+}
+
+fn default_voltage_tolerance() -> f32 {
+    5.0
+}
+
+fn default_thermal_cutoff() -> bool {
+    true
+}
+
+fn default_baseline_tolerance() -> f64 {
+    DEFAULT_TOLERANCE_PERCENT
+}
+
+fn default_alert_retries() -> u32 {
+    3
 }
 
 /// Output format
@@ -62,6 +185,10 @@ pub enum OutputFormat {
     Json,
     /// CSV output
     Csv,
+    /// JUnit XML output
+    Junit,
+    /// HTML report
+    Html,
 }
 
 impl Default for TestConfig {
@@ -88,9 +215,28 @@ impl Default for TestConfig {
             thermal_warning_threshold: 80.0,
             thermal_critical_threshold: 90.0,
             thermal_monitor_interval: Duration::from_secs(5),
+            thermal_sensor_overrides: Vec::new(),
+            voltage_tolerance_percent: 5.0,
+            thermal_background: false,
+            adaptive: false,
+            thermal_cutoff: true,
             alert_threshold: 95,
             alert_webhook_url: None,
             alert_email: None,
+            alert_smtp_server: None,
+            alert_email_from: None,
+            alert_retries: default_alert_retries(),
+            alert_dry_run: false,
+            baseline_file: None,
+            baseline_tolerance_percent: DEFAULT_TOLERANCE_PERCENT,
+            baseline: None,
+            score_weights: ScoreWeights::new(),
+            policy_file: None,
+            policy: None,
+            metrics_listen: None,
+            metrics_textfile: None,
+            event_log: None,
+            abort: AbortSignal::new(),
         }
     }
 }
@@ -176,6 +322,49 @@ impl TestConfig {
         self.storage_file_size = 2_147_483_648; 
     }
     
+    /// Returns the baseline range of a metric, if one was recorded for this machine
+    pub fn expected_range(&self, test: &str, metric: &str) -> Option<&MetricRange> {
+        self.baseline.as_ref().and_then(|baseline| baseline.range(test, metric))
+    }
+    
+    /// Global temperature thresholds
+    pub fn default_thresholds(&self) -> SensorThresholds {
+        SensorThresholds {
+            warning: self.thermal_warning_threshold,
+            critical: self.thermal_critical_threshold,
+        }
+    }
+    
+    /// Temperature thresholds for one sensor
+    ///
+    /// The limits a sensor reports itself can only lower the global
+    /// thresholds: its `crit` value caps the critical threshold and its `max`
+    /// value the warning threshold. A sensor that reports only a critical
+    /// limit gets a warning threshold the same distance below it as the
+    /// global thresholds are apart. The first matching override replaces
+    /// either value, and is the only way to raise one.
+    pub fn sensor_thresholds(&self, sensor: &ThermalSensor) -> SensorThresholds {
+        let global = self.default_thresholds();
+        let reported_critical = sensor.critical_temp_celsius.filter(|c| *c > 0.0);
+        
+        let critical = reported_critical.map_or(global.critical, |c| c.min(global.critical));
+        let warning = match (sensor.max_temp_celsius.filter(|m| *m > 0.0), reported_critical) {
+            (Some(max), _) => max.min(global.warning),
+            (None, Some(_)) => critical - (global.critical - global.warning).max(0.0),
+            (None, None) => global.warning,
+        };
+        
+        let (warning, critical) = match self.thermal_sensor_overrides.iter().find(|o| glob_match(&o.sensor, &sensor.name)) {
+            Some(o) => (o.warning.unwrap_or(warning), o.critical.unwrap_or(critical)),
+            None => (warning, critical),
+        };
+        
+        SensorThresholds {
+            warning: warning.min(critical),
+            critical,
+        }
+    }
+    
     /// Parse duration string
     pub fn parse_duration(duration_str: &str) -> Result<Duration, String> {
         let duration = humantime::parse_duration(duration_str)
@@ -235,8 +424,53 @@ impl TestConfig {
                 .map_err(|e| format!("Failed to parse JSON config: {}", e))?
         };
         
+        config.validate()?;
         Ok(config)
     }
+    
+    /// Checks settings that would make the run misbehave, once the
+    /// configuration file and command-line flags have been merged.
+    pub fn validate(&self) -> Result<(), String> {
+        if !(1..=10).contains(&self.stress_level) {
+            return Err(format!("Invalid stress level: {} (must be between 1 and 10)", self.stress_level));
+        }
+        
+        if let Some((name, weight)) = self.score_weights.iter().find(|(_, weight)| !weight.is_finite() || **weight < 0.0) {
+            return Err(format!("Invalid score weight for {}: {}", name, weight));
+        }
+        
+        if self.thermal_monitor_interval.is_zero() {
+            return Err("Invalid thermal monitor interval: must be greater than zero".to_string());
+        }
+        
+        Ok(())
+    }
+}
+
+/// Matches `name` against a glob pattern supporting `*` and `?`
+fn glob_match(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    let mut backtrack = None;
+    
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, matched + 1));
+        } else {
+            return false;
+        }
+    }
+    
+    pattern[p..].iter().all(|c| *c == '*')
 }
 
 #[cfg(test)]
@@ -348,4 +582,78 @@ mod tests {
         config.apply_preset_full();
         assert_eq!(config.duration, Duration::from_secs(8 * 60 * 60));
     }
+
+    #[test]
+    fn test_glob_match() {
+        assert!(glob_match("nvme*", "nvme/Composite"));
+        assert!(glob_match("*Package*", "coretemp/Package id 0"));
+        assert!(glob_match("coretemp/Core ?", "coretemp/Core 3"));
+        assert!(glob_match("*", ""));
+        assert!(!glob_match("nvme*", "coretemp/Core 0"));
+        assert!(!glob_match("coretemp/Core ?", "coretemp/Core 12"));
+    }
+
+    #[test]
+    fn test_sensor_thresholds() {
+        let sensor = |name: &str, critical: Option<f32>, max: Option<f32>| ThermalSensor {
+            name: name.to_string(),
+            location: "hwmon0".to_string(),
+            current_temp_celsius: 40.0,
+            critical_temp_celsius: critical,
+            max_temp_celsius: max,
+        };
+        let mut config = TestConfig::default();
+        
+        assert_eq!(config.sensor_thresholds(&sensor("acpitz", None, None)), SensorThresholds { warning: 80.0, critical: 90.0 });
+        assert_eq!(config.sensor_thresholds(&sensor("coretemp/Core 0", Some(100.0), Some(82.0))), SensorThresholds { warning: 80.0, critical: 90.0 });
+        assert_eq!(config.sensor_thresholds(&sensor("coretemp/Core 1", Some(100.0), Some(75.0))), SensorThresholds { warning: 75.0, critical: 90.0 });
+        assert_eq!(config.sensor_thresholds(&sensor("acpitz", Some(105.0), None)), SensorThresholds { warning: 80.0, critical: 90.0 });
+        assert_eq!(config.sensor_thresholds(&sensor("nvme/Composite", Some(84.8), None)), SensorThresholds { warning: 74.8, critical: 84.8 });
+        
+        config.thermal_sensor_overrides = vec![
+            SensorThresholdOverride { sensor: "nvme*".to_string(), warning: Some(65.0), critical: None },
+            SensorThresholdOverride { sensor: "*".to_string(), warning: None, critical: Some(70.0) },
+        ];
+        assert_eq!(config.sensor_thresholds(&sensor("nvme/Composite", Some(84.8), None)), SensorThresholds { warning: 65.0, critical: 84.8 });
+        assert_eq!(config.sensor_thresholds(&sensor("acpitz", None, None)), SensorThresholds { warning: 70.0, critical: 70.0 });
+        
+        config.thermal_sensor_overrides = vec![
+            SensorThresholdOverride { sensor: "coretemp/*".to_string(), warning: Some(95.0), critical: Some(100.0) },
+        ];
+        assert_eq!(config.sensor_thresholds(&sensor("coretemp/Core 0", Some(100.0), Some(82.0))), SensorThresholds { warning: 95.0, critical: 100.0 });
+    }
+    
+    #[test]
+    fn test_validate() {
+        let path = std::env::temp_dir().join(format!("burnin-config-{}.json", std::process::id()));
+        let config = TestConfig { thermal_monitor_interval: Duration::ZERO, ..TestConfig::default() };
+        std::fs::write(&path, serde_json::to_string(&config).unwrap()).unwrap();
+        let error = TestConfig::from_file(path.to_str().unwrap()).unwrap_err();
+        std::fs::remove_file(&path).unwrap();
+        assert!(error.contains("thermal monitor interval"), "{}", error);
+        
+        assert!(TestConfig::default().validate().is_ok());
+        assert!(TestConfig { stress_level: 0, ..TestConfig::default() }.validate().is_err());
+    }
+    
+    #[test]
+    fn test_parse_toml() {
+        let config: TestConfig = toml::from_str(r#"
+            duration = "30m"
+            stress_level = 5
+            thermal_monitor_interval = 2
+            
+            [[thermal_sensor_overrides]]
+            sensor = "nvme*"
+            critical = 70.0
+        "#).unwrap();
+        assert_eq!(config.duration, Duration::from_secs(30 * 60));
+        assert_eq!(config.stress_level, 5);
+        assert_eq!(config.thermal_monitor_interval, Duration::from_secs(2));
+        assert_eq!(config.thermal_sensor_overrides[0].critical, Some(70.0));
+        assert!(config.cpu_enabled);
+        assert_eq!(config.voltage_tolerance_percent, 5.0);
+        
+        assert!(toml::from_str::<TestConfig>("duration = \"soon\"").is_err());
+    }
 }
@@ -0,0 +1,128 @@
+use std::fs;
+
+/// Instruction set extensions probed on x86_64 via CPUID.
+#[cfg(target_arch = "x86_64")]
+const X86_FEATURES: &[&str] = &[
+    "sse2", "sse3", "ssse3", "sse4.1", "sse4.2", "popcnt", "aes", "pclmulqdq",
+    "avx", "avx2", "fma", "f16c", "bmi1", "bmi2", "sha",
+    "avx512f", "avx512dq", "avx512bw", "avx512vl",
+];
+
+/// Detects the instruction set extensions supported by the running CPU.
+///
+/// Uses CPUID (x86_64) or the kernel's hwcap (aarch64) through the standard
+/// library's runtime detection, and falls back to the `flags`/`Features`
+/// line of `/proc/cpuinfo` on other architectures.
+pub fn detect_cpu_features() -> Vec<String> {
+    let features = detect_runtime_features();
+    if !features.is_empty() {
+        return features;
+    }
+
+    fs::read_to_string("/proc/cpuinfo")
+        .map(|contents| parse_cpuinfo_flags(&contents))
+        .unwrap_or_default()
+}
+
+#[cfg(target_arch = "x86_64")]
+fn detect_runtime_features() -> Vec<String> {
+    X86_FEATURES.iter()
+        .filter(|feature| is_x86_feature_enabled(feature))
+        .map(|feature| feature.to_string())
+        .collect()
+}
+
+#[cfg(target_arch = "aarch64")]
+fn detect_runtime_features() -> Vec<String> {
+    let mut features = Vec::new();
+    if std::arch::is_aarch64_feature_detected!("neon") {
+        features.push("neon".to_string());
+    }
+    if std::arch::is_aarch64_feature_detected!("fp16") {
+        features.push("fp16".to_string());
+    }
+    if std::arch::is_aarch64_feature_detected!("aes") {
+        features.push("aes".to_string());
+    }
+    if std::arch::is_aarch64_feature_detected!("sha2") {
+        features.push("sha2".to_string());
+    }
+    if std::arch::is_aarch64_feature_detected!("crc") {
+        features.push("crc".to_string());
+    }
+    if std::arch::is_aarch64_feature_detected!("sve") {
+        features.push("sve".to_string());
+    }
+    features
+}
+
+#[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+fn detect_runtime_features() -> Vec<String> {
+    Vec::new()
+}
+
+/// Returns `true` if the named x86 feature is supported by the running CPU.
+#[cfg(target_arch = "x86_64")]
+pub fn is_x86_feature_enabled(feature: &str) -> bool {
+    match feature {
+        "sse2" => std::arch::is_x86_feature_detected!("sse2"),
+        "sse3" => std::arch::is_x86_feature_detected!("sse3"),
+        "ssse3" => std::arch::is_x86_feature_detected!("ssse3"),
+        "sse4.1" => std::arch::is_x86_feature_detected!("sse4.1"),
+        "sse4.2" => std::arch::is_x86_feature_detected!("sse4.2"),
+        "popcnt" => std::arch::is_x86_feature_detected!("popcnt"),
+        "aes" => std::arch::is_x86_feature_detected!("aes"),
+        "pclmulqdq" => std::arch::is_x86_feature_detected!("pclmulqdq"),
+        "avx" => std::arch::is_x86_feature_detected!("avx"),
+        "avx2" => std::arch::is_x86_feature_detected!("avx2"),
+        "fma" => std::arch::is_x86_feature_detected!("fma"),
+        "f16c" => std::arch::is_x86_feature_detected!("f16c"),
+        "bmi1" => std::arch::is_x86_feature_detected!("bmi1"),
+        "bmi2" => std::arch::is_x86_feature_detected!("bmi2"),
+        "sha" => std::arch::is_x86_feature_detected!("sha"),
+        "avx512f" => std::arch::is_x86_feature_detected!("avx512f"),
+        "avx512dq" => std::arch::is_x86_feature_detected!("avx512dq"),
+        "avx512bw" => std::arch::is_x86_feature_detected!("avx512bw"),
+        "avx512vl" => std::arch::is_x86_feature_detected!("avx512vl"),
+        _ => false,
+    }
+}
+
+/// Extracts the feature flags from the contents of `/proc/cpuinfo`.
+///
+/// x86 kernels report them on a `flags` line, ARM kernels on `Features`.
+/// Only the first processor entry is considered.
+pub fn parse_cpuinfo_flags(contents: &str) -> Vec<String> {
+    contents.lines()
+        .find_map(|line| {
+            let (key, value) = line.split_once(':')?;
+            match key.trim() {
+                "flags" | "Features" => Some(value),
+                _ => None,
+            }
+        })
+        .map(|value| value.split_whitespace().map(str::to_string).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_cpuinfo_flags_x86() {
+        let contents = "processor\t: 0\nvendor_id\t: GenuineIntel\nflags\t\t: fpu sse2 avx2 fma\n\nprocessor\t: 1\nflags\t\t: fpu\n";
+        assert_eq!(parse_cpuinfo_flags(contents), vec!["fpu", "sse2", "avx2", "fma"]);
+    }
+
+    #[test]
+    fn test_parse_cpuinfo_flags_arm() {
+        let contents = "processor\t: 0\nBogoMIPS\t: 108.00\nFeatures\t: fp asimd evtstrm aes crc32\n";
+        assert_eq!(parse_cpuinfo_flags(contents), vec!["fp", "asimd", "evtstrm", "aes", "crc32"]);
+    }
+
+    #[test]
+    fn test_parse_cpuinfo_flags_missing() {
+        assert!(parse_cpuinfo_flags("processor\t: 0\n").is_empty());
+    }
+}
@@ -0,0 +1,387 @@
+use std::fs;
+use std::path::Path;
+
+use crate::core::hardware::{DmiInfo, MemoryModule};
+
+/// Location of the DMI identity attributes in sysfs.
+pub const SYSFS_DMI_ID: &str = "/sys/class/dmi/id";
+
+/// Location of the raw SMBIOS structure table. Readable by root only.
+pub const SYSFS_DMI_TABLE: &str = "/sys/firmware/dmi/tables/DMI";
+
+const TYPE_PHYSICAL_MEMORY_ARRAY: u8 = 16;
+const TYPE_MEMORY_DEVICE: u8 = 17;
+const TYPE_END_OF_TABLE: u8 = 127;
+
+/// Values vendors leave in unset DMI and SMBIOS strings.
+const PLACEHOLDERS: &[&str] = &[
+    "to be filled by o.e.m.",
+    "default string",
+    "not specified",
+    "not available",
+    "none",
+    "unknown",
+    "system serial number",
+    "system product name",
+    "system manufacturer",
+    "0123456789",
+    "o.e.m.",
+];
+
+/// Memory inventory decoded from the SMBIOS table.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct SmbiosMemory {
+    pub modules: Vec<MemoryModule>,
+    pub ecc_enabled: Option<bool>,
+}
+
+impl SmbiosMemory {
+    /// The speed all modules run at: the lowest configured speed, falling
+    /// back to the lowest rated speed.
+    pub fn speed_mhz(&self) -> Option<u32> {
+        self.modules.iter()
+            .filter_map(|module| module.configured_speed_mhz)
+            .min()
+            .or_else(|| self.modules.iter().filter_map(|module| module.speed_mhz).min())
+    }
+}
+
+/// Reads the machine identity of the running system.
+pub fn read_dmi_id() -> DmiInfo {
+    read_dmi_id_from(Path::new(SYSFS_DMI_ID))
+}
+
+/// Reads the machine identity from a `/sys/class/dmi/id` style directory.
+pub fn read_dmi_id_from(dir: &Path) -> DmiInfo {
+    let read = |file: &str| {
+        fs::read_to_string(dir.join(file))
+            .ok()
+            .and_then(|value| clean(&value))
+    };
+
+    DmiInfo {
+        system_vendor: read("sys_vendor"),
+        system_product: read("product_name"),
+        system_serial: read("product_serial"),
+        board_vendor: read("board_vendor"),
+        board_product: read("board_name"),
+        board_serial: read("board_serial"),
+        bios_vendor: read("bios_vendor"),
+        bios_version: read("bios_version"),
+        bios_date: read("bios_date"),
+    }
+}
+
+/// Reads the memory inventory of the running system, or an empty one if
+/// the SMBIOS table is missing or not readable.
+pub fn read_smbios_memory() -> SmbiosMemory {
+    fs::read(SYSFS_DMI_TABLE)
+        .map(|table| parse_smbios_memory(&table))
+        .unwrap_or_default()
+}
+
+/// Decodes memory arrays (type 16) and memory devices (type 17) from a raw
+/// SMBIOS structure table.
+///
+/// Empty slots are skipped. ECC is taken from the system memory arrays'
+/// error correction type, or failing that from the modules' total width
+/// being wider than their data width.
+pub fn parse_smbios_memory(table: &[u8]) -> SmbiosMemory {
+    let mut memory = SmbiosMemory::default();
+    let mut array_ecc = None;
+    let mut width_ecc = None;
+
+    for structure in structures(table) {
+        match structure.kind {
+            TYPE_PHYSICAL_MEMORY_ARRAY => {
+                // Only arrays used as system memory, not video or flash.
+                if structure.byte(0x05) != Some(0x03) {
+                    continue;
+                }
+                let ecc = match structure.byte(0x06) {
+                    Some(0x05..=0x07) => Some(true),
+                    Some(0x03 | 0x04) => Some(false),
+                    _ => None,
+                };
+                array_ecc = array_ecc.or(ecc);
+            }
+            TYPE_MEMORY_DEVICE => {
+                let Some(size_bytes) = module_size(&structure) else {
+                    continue;
+                };
+                if let (Some(total), Some(data)) = (structure.word(0x08), structure.word(0x0A)) {
+                    if total != 0xFFFF && data != 0xFFFF && data != 0 {
+                        width_ecc = Some(width_ecc.unwrap_or(false) || total > data);
+                    }
+                }
+                memory.modules.push(MemoryModule {
+                    locator: structure.string(0x10).unwrap_or_else(|| format!("DIMM {}", memory.modules.len())),
+                    bank: structure.string(0x11),
+                    size_bytes,
+                    memory_type: structure.byte(0x12).and_then(memory_type).map(str::to_string),
+                    speed_mhz: speed(&structure, 0x15, 0x54),
+                    configured_speed_mhz: speed(&structure, 0x20, 0x58),
+                    manufacturer: structure.string(0x17),
+                    part_number: structure.string(0x1A),
+                    serial_number: structure.string(0x18),
+                });
+            }
+            _ => {}
+        }
+    }
+
+    memory.ecc_enabled = array_ecc.or(width_ecc);
+    memory
+}
+
+/// One structure of the SMBIOS table: its formatted area and string set.
+struct Structure<'a> {
+    kind: u8,
+    formatted: &'a [u8],
+    strings: Vec<&'a [u8]>,
+}
+
+impl Structure<'_> {
+    fn byte(&self, offset: usize) -> Option<u8> {
+        self.formatted.get(offset).copied()
+    }
+
+    fn word(&self, offset: usize) -> Option<u16> {
+        let bytes = self.formatted.get(offset..offset + 2)?;
+        Some(u16::from_le_bytes([bytes[0], bytes[1]]))
+    }
+
+    fn dword(&self, offset: usize) -> Option<u32> {
+        let bytes = self.formatted.get(offset..offset + 4)?;
+        Some(u32::from_le_bytes([bytes[0], bytes[1], bytes[2], bytes[3]]))
+    }
+
+    /// Resolves the string referenced at `offset`; string numbers start at 1.
+    fn string(&self, offset: usize) -> Option<String> {
+        let index = self.byte(offset)? as usize;
+        let raw = self.strings.get(index.checked_sub(1)?)?;
+        clean(&String::from_utf8_lossy(raw))
+    }
+}
+
+/// Splits a table into structures, stopping at the end-of-table marker or
+/// the first truncated structure.
+fn structures(table: &[u8]) -> Vec<Structure<'_>> {
+    let mut structures = Vec::new();
+    let mut offset = 0;
+
+    while offset + 4 <= table.len() {
+        let kind = table[offset];
+        let length = table[offset + 1] as usize;
+        if length < 4 || offset + length > table.len() {
+            break;
+        }
+        let formatted = &table[offset..offset + length];
+
+        // The string set ends with two consecutive NULs.
+        let strings_start = offset + length;
+        let Some(end) = table[strings_start..].windows(2).position(|pair| pair == [0, 0]) else {
+            break;
+        };
+        let strings = table[strings_start..strings_start + end]
+            .split(|byte| *byte == 0)
+            .filter(|s| !s.is_empty())
+            .collect();
+
+        structures.push(Structure { kind, formatted, strings });
+        if kind == TYPE_END_OF_TABLE {
+            break;
+        }
+        offset = strings_start + end + 2;
+    }
+
+    structures
+}
+
+/// Decodes a memory device's size, or `None` for an empty or unknown slot.
+fn module_size(structure: &Structure) -> Option<u64> {
+    const MIB: u64 = 1024 * 1024;
+
+    match structure.word(0x0C)? {
+        0 | 0xFFFF => None,
+        0x7FFF => structure.dword(0x1C)
+            .map(|mib| (mib & 0x7FFF_FFFF) as u64 * MIB)
+            .filter(|size| *size > 0),
+        size if size & 0x8000 != 0 => Some((size & 0x7FFF) as u64 * 1024),
+        size => Some(size as u64 * MIB),
+    }
+}
+
+/// Decodes a speed word, following 0xFFFF to the extended speed dword.
+fn speed(structure: &Structure, offset: usize, extended: usize) -> Option<u32> {
+    match structure.word(offset)? {
+        0 => None,
+        0xFFFF => structure.dword(extended).filter(|speed| *speed > 0),
+        speed => Some(speed as u32),
+    }
+}
+
+fn memory_type(code: u8) -> Option<&'static str> {
+    Some(match code {
+        0x12 => "DDR",
+        0x13 => "DDR2",
+        0x18 => "DDR3",
+        0x1A => "DDR4",
+        0x1B => "LPDDR",
+        0x1C => "LPDDR2",
+        0x1D => "LPDDR3",
+        0x1E => "LPDDR4",
+        0x20 => "HBM",
+        0x21 => "HBM2",
+        0x22 => "DDR5",
+        0x23 => "LPDDR5",
+        0x24 => "HBM3",
+        _ => return None,
+    })
+}
+
+fn clean(value: &str) -> Option<String> {
+    let value = value.trim();
+    if value.is_empty() || PLACEHOLDERS.contains(&value.to_lowercase().as_str()) {
+        None
+    } else {
+        Some(value.to_string())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::fixture_dir;
+
+    /// Appends a structure with the given formatted area and strings.
+    fn push_structure(table: &mut Vec<u8>, formatted: &[u8], strings: &[&str]) {
+        table.extend_from_slice(formatted);
+        for string in strings {
+            table.extend_from_slice(string.as_bytes());
+            table.push(0);
+        }
+        if strings.is_empty() {
+            table.push(0);
+        }
+        table.push(0);
+    }
+
+    fn memory_array(ecc: u8) -> Vec<u8> {
+        let mut formatted = vec![0u8; 0x17];
+        formatted[0] = TYPE_PHYSICAL_MEMORY_ARRAY;
+        formatted[1] = 0x17;
+        formatted[0x05] = 0x03;
+        formatted[0x06] = ecc;
+        formatted
+    }
+
+    fn memory_device(size: u16, extended_mib: u32, widths: (u16, u16), speeds: (u16, u16)) -> Vec<u8> {
+        let mut formatted = vec![0u8; 0x28];
+        formatted[0] = TYPE_MEMORY_DEVICE;
+        formatted[1] = 0x28;
+        formatted[0x08..0x0A].copy_from_slice(&widths.0.to_le_bytes());
+        formatted[0x0A..0x0C].copy_from_slice(&widths.1.to_le_bytes());
+        formatted[0x0C..0x0E].copy_from_slice(&size.to_le_bytes());
+        formatted[0x10] = 1;
+        formatted[0x11] = 2;
+        formatted[0x12] = 0x1A;
+        formatted[0x15..0x17].copy_from_slice(&speeds.0.to_le_bytes());
+        formatted[0x17] = 3;
+        formatted[0x18] = 4;
+        formatted[0x1A] = 5;
+        formatted[0x1C..0x20].copy_from_slice(&extended_mib.to_le_bytes());
+        formatted[0x20..0x22].copy_from_slice(&speeds.1.to_le_bytes());
+        formatted
+    }
+
+    fn end_of_table() -> Vec<u8> {
+        vec![TYPE_END_OF_TABLE, 4, 0xFF, 0xFE]
+    }
+
+    #[test]
+    fn test_parse_smbios_memory() {
+        let mut table = Vec::new();
+        push_structure(&mut table, &memory_array(0x06), &[]);
+        push_structure(
+            &mut table,
+            &memory_device(16384, 0, (72, 64), (3200, 2933)),
+            &["DIMM_A1", "BANK 0", "Samsung", "0x1234ABCD", "M393A2K43DB3-CWE   "],
+        );
+        push_structure(&mut table, &memory_device(0, 0, (0xFFFF, 0xFFFF), (0, 0)), &["DIMM_A2", "BANK 1", "NO DIMM"]);
+        push_structure(
+            &mut table,
+            &memory_device(0x7FFF, 65536, (72, 64), (3200, 3200)),
+            &["DIMM_B1", "BANK 2", "Micron", "Not Specified", "36ASF8G72PZ"],
+        );
+        push_structure(&mut table, &end_of_table(), &[]);
+        push_structure(&mut table, &memory_device(8192, 0, (64, 64), (2400, 2400)), &["after end"]);
+
+        let memory = parse_smbios_memory(&table);
+        assert_eq!(memory.modules.len(), 2);
+        assert_eq!(memory.ecc_enabled, Some(true));
+        assert_eq!(memory.speed_mhz(), Some(2933));
+
+        let first = &memory.modules[0];
+        assert_eq!(first.locator, "DIMM_A1");
+        assert_eq!(first.bank.as_deref(), Some("BANK 0"));
+        assert_eq!(first.size_bytes, 16 << 30);
+        assert_eq!(first.memory_type.as_deref(), Some("DDR4"));
+        assert_eq!(first.speed_mhz, Some(3200));
+        assert_eq!(first.manufacturer.as_deref(), Some("Samsung"));
+        assert_eq!(first.part_number.as_deref(), Some("M393A2K43DB3-CWE"));
+        assert_eq!(first.serial_number.as_deref(), Some("0x1234ABCD"));
+
+        let second = &memory.modules[1];
+        assert_eq!(second.size_bytes, 64 << 30);
+        assert_eq!(second.serial_number, None);
+    }
+
+    #[test]
+    fn test_ecc_from_widths_and_truncated_tables() {
+        let mut table = Vec::new();
+        push_structure(&mut table, &memory_array(0x02), &[]);
+        push_structure(&mut table, &memory_device(8192, 0, (64, 64), (2666, 0)), &["DIMM0"]);
+        let memory = parse_smbios_memory(&table);
+        assert_eq!(memory.ecc_enabled, Some(false));
+        assert_eq!(memory.speed_mhz(), Some(2666));
+
+        let mut no_ecc = Vec::new();
+        push_structure(&mut no_ecc, &memory_array(0x03), &[]);
+        push_structure(&mut no_ecc, &memory_device(8192, 0, (72, 64), (2666, 0)), &["DIMM0"]);
+        assert_eq!(parse_smbios_memory(&no_ecc).ecc_enabled, Some(false));
+
+        let truncated = &table[..table.len() - 10];
+        assert!(parse_smbios_memory(truncated).modules.is_empty());
+        assert_eq!(parse_smbios_memory(&[]), SmbiosMemory::default());
+    }
+
+    #[test]
+    fn test_read_dmi_id_from_fixture() {
+        let dir = fixture_dir("dmi-id");
+        for (file, value) in [
+            ("sys_vendor", "Supermicro"),
+            ("product_name", "SYS-1029P-WTR"),
+            ("product_serial", "A123456789"),
+            ("board_vendor", "Supermicro"),
+            ("board_name", "X11DDW-L"),
+            ("board_serial", "To be filled by O.E.M."),
+            ("bios_vendor", "American Megatrends Inc."),
+            ("bios_version", "3.4"),
+            ("bios_date", "10/28/2020"),
+        ] {
+            fs::write(dir.join(file), format!("{}\n", value)).unwrap();
+        }
+
+        let dmi = read_dmi_id_from(&dir);
+        assert_eq!(dmi.system_vendor.as_deref(), Some("Supermicro"));
+        assert_eq!(dmi.system_product.as_deref(), Some("SYS-1029P-WTR"));
+        assert_eq!(dmi.system_serial.as_deref(), Some("A123456789"));
+        assert_eq!(dmi.board_product.as_deref(), Some("X11DDW-L"));
+        assert_eq!(dmi.board_serial, None);
+        assert_eq!(dmi.bios_version.as_deref(), Some("3.4"));
+
+        assert_eq!(read_dmi_id_from(Path::new("/nonexistent/dmi")), DmiInfo::default());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
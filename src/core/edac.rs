@@ -0,0 +1,364 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::monitor::HealthMonitor;
+use crate::core::test::{TestIssue, IssueSeverity};
+
+/// Location of the EDAC memory controllers in sysfs.
+pub const SYSFS_EDAC_MC: &str = "/sys/devices/system/edac/mc";
+
+/// Per-CPU interrupt counters, including machine check exceptions.
+pub const PROC_INTERRUPTS: &str = "/proc/interrupts";
+
+/// Corrected and uncorrected error counters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ErrorCounts {
+    pub corrected: u64,
+    pub uncorrected: u64,
+}
+
+impl ErrorCounts {
+    /// Errors counted since `before`. Counters that went backwards, as after
+    /// a driver reload, count from zero.
+    fn since(&self, before: Option<&ErrorCounts>) -> ErrorCounts {
+        match before {
+            Some(before) if self.corrected >= before.corrected && self.uncorrected >= before.uncorrected => ErrorCounts {
+                corrected: self.corrected - before.corrected,
+                uncorrected: self.uncorrected - before.uncorrected,
+            },
+            _ => *self,
+        }
+    }
+
+    fn is_zero(&self) -> bool {
+        self.corrected == 0 && self.uncorrected == 0
+    }
+}
+
+/// The counters of one memory controller and of each DIMM behind it.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct ControllerCounts {
+    pub name: String,
+    pub totals: ErrorCounts,
+    /// Keyed by DIMM label, or by `mcN/dimmM` where the firmware provides none
+    pub dimms: BTreeMap<String, ErrorCounts>,
+}
+
+/// Hardware error counters at one point in time.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct EdacSnapshot {
+    /// Keyed by controller directory, such as `mc0`
+    pub controllers: BTreeMap<String, ControllerCounts>,
+    /// Machine check exceptions summed over all CPUs
+    pub machine_checks: Option<u64>,
+}
+
+/// Reads the hardware error counters of the running system.
+pub fn snapshot() -> EdacSnapshot {
+    snapshot_from(Path::new(SYSFS_EDAC_MC), Path::new(PROC_INTERRUPTS))
+}
+
+/// Reads the EDAC controllers under `mc_root` and the machine check count
+/// from an `/proc/interrupts` style file.
+///
+/// DIMMs are read from the `dimmN` and `rankN` directories of current
+/// kernels, or from the `chN_*` attributes of legacy `csrowN` directories.
+pub fn snapshot_from(mc_root: &Path, interrupts: &Path) -> EdacSnapshot {
+    let mut snapshot = EdacSnapshot {
+        machine_checks: fs::read_to_string(interrupts).ok().and_then(|contents| parse_machine_checks(&contents)),
+        ..EdacSnapshot::default()
+    };
+
+    for mc in sorted_entries(mc_root, &["mc"]) {
+        let dir_name = file_name(&mc);
+        let mut controller = ControllerCounts {
+            name: read_trimmed(&mc.join("mc_name")).unwrap_or_else(|| dir_name.clone()),
+            totals: ErrorCounts {
+                corrected: read_count(&mc.join("ce_count")).unwrap_or(0),
+                uncorrected: read_count(&mc.join("ue_count")).unwrap_or(0),
+            },
+            dimms: BTreeMap::new(),
+        };
+
+        for dimm in sorted_entries(&mc, &["dimm", "rank"]) {
+            let label = read_trimmed(&dimm.join("dimm_label"))
+                .unwrap_or_else(|| format!("{}/{}", dir_name, file_name(&dimm)));
+            let counts = ErrorCounts {
+                corrected: read_count(&dimm.join("dimm_ce_count")).unwrap_or(0),
+                uncorrected: read_count(&dimm.join("dimm_ue_count")).unwrap_or(0),
+            };
+            add(&mut controller.dimms, label, counts);
+        }
+
+        if controller.dimms.is_empty() {
+            for csrow in sorted_entries(&mc, &["csrow"]) {
+                let csrow_name = format!("{}/{}", dir_name, file_name(&csrow));
+                let mut labels = Vec::new();
+                for channel in 0.. {
+                    let Some(corrected) = read_count(&csrow.join(format!("ch{}_ce_count", channel))) else {
+                        break;
+                    };
+                    let label = read_trimmed(&csrow.join(format!("ch{}_dimm_label", channel)))
+                        .unwrap_or_else(|| format!("{}/ch{}", csrow_name, channel));
+                    add(&mut controller.dimms, label.clone(), ErrorCounts { corrected, uncorrected: 0 });
+                    labels.push(label);
+                }
+
+                // Uncorrected errors are only counted per csrow, so they are
+                // attributed to every DIMM on it together.
+                let uncorrected = read_count(&csrow.join("ue_count")).unwrap_or(0);
+                if uncorrected > 0 {
+                    let label = if labels.is_empty() { csrow_name } else { labels.join(" / ") };
+                    add(&mut controller.dimms, label, ErrorCounts { corrected: 0, uncorrected });
+                }
+            }
+        }
+
+        snapshot.controllers.insert(dir_name, controller);
+    }
+
+    snapshot
+}
+
+impl EdacSnapshot {
+    /// Returns `true` if any error source could be read.
+    pub fn is_available(&self) -> bool {
+        !self.controllers.is_empty() || self.machine_checks.is_some()
+    }
+
+    /// Builds an issue for every error counted since `before`.
+    ///
+    /// Errors a controller counts but does not attribute to a DIMM are
+    /// reported against the controller.
+    pub fn issues_since(&self, before: &EdacSnapshot) -> Vec<TestIssue> {
+        let mut issues = Vec::new();
+
+        for (dir, controller) in &self.controllers {
+            let previous = before.controllers.get(dir);
+            let mut attributed = ErrorCounts::default();
+
+            for (label, counts) in &controller.dimms {
+                let delta = counts.since(previous.and_then(|p| p.dimms.get(label)));
+                attributed.corrected += delta.corrected;
+                attributed.uncorrected += delta.uncorrected;
+                memory_issues(&mut issues, &format!("DIMM {}", label), delta);
+            }
+
+            let total = controller.totals.since(previous.map(|p| &p.totals));
+            let unattributed = ErrorCounts {
+                corrected: total.corrected.saturating_sub(attributed.corrected),
+                uncorrected: total.uncorrected.saturating_sub(attributed.uncorrected),
+            };
+            memory_issues(&mut issues, &format!("memory controller {} ({})", dir, controller.name), unattributed);
+        }
+
+        if let (Some(now), Some(then)) = (self.machine_checks, before.machine_checks) {
+            let delta = now.saturating_sub(then);
+            if delta > 0 {
+                issues.push(TestIssue {
+                    component: "cpu".to_string(),
+                    severity: IssueSeverity::High,
+                    message: format!("{} machine check exception{} during the run", delta, plural(delta)),
+                    action: Some("Check the kernel log, rasdaemon or mcelog for the failing bank and CPU".to_string()),
+                });
+            }
+        }
+
+        issues
+    }
+}
+
+fn memory_issues(issues: &mut Vec<TestIssue>, location: &str, delta: ErrorCounts) {
+    if delta.is_zero() {
+        return;
+    }
+
+    if delta.uncorrected > 0 {
+        issues.push(TestIssue {
+            component: "memory".to_string(),
+            severity: IssueSeverity::Critical,
+            message: format!("{} uncorrectable memory error{} on {}", delta.uncorrected, plural(delta.uncorrected), location),
+            action: Some(format!("Replace {}", location)),
+        });
+    }
+    if delta.corrected > 0 {
+        issues.push(TestIssue {
+            component: "memory".to_string(),
+            severity: IssueSeverity::High,
+            message: format!("{} corrected memory error{} on {}", delta.corrected, plural(delta.corrected), location),
+            action: Some(format!(
+                "Reseat or replace {}; corrected errors under load often precede uncorrectable ones",
+                location,
+            )),
+        });
+    }
+}
+
+/// Sums the per-CPU counts of the `MCE` row of `/proc/interrupts`.
+fn parse_machine_checks(interrupts: &str) -> Option<u64> {
+    let row = interrupts.lines().find(|line| line.trim_start().starts_with("MCE:"))?;
+    Some(row.split_whitespace()
+        .skip(1)
+        .map_while(|field| field.parse::<u64>().ok())
+        .sum())
+}
+
+/// Tracks EDAC and machine check counters across a run.
+#[derive(Debug, Default)]
+pub struct EdacMonitor {
+    before: Option<EdacSnapshot>,
+}
+
+impl HealthMonitor for EdacMonitor {
+    fn start(&mut self) {
+        self.before = Some(snapshot());
+    }
+
+    fn finish(&mut self) -> Vec<TestIssue> {
+        match self.before.take() {
+            Some(before) => snapshot().issues_since(&before),
+            None => Vec::new(),
+        }
+    }
+}
+
+fn add(dimms: &mut BTreeMap<String, ErrorCounts>, label: String, counts: ErrorCounts) {
+    let entry = dimms.entry(label).or_default();
+    entry.corrected += counts.corrected;
+    entry.uncorrected += counts.uncorrected;
+}
+
+fn plural(count: u64) -> &'static str {
+    if count == 1 { "" } else { "s" }
+}
+
+/// Returns the entries of `root` named a prefix followed by a number, in
+/// numeric order.
+fn sorted_entries(root: &Path, prefixes: &[&str]) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<(String, u32, PathBuf)> = entries.flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let prefix = prefixes.iter().find(|prefix| name.starts_with(*prefix))?;
+            let index = name[prefix.len()..].parse().ok()?;
+            Some((prefix.to_string(), index, entry.path()))
+        })
+        .collect();
+    dirs.sort();
+    dirs.into_iter().map(|(_, _, path)| path).collect()
+}
+
+fn read_count(path: &Path) -> Option<u64> {
+    read_trimmed(path)?.parse().ok()
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+        .filter(|contents| !contents.is_empty())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{fixture_dir, write};
+
+    const INTERRUPTS: &str = "           CPU0       CPU1
+  0:         35          0   IO-APIC    2-edge      timer
+ MCE:          1          2   Machine check exceptions
+ MCP:        120        120   Machine check polls
+";
+
+    fn fake_edac(root: &Path) -> PathBuf {
+        let mc = root.join("mc");
+
+        let mc0 = mc.join("mc0");
+        write(&mc0, "mc_name", "Skylake Socket#0 IMC#0");
+        write(&mc0, "ce_count", "3");
+        write(&mc0, "ue_count", "0");
+        write(&mc0.join("dimm0"), "dimm_label", "CPU_SrcID#0_MC#0_Chan#0_DIMM#0");
+        write(&mc0.join("dimm0"), "dimm_ce_count", "2");
+        write(&mc0.join("dimm0"), "dimm_ue_count", "0");
+        write(&mc0.join("dimm1"), "dimm_ce_count", "0");
+        write(&mc0.join("dimm1"), "dimm_ue_count", "0");
+
+        let mc1 = mc.join("mc1");
+        write(&mc1, "ce_count", "0");
+        write(&mc1, "ue_count", "0");
+        let csrow = mc1.join("csrow0");
+        write(&csrow, "ch0_ce_count", "0");
+        write(&csrow, "ch0_dimm_label", "DIMM_B1");
+        write(&csrow, "ch1_ce_count", "0");
+        write(&csrow, "ch1_dimm_label", "DIMM_B2");
+        write(&csrow, "ue_count", "0");
+
+        mc
+    }
+
+    #[test]
+    fn test_snapshot_from_fake_sysfs() {
+        let root = fixture_dir("edac-snapshot");
+        let mc = fake_edac(&root);
+        let interrupts = root.join("interrupts");
+        fs::write(&interrupts, INTERRUPTS).unwrap();
+
+        let snapshot = snapshot_from(&mc, &interrupts);
+        assert!(snapshot.is_available());
+        assert_eq!(snapshot.machine_checks, Some(3));
+
+        let mc0 = &snapshot.controllers["mc0"];
+        assert_eq!(mc0.name, "Skylake Socket#0 IMC#0");
+        assert_eq!(mc0.totals, ErrorCounts { corrected: 3, uncorrected: 0 });
+        assert_eq!(mc0.dimms["CPU_SrcID#0_MC#0_Chan#0_DIMM#0"].corrected, 2);
+        assert!(mc0.dimms.contains_key("mc0/dimm1"));
+
+        let mc1 = &snapshot.controllers["mc1"];
+        assert_eq!(mc1.name, "mc1");
+        assert_eq!(mc1.dimms.keys().collect::<Vec<_>>(), ["DIMM_B1", "DIMM_B2"]);
+
+        assert!(!snapshot_from(&root.join("missing"), &root.join("missing")).is_available());
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_issues_since() {
+        let root = fixture_dir("edac-delta");
+        let mc = fake_edac(&root);
+        let interrupts = root.join("interrupts");
+        fs::write(&interrupts, INTERRUPTS).unwrap();
+        let before = snapshot_from(&mc, &interrupts);
+        assert!(before.issues_since(&before).is_empty());
+
+        write(&mc.join("mc0/dimm0"), "dimm_ce_count", "7");
+        write(&mc.join("mc0"), "ce_count", "9");
+        write(&mc.join("mc1/csrow0"), "ue_count", "1");
+        write(&mc.join("mc1"), "ue_count", "1");
+        fs::write(&interrupts, INTERRUPTS.replace("MCE:          1", "MCE:          2")).unwrap();
+        let after = snapshot_from(&mc, &interrupts);
+
+        let issues = after.issues_since(&before);
+        let messages: Vec<_> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages, [
+            "5 corrected memory errors on DIMM CPU_SrcID#0_MC#0_Chan#0_DIMM#0",
+            "1 corrected memory error on memory controller mc0 (Skylake Socket#0 IMC#0)",
+            "1 uncorrectable memory error on DIMM DIMM_B1 / DIMM_B2",
+            "1 machine check exception during the run",
+        ]);
+        assert_eq!(issues[2].severity, IssueSeverity::Critical);
+        assert_eq!(issues[2].component, "memory");
+        assert_eq!(issues[3].component, "cpu");
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,5 +1,17 @@
 use serde::{Serialize, Deserialize};
 use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::OnceLock;
+use sysinfo::{System, Disks, DiskKind};
+
+use crate::core::cache::detect_cache_sizes;
+use crate::core::cgroup;
+use crate::core::cpu_features::detect_cpu_features;
+use crate::core::dmi;
+use crate::core::hwmon;
+use crate::core::pci;
+use crate::core::virt::detect_virtualization;
 
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -10,6 +22,16 @@ pub struct HardwareInfo {
     pub storage_devices: Vec<StorageDevice>,
     pub virtualization: Option<VirtualizationType>,
     pub thermal_sensors: Vec<ThermalSensor>,
+    #[serde(default)]
+    pub fans: Vec<FanSensor>,
+    #[serde(default)]
+    pub voltage_sensors: Vec<VoltageSensor>,
+    #[serde(default)]
+    pub resource_limits: ResourceLimits,
+    #[serde(default)]
+    pub dmi: DmiInfo,
+    #[serde(default)]
+    pub pci_devices: Vec<PciDevice>,
 }
 
 
@@ -40,6 +62,50 @@ pub struct MemoryInfo {
     pub available_bytes: u64,
     pub speed_mhz: Option<u32>,
     pub ecc_enabled: Option<bool>,
+    #[serde(default)]
+    pub modules: Vec<MemoryModule>,
+}
+
+
+/// One populated memory slot, from an SMBIOS type 17 structure.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct MemoryModule {
+    pub locator: String,
+    pub bank: Option<String>,
+    pub size_bytes: u64,
+    pub memory_type: Option<String>,
+    pub speed_mhz: Option<u32>,
+    pub configured_speed_mhz: Option<u32>,
+    pub manufacturer: Option<String>,
+    pub part_number: Option<String>,
+    pub serial_number: Option<String>,
+}
+
+
+/// Machine identity from `/sys/class/dmi/id`.
+///
+/// Serial numbers are only readable by root and are `None` otherwise.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct DmiInfo {
+    pub system_vendor: Option<String>,
+    pub system_product: Option<String>,
+    pub system_serial: Option<String>,
+    pub board_vendor: Option<String>,
+    pub board_product: Option<String>,
+    pub board_serial: Option<String>,
+    pub bios_vendor: Option<String>,
+    pub bios_version: Option<String>,
+    pub bios_date: Option<String>,
+}
+
+impl DmiInfo {
+    /// Vendor and product name of the machine, if either is known.
+    pub fn machine(&self) -> Option<String> {
+        let parts: Vec<&str> = [&self.system_vendor, &self.system_product].iter()
+            .filter_map(|part| part.as_deref())
+            .collect();
+        (!parts.is_empty()).then(|| parts.join(" "))
+    }
 }
 
 
@@ -84,6 +150,147 @@ pub struct ThermalSensor {
     pub location: String,
     pub current_temp_celsius: f32,
     pub critical_temp_celsius: Option<f32>,
+    #[serde(default)]
+    pub max_temp_celsius: Option<f32>,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FanSensor {
+    pub name: String,
+    pub location: String,
+    pub rpm: u32,
+    pub min_rpm: Option<u32>,
+}
+
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct VoltageSensor {
+    pub name: String,
+    pub location: String,
+    pub volts: f32,
+    pub min_volts: Option<f32>,
+    pub max_volts: Option<f32>,
+}
+
+
+/// CPU and memory limits imposed on this process by its cgroup.
+///
+/// `None` means the cgroup does not limit that resource.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct ResourceLimits {
+    pub cpu_quota_cores: Option<f64>,
+    pub cpuset_cpus: Option<u32>,
+    pub memory_limit_bytes: Option<u64>,
+    pub memory_usage_bytes: Option<u64>,
+}
+
+impl ResourceLimits {
+    /// Returns `true` if the cgroup limits CPU or memory.
+    pub fn is_limited(&self) -> bool {
+        self.cpu_quota_cores.is_some() || self.cpuset_cpus.is_some() || self.memory_limit_bytes.is_some()
+    }
+    
+    /// Number of CPUs the process can keep busy, given `host_cpus` online CPUs.
+    ///
+    /// A fractional quota is rounded up, so a 1.5 CPU quota allows two threads.
+    pub fn effective_cpus(&self, host_cpus: usize) -> usize {
+        let mut cpus = host_cpus;
+        if let Some(cpuset) = self.cpuset_cpus {
+            cpus = cpus.min(cpuset as usize);
+        }
+        if let Some(quota) = self.cpu_quota_cores {
+            cpus = cpus.min(quota.ceil() as usize);
+        }
+        cpus.max(1)
+    }
+    
+    /// Memory the process can still allocate, given `host_available` bytes
+    /// available on the host.
+    pub fn available_memory(&self, host_available: u64) -> u64 {
+        match self.memory_limit_bytes {
+            Some(limit) => {
+                let used = self.memory_usage_bytes.unwrap_or(0);
+                host_available.min(limit.saturating_sub(used))
+            }
+            None => host_available,
+        }
+    }
+}
+
+
+/// A PCI Express device from `/sys/bus/pci/devices`.
+///
+/// Link fields are `None` for conventional PCI devices and for functions
+/// whose link is not exposed, such as most virtual devices.
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct PciDevice {
+    pub address: String,
+    pub vendor_id: String,
+    pub device_id: String,
+    pub class: String,
+    pub driver: Option<String>,
+    pub current_link_speed_gts: Option<f32>,
+    pub max_link_speed_gts: Option<f32>,
+    pub current_link_width: Option<u32>,
+    pub max_link_width: Option<u32>,
+    pub aer: Option<AerCounts>,
+    /// Address of the bridge the device hangs off, if it is a PCI device
+    pub upstream: Option<String>,
+}
+
+impl PciDevice {
+    /// Returns `true` if the link trained below the speed or width the
+    /// device supports. A link without a width is down, as on the root
+    /// port of an empty slot, and is never downtrained.
+    pub fn is_downtrained(&self) -> bool {
+        let Some(width) = self.current_link_width.filter(|width| *width > 0) else {
+            return false;
+        };
+        let slower = matches!(
+            (self.current_link_speed_gts, self.max_link_speed_gts),
+            (Some(current), Some(max)) if current > 0.0 && current < max
+        );
+        slower || self.max_link_width.is_some_and(|max| width < max)
+    }
+
+    /// Returns `true` if the link trained below the width the device
+    /// supports.
+    pub fn is_narrowed(&self) -> bool {
+        matches!(
+            (self.current_link_width, self.max_link_width),
+            (Some(current), Some(max)) if current > 0 && current < max
+        )
+    }
+
+    /// Returns `true` for PCI-to-PCI bridges, such as root ports.
+    pub fn is_bridge(&self) -> bool {
+        self.class.trim_start_matches("0x").starts_with("0604")
+    }
+
+    /// Returns `true` for display controllers, which may lower their link
+    /// speed while idle.
+    pub fn is_display(&self) -> bool {
+        self.class.trim_start_matches("0x").starts_with("03")
+    }
+}
+
+/// Advanced Error Reporting totals of a PCIe device since boot.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Serialize, Deserialize)]
+pub struct AerCounts {
+    pub correctable: u64,
+    pub nonfatal: u64,
+    pub fatal: u64,
+}
+
+
+/// A test parameter changed by [`SystemProfile::adapt_test_config`].
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct ConfigAdjustment {
+    pub parameter: String,
+    pub from: String,
+    pub to: String,
+    pub reason: String,
 }
 
 
@@ -101,46 +308,264 @@ impl SystemProfile {
     
     pub fn optimize_test_config(&self, base_config: &crate::core::config::TestConfig) 
         -> crate::core::config::TestConfig {
+        self.adapt_test_config(base_config).0
+    }
+    
+    /// Adapts `base_config` to this system and lists every parameter that was
+    /// changed, with the reason.
+    pub fn adapt_test_config(&self, base_config: &crate::core::config::TestConfig) 
+        -> (crate::core::config::TestConfig, Vec<ConfigAdjustment>) {
         
         let mut optimized = base_config.clone();
+        let mut adjustments = Vec::new();
+        let mut adjust = |parameter: &str, from: String, to: String, reason: String| {
+            if from != to {
+                adjustments.push(ConfigAdjustment { parameter: parameter.to_string(), from, to, reason });
+            }
+        };
         
         
         if let Some(virt_type) = &self.hardware_info.virtualization {
-            match virt_type {
-                VirtualizationType::Docker | VirtualizationType::Lxc => {
-                    
-                    optimized.stress_level = (f64::from(optimized.stress_level) * 0.7).round() as u8;
-                    optimized.thermal_monitoring = false;
-                }
-                _ => {
-                    
-                    optimized.stress_level = (f64::from(optimized.stress_level) * 0.8).round() as u8;
-                }
+            let stress_factor = match virt_type {
+                VirtualizationType::None => None,
+                VirtualizationType::Docker | VirtualizationType::Lxc => Some((0.7, format!("running in a {:?} container", virt_type))),
+                VirtualizationType::Unknown => Some((0.8, "running under an unrecognized hypervisor".to_string())),
+                _ => Some((0.8, format!("running in a {:?} virtual machine", virt_type))),
+            };
+            
+            if let Some((factor, reason)) = stress_factor {
+                let stress_level = (f64::from(optimized.stress_level) * factor).round() as u8;
+                adjust("stress_level", optimized.stress_level.to_string(), stress_level.to_string(), reason);
+                optimized.stress_level = stress_level;
+            }
+            
+            if matches!(virt_type, VirtualizationType::Docker | VirtualizationType::Lxc) && optimized.thermal_monitoring {
+                adjust("thermal_monitoring", "true".to_string(), "false".to_string(),
+                    "host temperature sensors are not reliable inside a container".to_string());
+                optimized.thermal_monitoring = false;
             }
         }
         
         
         let mem_info = &self.hardware_info.memory_info;
-        let available_gb = mem_info.available_bytes as f64 / 1_073_741_824.0;
-        if available_gb < 2.0 {
-            
-            optimized.memory_test_size_percent = 50;
+        let limits = &self.hardware_info.resource_limits;
+        let available_gb = limits.available_memory(mem_info.available_bytes) as f64 / 1_073_741_824.0;
+        let memory_percent = if available_gb < 2.0 {
+            Some(50)
         } else if available_gb < 8.0 {
-            
-            optimized.memory_test_size_percent = 70;
+            Some(70)
+        } else {
+            None
+        };
+        if let Some(percent) = memory_percent.filter(|p| *p < optimized.memory_test_size_percent) {
+            adjust("memory_test_size_percent", optimized.memory_test_size_percent.to_string(), percent.to_string(),
+                format!("only {:.1} GB of memory is available", available_gb));
+            optimized.memory_test_size_percent = percent;
         }
         
         
         let cpu_info = &self.hardware_info.cpu_info;
         if optimized.threads == 0 {  
             
-            optimized.threads = (cpu_info.logical_cores as f32 * 0.75).round() as u32;
-            
-            if optimized.threads == 0 {
-                optimized.threads = 1;
+            let cpus = limits.effective_cpus(cpu_info.logical_cores as usize);
+            optimized.threads = ((cpus as f32 * 0.75).round() as u32).max(1);
+            adjust("threads", "auto".to_string(), optimized.threads.to_string(),
+                format!("75% of the {} available CPUs", cpus));
+        }
+        
+        (optimized, adjustments)
+    }
+}
+
+/// Hardware detected by the first call to [`detect`].
+static DETECTED: OnceLock<HardwareInfo> = OnceLock::new();
+
+/// Returns the hardware of this system, detecting it on the first call.
+///
+/// Later calls return the same snapshot, so sensor values are those seen at
+/// the start of the run; read [`hwmon`] directly for live values.
+pub fn detect() -> HardwareInfo {
+    DETECTED.get_or_init(detect_uncached).clone()
+}
+
+/// Detects every part of the hardware description without caching.
+pub fn detect_uncached() -> HardwareInfo {
+    let mut system = System::new_all();
+    system.refresh_cpu();
+    
+    // The global entry only aggregates usage; brand, vendor and frequency
+    // are reported per core.
+    let cpu = system.cpus().first().unwrap_or_else(|| system.global_cpu_info());
+    let cpu_info = CpuInfo {
+        model_name: cpu.brand().trim().to_string(),
+        vendor: cpu.vendor_id().to_string(),
+        physical_cores: num_cpus::get_physical() as u32,
+        logical_cores: num_cpus::get() as u32,
+        frequency_mhz: cpu.frequency() as f64,
+        cache_sizes: detect_cache_sizes(),
+        features: detect_cpu_features(),
+    };
+    
+    let sensors = hwmon::read_sensors();
+    let smbios = dmi::read_smbios_memory();
+    
+    HardwareInfo {
+        system_info: SystemInfo {
+            hostname: System::host_name().unwrap_or_else(|| "unknown".to_string()),
+            os_name: System::name().unwrap_or_else(|| "unknown".to_string()),
+            os_version: System::os_version().unwrap_or_else(|| "unknown".to_string()),
+            kernel_version: System::kernel_version().unwrap_or_else(|| "unknown".to_string()),
+        },
+        cpu_info,
+        memory_info: MemoryInfo {
+            total_bytes: system.total_memory(),
+            available_bytes: system.available_memory(),
+            speed_mhz: smbios.speed_mhz(),
+            ecc_enabled: smbios.ecc_enabled,
+            modules: smbios.modules,
+        },
+        storage_devices: detect_storage_devices(),
+        virtualization: Some(detect_virtualization()),
+        thermal_sensors: hwmon::read_temperatures(&sensors),
+        fans: sensors.fans,
+        voltage_sensors: sensors.voltages,
+        resource_limits: cgroup::read_limits(),
+        dmi: dmi::read_dmi_id(),
+        pci_devices: pci::read_devices(),
+    }
+}
+
+/// Lists the mounted disks, with the model and type of the block device
+/// behind each one where sysfs knows it.
+fn detect_storage_devices() -> Vec<StorageDevice> {
+    let disks = Disks::new_with_refreshed_list();
+    let mut devices = Vec::new();
+    
+    for disk in &disks {
+        let name = disk.name().to_string_lossy().to_string();
+        let block = Path::new("/sys/class/block").join(name.trim_start_matches("/dev/"));
+        let device = parent_block_device(&block);
+        
+        let device_type = if name.contains("nvme") {
+            DiskType::Nvme
+        } else {
+            match disk.kind() {
+                DiskKind::SSD => DiskType::Ssd,
+                DiskKind::HDD => DiskType::Hdd,
+                _ => DiskType::Unknown,
             }
+        };
+        
+        devices.push(StorageDevice {
+            name,
+            model: device.as_deref()
+                .and_then(|dir| fs::read_to_string(dir.join("device/model")).ok())
+                .map(|model| model.trim().to_string())
+                .filter(|model| !model.is_empty())
+                .unwrap_or_else(|| "Unknown".to_string()),
+            device_type,
+            size_bytes: disk.total_space(),
+            mount_point: Some(disk.mount_point().to_string_lossy().to_string()),
+            filesystem: Some(disk.file_system().to_string_lossy().to_string()),
+            smart_supported: false,
+        });
+    }
+    
+    devices
+}
+
+/// Resolves a block device in `/sys/class/block` to its whole-disk device,
+/// going up one level for partitions.
+fn parent_block_device(block: &Path) -> Option<PathBuf> {
+    let dir = fs::canonicalize(block).ok()?;
+    if dir.join("partition").exists() {
+        dir.parent().map(Path::to_path_buf)
+    } else {
+        Some(dir)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::config::TestConfig;
+
+    fn hardware(virtualization: VirtualizationType, available_gb: u64, cores: u32) -> HardwareInfo {
+        HardwareInfo {
+            system_info: SystemInfo {
+                hostname: "test".to_string(),
+                os_name: "Linux".to_string(),
+                os_version: "6.1".to_string(),
+                kernel_version: "6.1.0".to_string(),
+            },
+            cpu_info: CpuInfo {
+                model_name: "Test CPU".to_string(),
+                vendor: "GenuineIntel".to_string(),
+                physical_cores: cores,
+                logical_cores: cores,
+                frequency_mhz: 3000.0,
+                cache_sizes: HashMap::new(),
+                features: Vec::new(),
+            },
+            memory_info: MemoryInfo {
+                total_bytes: available_gb << 31,
+                available_bytes: available_gb << 30,
+                speed_mhz: None,
+                ecc_enabled: None,
+                modules: Vec::new(),
+            },
+            storage_devices: Vec::new(),
+            virtualization: Some(virtualization),
+            thermal_sensors: Vec::new(),
+            fans: Vec::new(),
+            voltage_sensors: Vec::new(),
+            resource_limits: ResourceLimits::default(),
+            dmi: DmiInfo::default(),
+            pci_devices: Vec::new(),
         }
+    }
+
+    #[test]
+    fn test_adapt_bare_metal() {
+        let profile = SystemProfile::new(hardware(VirtualizationType::None, 64, 16));
+        let (config, adjustments) = profile.adapt_test_config(&TestConfig::default());
+        assert_eq!(config.stress_level, 8);
+        assert_eq!(config.threads, 12);
+        assert_eq!(adjustments, vec![ConfigAdjustment {
+            parameter: "threads".to_string(),
+            from: "auto".to_string(),
+            to: "12".to_string(),
+            reason: "75% of the 16 available CPUs".to_string(),
+        }]);
+    }
+
+    #[test]
+    fn test_adapt_container_with_cgroup_limits() {
+        let mut info = hardware(VirtualizationType::Docker, 64, 16);
+        info.resource_limits.cpu_quota_cores = Some(4.0);
+        info.resource_limits.memory_limit_bytes = Some(1 << 30);
+        let profile = SystemProfile::new(info);
         
-        optimized
+        let base = TestConfig { threads: 2, ..TestConfig::default() };
+        let (config, adjustments) = profile.adapt_test_config(&base);
+        assert_eq!(config.stress_level, 6);
+        assert!(!config.thermal_monitoring);
+        assert_eq!(config.memory_test_size_percent, 50);
+        assert_eq!(config.threads, 2);
+        
+        let parameters: Vec<_> = adjustments.iter().map(|a| a.parameter.as_str()).collect();
+        assert_eq!(parameters, ["stress_level", "thermal_monitoring", "memory_test_size_percent"]);
+        assert_eq!(adjustments[2].reason, "only 1.0 GB of memory is available");
+    }
+
+    #[test]
+    fn test_detect_is_cached_and_complete() {
+        let first = detect();
+        let second = detect();
+        assert_eq!(first.system_info.hostname, second.system_info.hostname);
+        assert_eq!(first.cpu_info.logical_cores, num_cpus::get() as u32);
+        assert!(first.virtualization.is_some());
+        assert_eq!(first.thermal_sensors.len(), second.thermal_sensors.len());
+        assert_eq!(first.storage_devices.len(), second.storage_devices.len());
     }
 }
@@ -0,0 +1,334 @@
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use sysinfo::Components;
+
+use crate::core::hardware::{ThermalSensor, FanSensor, VoltageSensor};
+
+/// Location of the hardware monitoring chips in sysfs.
+pub const SYSFS_HWMON: &str = "/sys/class/hwmon";
+
+/// Location of the ACPI and platform thermal zones in sysfs.
+pub const SYSFS_THERMAL: &str = "/sys/class/thermal";
+
+/// Everything the sensor backend found in one pass over sysfs.
+#[derive(Debug, Clone, Default)]
+pub struct SensorReadings {
+    pub temperatures: Vec<ThermalSensor>,
+    pub fans: Vec<FanSensor>,
+    pub voltages: Vec<VoltageSensor>,
+}
+
+/// Reads all hwmon chips and thermal zones of the running system.
+pub fn read_sensors() -> SensorReadings {
+    read_sensors_from(Path::new(SYSFS_HWMON), Path::new(SYSFS_THERMAL))
+}
+
+/// Reads all hwmon chips under `hwmon_root` and thermal zones under
+/// `thermal_root`.
+///
+/// Sensor names are `chip/label`, falling back to the channel name such as
+/// `temp1` when the chip provides no label. Thermal zones that are also
+/// exported as an hwmon chip of the same name are skipped. Names that repeat,
+/// such as the composite temperature of several NVMe drives, are numbered
+/// `name #2`, `name #3`, ... in sysfs order.
+pub fn read_sensors_from(hwmon_root: &Path, thermal_root: &Path) -> SensorReadings {
+    let mut readings = SensorReadings::default();
+    let mut chips = Vec::new();
+
+    for dir in sorted_entries(hwmon_root, "hwmon") {
+        let chip = read_trimmed(&dir.join("name")).unwrap_or_else(|| file_name(&dir));
+        let location = fs::read_link(dir.join("device"))
+            .ok()
+            .map(|target| file_name(&target))
+            .unwrap_or_else(|| file_name(&dir));
+
+        for index in channels(&dir, "temp") {
+            let Some(temp) = read_scaled(&dir, &format!("temp{}_input", index), 1000.0) else {
+                continue;
+            };
+            readings.temperatures.push(ThermalSensor {
+                name: channel_name(&dir, &chip, "temp", index),
+                location: location.clone(),
+                current_temp_celsius: temp,
+                critical_temp_celsius: read_scaled(&dir, &format!("temp{}_crit", index), 1000.0),
+                max_temp_celsius: read_scaled(&dir, &format!("temp{}_max", index), 1000.0),
+            });
+        }
+
+        for index in channels(&dir, "fan") {
+            let Some(rpm) = read_scaled(&dir, &format!("fan{}_input", index), 1.0) else {
+                continue;
+            };
+            readings.fans.push(FanSensor {
+                name: channel_name(&dir, &chip, "fan", index),
+                location: location.clone(),
+                rpm: rpm as u32,
+                min_rpm: read_scaled(&dir, &format!("fan{}_min", index), 1.0).map(|rpm| rpm as u32),
+            });
+        }
+
+        for index in channels(&dir, "in") {
+            let Some(volts) = read_scaled(&dir, &format!("in{}_input", index), 1000.0) else {
+                continue;
+            };
+            readings.voltages.push(VoltageSensor {
+                name: channel_name(&dir, &chip, "in", index),
+                location: location.clone(),
+                volts,
+                min_volts: read_scaled(&dir, &format!("in{}_min", index), 1000.0),
+                max_volts: read_scaled(&dir, &format!("in{}_max", index), 1000.0),
+            });
+        }
+
+        chips.push(chip);
+    }
+
+    for dir in sorted_entries(thermal_root, "thermal_zone") {
+        let Some(zone_type) = read_trimmed(&dir.join("type")) else {
+            continue;
+        };
+        if chips.contains(&zone_type) {
+            continue;
+        }
+        let Some(temp) = read_scaled(&dir, "temp", 1000.0) else {
+            continue;
+        };
+
+        readings.temperatures.push(ThermalSensor {
+            name: zone_type,
+            location: file_name(&dir),
+            current_temp_celsius: temp,
+            critical_temp_celsius: trip_point(&dir, "critical"),
+            max_temp_celsius: trip_point(&dir, "hot"),
+        });
+    }
+
+    number_repeats(readings.temperatures.iter_mut().map(|sensor| &mut sensor.name));
+    number_repeats(readings.fans.iter_mut().map(|sensor| &mut sensor.name));
+    number_repeats(readings.voltages.iter_mut().map(|sensor| &mut sensor.name));
+    readings
+}
+
+/// Returns the temperatures read from sysfs, or reads them through sysinfo
+/// where sysfs has none, naming repeated labels `label #2`, `label #3`, ...
+pub fn read_temperatures(sysfs: &SensorReadings) -> Vec<ThermalSensor> {
+    if !sysfs.temperatures.is_empty() {
+        return sysfs.temperatures.clone();
+    }
+
+    let components = Components::new_with_refreshed_list();
+    let mut readings = Vec::new();
+
+    for component in &components {
+        let temp = component.temperature();
+        if temp.is_nan() {
+            continue;
+        }
+        readings.push(ThermalSensor {
+            name: component.label().to_string(),
+            location: "Unknown".to_string(),
+            current_temp_celsius: temp,
+            critical_temp_celsius: component.critical(),
+            max_temp_celsius: None,
+        });
+    }
+
+    number_repeats(readings.iter_mut().map(|sensor| &mut sensor.name));
+    readings
+}
+
+/// Renames the second and later occurrences of a name to `name #2`,
+/// `name #3`, ...
+fn number_repeats<'a>(names: impl Iterator<Item = &'a mut String>) {
+    let mut seen: HashMap<String, usize> = HashMap::new();
+    for name in names {
+        let count = seen.entry(name.clone()).or_insert(0);
+        *count += 1;
+        if *count > 1 {
+            *name = format!("{} #{}", name, count);
+        }
+    }
+}
+
+/// Returns the entries of `root` whose names start with `prefix`, in numeric order.
+fn sorted_entries(root: &Path, prefix: &str) -> Vec<PathBuf> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let mut dirs: Vec<(u32, PathBuf)> = entries.flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            let index = name.strip_prefix(prefix)?.parse().ok()?;
+            Some((index, entry.path()))
+        })
+        .collect();
+    dirs.sort();
+    dirs.into_iter().map(|(_, path)| path).collect()
+}
+
+/// Returns the channel numbers of `kind` (`temp`, `fan` or `in`) that have an input file.
+fn channels(dir: &Path, kind: &str) -> Vec<u32> {
+    let Ok(entries) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+
+    let mut indices: Vec<u32> = entries.flatten()
+        .filter_map(|entry| {
+            let name = entry.file_name().to_string_lossy().into_owned();
+            name.strip_prefix(kind)?.strip_suffix("_input")?.parse().ok()
+        })
+        .collect();
+    indices.sort();
+    indices
+}
+
+fn channel_name(dir: &Path, chip: &str, kind: &str, index: u32) -> String {
+    let label = read_trimmed(&dir.join(format!("{}{}_label", kind, index)))
+        .unwrap_or_else(|| format!("{}{}", kind, index));
+    format!("{}/{}", chip, label)
+}
+
+/// Finds the temperature of the first trip point of the given type.
+fn trip_point(zone: &Path, trip_type: &str) -> Option<f32> {
+    (0..32)
+        .take_while(|index| zone.join(format!("trip_point_{}_type", index)).exists())
+        .find(|index| read_trimmed(&zone.join(format!("trip_point_{}_type", index))).as_deref() == Some(trip_type))
+        .and_then(|index| read_scaled(zone, &format!("trip_point_{}_temp", index), 1000.0))
+}
+
+/// Reads an integer sysfs attribute and divides it by `divisor`.
+fn read_scaled(dir: &Path, file: &str, divisor: f32) -> Option<f32> {
+    read_trimmed(&dir.join(file))?
+        .parse::<i64>()
+        .ok()
+        .map(|value| value as f32 / divisor)
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+        .filter(|contents| !contents.is_empty())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{fixture_dir, write};
+
+    fn fake_sysfs(root: &Path) -> (PathBuf, PathBuf) {
+        let hwmon = root.join("hwmon");
+        let thermal = root.join("thermal");
+
+        let coretemp = hwmon.join("hwmon1");
+        write(&coretemp, "name", "coretemp");
+        write(&coretemp, "temp1_input", "54000");
+        write(&coretemp, "temp1_label", "Package id 0");
+        write(&coretemp, "temp1_crit", "100000");
+        write(&coretemp, "temp1_max", "80000");
+        write(&coretemp, "temp2_input", "51500");
+        fs::create_dir_all(root.join("devices/coretemp.0")).unwrap();
+        std::os::unix::fs::symlink(root.join("devices/coretemp.0"), coretemp.join("device")).unwrap();
+
+        let superio = hwmon.join("hwmon10");
+        write(&superio, "name", "nct6798");
+        write(&superio, "fan1_input", "1200");
+        write(&superio, "fan1_label", "CPU Fan");
+        write(&superio, "fan1_min", "300");
+        write(&superio, "fan2_input", "0");
+        write(&superio, "in0_input", "1112");
+        write(&superio, "in0_label", "Vcore");
+        write(&superio, "in0_min", "800");
+        write(&superio, "in0_max", "1500");
+
+        for (chip, temp) in [("hwmon2", "41850"), ("hwmon3", "38850")] {
+            let nvme = hwmon.join(chip);
+            write(&nvme, "name", "nvme");
+            write(&nvme, "temp1_input", temp);
+            write(&nvme, "temp1_label", "Composite");
+        }
+
+        let acpi = hwmon.join("hwmon0");
+        write(&acpi, "name", "acpitz");
+        write(&acpi, "temp1_input", "27800");
+
+        let zone0 = thermal.join("thermal_zone0");
+        write(&zone0, "type", "acpitz");
+        write(&zone0, "temp", "27800");
+
+        let zone1 = thermal.join("thermal_zone1");
+        write(&zone1, "type", "x86_pkg_temp");
+        write(&zone1, "temp", "55000");
+        write(&zone1, "trip_point_0_type", "passive");
+        write(&zone1, "trip_point_0_temp", "95000");
+        write(&zone1, "trip_point_1_type", "critical");
+        write(&zone1, "trip_point_1_temp", "105000");
+        write(&thermal.join("cooling_device0"), "type", "Processor");
+
+        let zone2 = thermal.join("thermal_zone2");
+        write(&zone2, "type", "x86_pkg_temp");
+        write(&zone2, "temp", "53000");
+
+        (hwmon, thermal)
+    }
+
+    #[test]
+    fn test_read_sensors_from_fake_sysfs() {
+        let root = fixture_dir("hwmon-sysfs");
+        let (hwmon, thermal) = fake_sysfs(&root);
+        let readings = read_sensors_from(&hwmon, &thermal);
+
+        let names: Vec<_> = readings.temperatures.iter().map(|t| t.name.as_str()).collect();
+        assert_eq!(names, [
+            "acpitz/temp1", "coretemp/Package id 0", "coretemp/temp2", "nvme/Composite", "nvme/Composite #2",
+            "x86_pkg_temp", "x86_pkg_temp #2",
+        ]);
+
+        let package = &readings.temperatures[1];
+        assert_eq!(package.location, "coretemp.0");
+        assert_eq!(package.current_temp_celsius, 54.0);
+        assert_eq!(package.critical_temp_celsius, Some(100.0));
+        assert_eq!(package.max_temp_celsius, Some(80.0));
+
+        assert_eq!(readings.temperatures[4].current_temp_celsius, 38.85);
+
+        let zone = &readings.temperatures[5];
+        assert_eq!(zone.location, "thermal_zone1");
+        assert_eq!(zone.critical_temp_celsius, Some(105.0));
+        assert_eq!(zone.max_temp_celsius, None);
+
+        assert_eq!(readings.fans.len(), 2);
+        assert_eq!(readings.fans[0].name, "nct6798/CPU Fan");
+        assert_eq!(readings.fans[0].rpm, 1200);
+        assert_eq!(readings.fans[0].min_rpm, Some(300));
+        assert_eq!(readings.fans[1].name, "nct6798/fan2");
+        assert_eq!(readings.fans[1].rpm, 0);
+
+        assert_eq!(readings.voltages.len(), 1);
+        let vcore = &readings.voltages[0];
+        assert_eq!(vcore.name, "nct6798/Vcore");
+        assert_eq!(vcore.location, "hwmon10");
+        assert!((vcore.volts - 1.112).abs() < 1e-6);
+        assert_eq!(vcore.min_volts, Some(0.8));
+        assert_eq!(vcore.max_volts, Some(1.5));
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_read_sensors_missing_sysfs() {
+        let readings = read_sensors_from(Path::new("/nonexistent/hwmon"), Path::new("/nonexistent/thermal"));
+        assert!(readings.temperatures.is_empty());
+        assert!(readings.fans.is_empty());
+        assert!(readings.voltages.is_empty());
+    }
+}
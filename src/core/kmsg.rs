@@ -0,0 +1,427 @@
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{ErrorKind, Read, Seek, SeekFrom};
+use std::os::unix::fs::OpenOptionsExt;
+use std::path::PathBuf;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::Duration;
+
+use crate::core::monitor::HealthMonitor;
+use crate::core::test::{TestIssue, IssueSeverity};
+
+/// The kernel's structured log device.
+pub const DEV_KMSG: &str = "/dev/kmsg";
+
+/// How often the tail thread checks for new records.
+const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Longest kernel log record; reads from `/dev/kmsg` return one record and
+/// fail with `EINVAL` if the buffer is smaller.
+const MAX_RECORD: usize = 8192;
+
+/// Example lines kept per rule for the issue message.
+const MAX_EXAMPLES: usize = 3;
+
+/// A class of kernel log lines that indicate a hardware problem.
+///
+/// A line matches if it contains every string of any one of `patterns`,
+/// compared in lowercase, and passes `filter` if there is one.
+struct Rule {
+    patterns: &'static [&'static [&'static str]],
+    filter: Option<fn(&str) -> bool>,
+    component: &'static str,
+    severity: IssueSeverity,
+    description: &'static str,
+    action: &'static str,
+}
+
+/// Checked in order; the first matching rule classifies a line.
+///
+/// Machine checks and PCIe AER errors are left to the EDAC and PCI monitors,
+/// which count them without depending on the log.
+const RULES: &[Rule] = &[
+    Rule {
+        filter: None,
+        patterns: &[&["nvme", "timeout"], &["nvme", "controller is down"], &["nvme", "resetting controller"]],
+        component: "storage",
+        severity: IssueSeverity::High,
+        description: "NVMe command timeouts or controller resets",
+        action: "Check the NVMe drive's SMART log and firmware, and reseat the drive",
+    },
+    Rule {
+        filter: Some(names_physical_disk),
+        patterns: &[&["i/o error"], &["medium error"], &["unrecovered read error"], &["blk_update_request", "error"]],
+        component: "storage",
+        severity: IssueSeverity::High,
+        description: "block device I/O errors",
+        action: "Check the drive's SMART data and cabling; replace the drive if errors persist",
+    },
+    Rule {
+        filter: None,
+        patterns: &[&["ata", "hard resetting link"], &["ata", "exception emask"], &["ata", "failed command"], &["ata", "link is slow to respond"]],
+        component: "storage",
+        severity: IssueSeverity::High,
+        description: "SATA link errors or resets",
+        action: "Check the SATA cable, backplane and port",
+    },
+    Rule {
+        filter: None,
+        patterns: &[&["ext4-fs error"], &["xfs", "corruption"], &["btrfs", "csum failed"]],
+        component: "storage",
+        severity: IssueSeverity::High,
+        description: "filesystem errors",
+        action: "Unmount and check the filesystem, then look for an underlying disk fault",
+    },
+    Rule {
+        filter: Some(names_pci_device),
+        patterns: &[&["link is down"], &["link down"]],
+        component: "network",
+        severity: IssueSeverity::Medium,
+        description: "network link drops",
+        action: "Check the cable, transceiver and switch port",
+    },
+    Rule {
+        filter: None,
+        patterns: &[&["transmit queue", "timed out"], &["tx timeout"]],
+        component: "network",
+        severity: IssueSeverity::Medium,
+        description: "network transmit timeouts",
+        action: "Check the cable, transceiver and switch port",
+    },
+    Rule {
+        filter: None,
+        patterns: &[&["critical temperature reached"]],
+        component: "thermal",
+        severity: IssueSeverity::Critical,
+        description: "critical temperature shutdowns",
+        action: "Fix cooling before running the test again",
+    },
+    Rule {
+        filter: None,
+        patterns: &[&["temperature above threshold"], &["cpu clock throttled"], &["package temperature", "throttled"]],
+        component: "thermal",
+        severity: IssueSeverity::High,
+        description: "thermal throttling",
+        action: "Check heatsink mounting, thermal paste and airflow",
+    },
+    Rule {
+        filter: None,
+        patterns: &[&["soft lockup"], &["hard lockup"], &["self-detected stall"], &["blocked for more than"]],
+        component: "cpu",
+        severity: IssueSeverity::High,
+        description: "CPU lockups or stalls",
+        action: "Check for unstable overclocks, power delivery problems or firmware bugs",
+    },
+    Rule {
+        filter: None,
+        patterns: &[&["out of memory: killed process"], &["oom-kill:"]],
+        component: "memory",
+        severity: IssueSeverity::High,
+        description: "out-of-memory kills",
+        action: "Lower the memory test size or stop other memory-heavy services",
+    },
+];
+
+/// Kernel log lines matched by one rule.
+#[derive(Debug, Default)]
+struct Matches {
+    count: usize,
+    examples: Vec<String>,
+}
+
+/// Counts of classified lines, keyed by rule index.
+#[derive(Debug, Default)]
+pub struct KernelLogScan {
+    matches: BTreeMap<usize, Matches>,
+}
+
+impl KernelLogScan {
+    /// Classifies one line of `/dev/kmsg` or `dmesg` output.
+    pub fn add_line(&mut self, line: &str) {
+        let Some(message) = message(line) else {
+            return;
+        };
+        let lower = message.to_lowercase();
+        let Some(index) = RULES.iter().position(|rule| {
+            rule.patterns.iter().any(|pattern| pattern.iter().all(|part| lower.contains(part)))
+                && rule.filter.is_none_or(|filter| filter(&lower))
+        }) else {
+            return;
+        };
+
+        let matches = self.matches.entry(index).or_default();
+        matches.count += 1;
+        if matches.examples.len() < MAX_EXAMPLES {
+            matches.examples.push(message.to_string());
+        }
+    }
+
+    /// Classifies every line of `text`.
+    pub fn add_text(&mut self, text: &str) {
+        for line in text.lines() {
+            self.add_line(line);
+        }
+    }
+
+    /// Returns one issue per class of lines seen, quoting the first line.
+    pub fn issues(&self) -> Vec<TestIssue> {
+        self.matches.iter()
+            .map(|(index, matches)| {
+                let rule = &RULES[*index];
+                TestIssue {
+                    component: rule.component.to_string(),
+                    severity: rule.severity,
+                    message: format!(
+                        "Kernel log reported {} ({} line{}, first: \"{}\")",
+                        rule.description,
+                        matches.count,
+                        if matches.count == 1 { "" } else { "s" },
+                        matches.examples[0],
+                    ),
+                    action: Some(rule.action.to_string()),
+                }
+            })
+            .collect()
+    }
+}
+
+/// Returns `true` if a line names a physical disk, such as `sda` or
+/// `nvme0n1`, rather than a loop, optical, RAM or device-mapper device.
+fn names_physical_disk(line: &str) -> bool {
+    line.split(|c: char| !c.is_ascii_alphanumeric()).any(|word| {
+        let letters_then_digits = |rest: &str| {
+            let digits = rest.trim_start_matches(|c: char| c.is_ascii_lowercase());
+            rest.len() > digits.len() && digits.chars().all(|c| c.is_ascii_digit())
+        };
+        ["sd", "vd", "xvd"].iter().any(|prefix| word.strip_prefix(prefix).is_some_and(letters_then_digits))
+            || ["nvme", "mmcblk"].iter().any(|prefix| word.strip_prefix(prefix).is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit())))
+    })
+}
+
+/// Returns `true` if a line was logged by a PCI device driver, which
+/// prefixes its messages with the device address such as `0000:05:00.0`.
+/// Virtual interfaces such as veth pairs and bridges have none.
+fn names_pci_device(line: &str) -> bool {
+    line.split_whitespace().any(|word| {
+        let parts: Vec<&str> = word.split([':', '.']).collect();
+        parts.len() == 4
+            && [4, 2, 2, 1].iter().zip(&parts).all(|(len, part)| part.len() == *len && part.chars().all(|c| c.is_ascii_hexdigit()))
+    })
+}
+
+/// Extracts the message from a `/dev/kmsg` record (`prio,seq,usec,flags;msg`)
+/// or a `dmesg` line (`[  12.345678] msg`). Record continuation lines, which
+/// start with a space, are skipped.
+fn message(line: &str) -> Option<&str> {
+    if line.starts_with(' ') || line.trim().is_empty() {
+        return None;
+    }
+
+    if let Some((header, message)) = line.split_once(';') {
+        if header.split(',').next().is_some_and(|prio| prio.parse::<u32>().is_ok()) {
+            return Some(message.trim_end());
+        }
+    }
+
+    let trimmed = line.trim();
+    if let Some(rest) = trimmed.strip_prefix('[') {
+        if let Some((_, message)) = rest.split_once(']') {
+            return Some(message.trim());
+        }
+    }
+    Some(trimmed)
+}
+
+/// Where the kernel log is read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum KernelLogSource {
+    /// Tail `/dev/kmsg` in a background thread.
+    Kmsg,
+    /// Read the lines appended to a `dmesg` or syslog file during the run.
+    File(PathBuf),
+}
+
+/// Scans the kernel log for hardware errors logged between `start` and
+/// `finish`.
+pub struct KernelLogMonitor {
+    source: KernelLogSource,
+    file_offset: u64,
+    running: Arc<AtomicBool>,
+    handle: Option<JoinHandle<KernelLogScan>>,
+}
+
+impl KernelLogMonitor {
+    pub fn new(source: KernelLogSource) -> Self {
+        Self {
+            source,
+            file_offset: 0,
+            running: Arc::new(AtomicBool::new(false)),
+            handle: None,
+        }
+    }
+
+    /// Opens `/dev/kmsg` positioned after the newest record, so only records
+    /// logged from now on are read. Fails without root or with
+    /// `kernel.dmesg_restrict` set.
+    fn open_kmsg() -> std::io::Result<File> {
+        let mut file = OpenOptions::new()
+            .read(true)
+            .custom_flags(libc::O_NONBLOCK)
+            .open(DEV_KMSG)?;
+        file.seek(SeekFrom::End(0))?;
+        Ok(file)
+    }
+}
+
+impl Default for KernelLogMonitor {
+    fn default() -> Self {
+        Self::new(KernelLogSource::Kmsg)
+    }
+}
+
+impl HealthMonitor for KernelLogMonitor {
+    fn start(&mut self) {
+        match &self.source {
+            KernelLogSource::File(path) => {
+                self.file_offset = std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+            }
+            KernelLogSource::Kmsg => {
+                let Ok(mut kmsg) = Self::open_kmsg() else {
+                    return;
+                };
+                self.running.store(true, Ordering::Relaxed);
+                let running = self.running.clone();
+                self.handle = Some(thread::spawn(move || {
+                    let mut scan = KernelLogScan::default();
+                    let mut buffer = vec![0u8; MAX_RECORD];
+                    loop {
+                        // Drain before checking the flag so records logged
+                        // just before the run ends are still seen.
+                        let stopping = !running.load(Ordering::Relaxed);
+                        loop {
+                            match kmsg.read(&mut buffer) {
+                                Ok(0) => break,
+                                Ok(n) => scan.add_text(&String::from_utf8_lossy(&buffer[..n])),
+                                // Records were overwritten before we read them.
+                                Err(e) if e.raw_os_error() == Some(libc::EPIPE) => continue,
+                                Err(e) if e.kind() == ErrorKind::Interrupted => continue,
+                                Err(_) => break,
+                            }
+                        }
+                        if stopping {
+                            return scan;
+                        }
+                        thread::sleep(POLL_INTERVAL);
+                    }
+                }));
+            }
+        }
+    }
+
+    fn finish(&mut self) -> Vec<TestIssue> {
+        match &self.source {
+            KernelLogSource::File(path) => {
+                let mut scan = KernelLogScan::default();
+                if let Ok(mut file) = File::open(path) {
+                    let mut appended = Vec::new();
+                    if file.seek(SeekFrom::Start(self.file_offset)).is_ok() && file.read_to_end(&mut appended).is_ok() {
+                        scan.add_text(&String::from_utf8_lossy(&appended));
+                    }
+                }
+                scan.issues()
+            }
+            KernelLogSource::Kmsg => {
+                self.running.store(false, Ordering::Relaxed);
+                self.handle.take()
+                    .and_then(|handle| handle.join().ok())
+                    .map(|scan| scan.issues())
+                    .unwrap_or_default()
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+    use std::io::Write;
+
+    const DMESG: &str = "\
+[    0.000000] Linux version 6.1.0-18-amd64
+[ 1204.551201] nvme nvme0: I/O 417 QID 6 timeout, aborting
+[ 1204.551288] nvme nvme0: I/O 418 QID 6 timeout, aborting
+[ 1210.002311] blk_update_request: I/O error, dev sdb, sector 1953525000 op 0x0:(READ)
+[ 1211.000000] pcieport 0000:00:1c.0: AER: Corrected error received: 0000:03:00.0
+[ 1212.000000] ixgbe 0000:05:00.0 eth0: NIC Link is Down
+[ 1213.000000] mce: [Hardware Error]: Machine check events logged
+[ 1214.000000] Out of memory: Killed process 4242 (burnin) total-vm:8000000kB
+[ 1215.000000] usb 1-1: new high-speed USB device number 2 using xhci_hcd
+[ 1216.000000] blk_update_request: I/O error, dev loop3, sector 0 op 0x0:(READ)
+[ 1217.000000] Buffer I/O error on dev sr0, logical block 0, async page read
+[ 1218.000000] br-4f1a: port 2(veth9c1d2e0) entered disabled state
+[ 1219.000000] veth9c1d2e0: link is down
+[ 1220.000000] mce: CPU3: Package temperature above threshold, cpu clock throttled
+";
+
+    #[test]
+    fn test_classify_dmesg() {
+        let mut scan = KernelLogScan::default();
+        scan.add_text(DMESG);
+        let issues = scan.issues();
+
+        let summary: Vec<(&str, IssueSeverity)> = issues.iter()
+            .map(|i| (i.component.as_str(), i.severity))
+            .collect();
+        assert_eq!(summary, [
+            ("storage", IssueSeverity::High),
+            ("storage", IssueSeverity::High),
+            ("network", IssueSeverity::Medium),
+            ("thermal", IssueSeverity::High),
+            ("memory", IssueSeverity::High),
+        ]);
+        assert!(issues[1].message.contains("(1 line, first: \"blk_update_request: I/O error, dev sdb"));
+        assert!(issues[2].message.contains("(1 line, first: \"ixgbe 0000:05:00.0 eth0: NIC Link is Down\")"));
+        assert_eq!(
+            issues[0].message,
+            "Kernel log reported NVMe command timeouts or controller resets (2 lines, first: \"nvme nvme0: I/O 417 QID 6 timeout, aborting\")",
+        );
+    }
+
+    #[test]
+    fn test_kmsg_records() {
+        assert_eq!(message("3,1024,1204551201,-;nvme nvme0: I/O 417 QID 6 timeout"), Some("nvme nvme0: I/O 417 QID 6 timeout"));
+        assert_eq!(message(" SUBSYSTEM=nvme"), None);
+        assert_eq!(message("[ 12.5] ata1: hard resetting link"), Some("ata1: hard resetting link"));
+
+        assert!(names_physical_disk("ext4-fs warning (device nvme0n1p2): i/o error 10 writing to inode"));
+        assert!(names_physical_disk("sd 2:0:0:0: [sdc] tag#5 sense key : medium error"));
+        assert!(!names_physical_disk("buffer i/o error on dev dm-0, logical block 12"));
+        assert!(!names_physical_disk("i/o error, dev zram0, sector 8"));
+        assert!(names_pci_device("r8169 0000:03:00.0 enp3s0: link is down"));
+        assert!(!names_pci_device("bond0: link status down for interface eth1"));
+
+        let mut scan = KernelLogScan::default();
+        scan.add_line("2,99,5000,-;thermal thermal_zone0: critical temperature reached (105 C), shutting down");
+        assert_eq!(scan.issues()[0].severity, IssueSeverity::Critical);
+        assert_eq!(scan.issues()[0].component, "thermal");
+    }
+
+    #[test]
+    fn test_file_monitor_reads_only_new_lines() {
+        let path = std::env::temp_dir().join(format!("burnin-kmsg-{}.log", std::process::id()));
+        fs::write(&path, "[ 1.0] blk_update_request: I/O error, dev sda, sector 0\n").unwrap();
+
+        let mut monitor = KernelLogMonitor::new(KernelLogSource::File(path.clone()));
+        monitor.start();
+        let mut file = fs::OpenOptions::new().append(true).open(&path).unwrap();
+        writeln!(file, "[ 2.0] ata3: hard resetting link").unwrap();
+        let issues = monitor.finish();
+
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.contains("SATA link errors"));
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,5 +1,22 @@
+pub mod abort;
+pub mod alert;
+pub mod baseline;
+pub mod cache;
+pub mod cgroup;
 pub mod config;
+pub mod cpu_features;
+pub mod dmi;
+pub mod edac;
 pub mod error;
 pub mod hardware;
+pub mod hwmon;
+pub mod kmsg;
+pub mod monitor;
+pub mod pci;
+pub mod policy;
 pub mod runner;
+pub mod scoring;
 pub mod test;
+#[cfg(test)]
+pub(crate) mod test_support;
+pub mod virt;
@@ -0,0 +1,145 @@
+use std::collections::BTreeSet;
+use std::time::Duration;
+use serde_json::json;
+
+use crate::core::edac::EdacMonitor;
+use crate::core::kmsg::KernelLogMonitor;
+use crate::core::pci::PciMonitor;
+use crate::core::test::{TestResult, TestStatus, TestIssue, IssueSeverity};
+
+/// Name of the result that collects issues no test exercised.
+pub const SYSTEM_HEALTH: &str = "system_health";
+
+/// The test that exercises each issue component.
+const COMPONENT_TESTS: &[(&str, &str)] = &[
+    ("cpu", "cpu_stress"),
+    ("memory", "memory_validation"),
+    ("storage", "storage_io"),
+    ("network", "network"),
+    ("thermal", "thermal_monitor"),
+    ("cooling", "thermal_monitor"),
+    ("power", "thermal_monitor"),
+];
+
+/// A source of hardware errors that the kernel records on its own, such as
+/// corrected ECC errors, which no test can observe directly.
+///
+/// Monitors are started before the first test and finished after the last.
+pub trait HealthMonitor: Send {
+    /// Records the starting state.
+    fn start(&mut self);
+
+    /// Returns an issue for every error seen since [`HealthMonitor::start`].
+    fn finish(&mut self) -> Vec<TestIssue>;
+}
+
+/// Returns every monitor that runs alongside the tests.
+pub fn default_monitors() -> Vec<Box<dyn HealthMonitor>> {
+    vec![
+        Box::new(EdacMonitor::default()),
+        Box::new(KernelLogMonitor::default()),
+        Box::new(PciMonitor::default()),
+    ]
+}
+
+/// Returns the test that exercises `component`, if any.
+pub fn test_for_component(component: &str) -> Option<&'static str> {
+    COMPONENT_TESTS.iter()
+        .find(|(name, _)| *name == component)
+        .map(|(_, test)| *test)
+}
+
+/// Returns the name of the result that an issue about `component` is added
+/// to: the test that exercises it, or [`SYSTEM_HEALTH`] if that test did not
+/// run.
+pub fn result_for_component(results: &[TestResult], component: &str) -> &'static str {
+    test_for_component(component)
+        .filter(|test| results.iter().any(|r| r.name == *test))
+        .unwrap_or(SYSTEM_HEALTH)
+}
+
+/// Adds each issue to the result named by [`result_for_component`],
+/// returning the names of the results that changed.
+///
+/// A critical issue fails the result it is added to; other issues lower its
+/// score.
+pub fn attach_issues(results: &mut Vec<TestResult>, issues: Vec<TestIssue>) -> BTreeSet<String> {
+    let mut changed = BTreeSet::new();
+    for issue in issues {
+        let test = result_for_component(results, &issue.component);
+        let index = match results.iter().position(|r| r.name == test) {
+            Some(index) => index,
+            None => {
+                results.push(TestResult {
+                    name: SYSTEM_HEALTH.to_string(),
+                    status: TestStatus::Completed,
+                    score: 100,
+                    duration: Duration::ZERO,
+                    metrics: json!({}),
+                    issues: Vec::new(),
+                    deductions: Vec::new(),
+                });
+                results.len() - 1
+            }
+        };
+
+        let result = &mut results[index];
+        let penalty = match issue.severity {
+            IssueSeverity::Critical => 50,
+            IssueSeverity::High => 20,
+            IssueSeverity::Medium => 10,
+            IssueSeverity::Low => 0,
+        };
+        result.deduct(issue.message.clone(), penalty);
+        if issue.severity == IssueSeverity::Critical {
+            result.status = TestStatus::Failed;
+        }
+        result.issues.push(issue);
+        changed.insert(result.name.clone());
+    }
+    changed
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn issue(component: &str, severity: IssueSeverity) -> TestIssue {
+        TestIssue {
+            component: component.to_string(),
+            severity,
+            message: format!("{} error", component),
+            action: None,
+        }
+    }
+
+    #[test]
+    fn test_attach_issues() {
+        let mut results = vec![TestResult {
+            name: "memory_validation".to_string(),
+            status: TestStatus::Completed,
+            score: 95,
+            duration: Duration::from_secs(60),
+            metrics: json!({}),
+            issues: Vec::new(),
+            deductions: Vec::new(),
+        }];
+
+        let changed = attach_issues(&mut results, vec![
+            issue("memory", IssueSeverity::High),
+            issue("cpu", IssueSeverity::High),
+            issue("memory", IssueSeverity::Critical),
+        ]);
+
+        assert_eq!(changed.into_iter().collect::<Vec<_>>(), ["memory_validation", SYSTEM_HEALTH]);
+        assert_eq!(results.len(), 2);
+        assert_eq!(results[0].status, TestStatus::Failed);
+        assert_eq!(results[0].score, 25);
+        assert_eq!(results[0].deductions.iter().map(|d| d.points).collect::<Vec<_>>(), [20, 50]);
+        assert_eq!(results[0].issues.len(), 2);
+        assert_eq!(results[1].name, SYSTEM_HEALTH);
+        assert_eq!(results[1].status, TestStatus::Completed);
+        assert_eq!(results[1].score, 80);
+        assert_eq!(results[1].issues[0].message, "cpu error");
+    }
+}
@@ -0,0 +1,362 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::core::hardware::{AerCounts, PciDevice};
+use crate::core::monitor::HealthMonitor;
+use crate::core::test::{TestIssue, IssueSeverity};
+
+/// Location of the PCI devices in sysfs.
+pub const SYSFS_PCI_DEVICES: &str = "/sys/bus/pci/devices";
+
+/// Reads every PCI device of the running system.
+pub fn read_devices() -> Vec<PciDevice> {
+    read_devices_from(Path::new(SYSFS_PCI_DEVICES))
+}
+
+/// Reads the devices under a `/sys/bus/pci/devices` style directory, sorted
+/// by address.
+///
+/// The maximum link speed and width are capped at those of the upstream
+/// port, so a Gen4 card in a Gen3 slot is not reported as downtrained.
+pub fn read_devices_from(root: &Path) -> Vec<PciDevice> {
+    let Ok(entries) = fs::read_dir(root) else {
+        return Vec::new();
+    };
+
+    let mut devices: Vec<PciDevice> = entries.flatten()
+        .map(|entry| {
+            let dir = entry.path();
+            let mut device = read_device(&dir);
+            if let Some(parent) = parent_device(&dir) {
+                device.upstream = Some(file_name(&parent));
+                device.max_link_speed_gts = cap(device.max_link_speed_gts, read_speed(&parent.join("max_link_speed")));
+                device.max_link_width = cap(device.max_link_width, read_width(&parent.join("max_link_width")));
+            }
+            device
+        })
+        .collect();
+    devices.sort_by(|a, b| a.address.cmp(&b.address));
+    devices
+}
+
+fn read_device(dir: &Path) -> PciDevice {
+    let aer = [
+        "aer_dev_correctable",
+        "aer_dev_nonfatal",
+        "aer_dev_fatal",
+    ].map(|file| fs::read_to_string(dir.join(file)).ok().map(|contents| parse_aer(&contents)));
+
+    PciDevice {
+        address: file_name(dir),
+        vendor_id: read_trimmed(&dir.join("vendor")).unwrap_or_default(),
+        device_id: read_trimmed(&dir.join("device")).unwrap_or_default(),
+        class: read_trimmed(&dir.join("class")).unwrap_or_default(),
+        driver: fs::read_link(dir.join("driver")).ok().map(|link| file_name(&link)),
+        current_link_speed_gts: read_speed(&dir.join("current_link_speed")),
+        max_link_speed_gts: read_speed(&dir.join("max_link_speed")),
+        current_link_width: read_width(&dir.join("current_link_width")),
+        max_link_width: read_width(&dir.join("max_link_width")),
+        aer: match aer {
+            [None, None, None] => None,
+            [correctable, nonfatal, fatal] => Some(AerCounts {
+                correctable: correctable.unwrap_or(0),
+                nonfatal: nonfatal.unwrap_or(0),
+                fatal: fatal.unwrap_or(0),
+            }),
+        },
+        upstream: None,
+    }
+}
+
+/// The bridge a device hangs off: the parent directory of its resolved
+/// sysfs path, if that is a PCI device rather than a host bridge.
+fn parent_device(dir: &Path) -> Option<PathBuf> {
+    let parent = fs::canonicalize(dir).ok()?.parent()?.to_path_buf();
+    parent.join("class").exists().then_some(parent)
+}
+
+fn cap<T: PartialOrd>(device: Option<T>, port: Option<T>) -> Option<T> {
+    match (device, port) {
+        (Some(device), Some(port)) if port < device => Some(port),
+        (device, _) => device,
+    }
+}
+
+/// Parses a link speed such as `8.0 GT/s PCIe`. `Unknown` and speeds of
+/// links that are down parse as `None`.
+fn parse_speed(contents: &str) -> Option<f32> {
+    contents.split_whitespace()
+        .next()?
+        .parse()
+        .ok()
+        .filter(|speed| *speed > 0.0)
+}
+
+fn read_speed(path: &Path) -> Option<f32> {
+    parse_speed(&read_trimmed(path)?)
+}
+
+fn read_width(path: &Path) -> Option<u32> {
+    read_trimmed(path)?
+        .trim_start_matches('x')
+        .parse()
+        .ok()
+        .filter(|width| *width > 0)
+}
+
+/// Reads the total from an `aer_dev_*` file, which lists one counter per
+/// error type followed by a `TOTAL_ERR_*` line.
+fn parse_aer(contents: &str) -> u64 {
+    let counters: Vec<(&str, u64)> = contents.lines()
+        .filter_map(|line| {
+            let (name, count) = line.trim().rsplit_once(' ')?;
+            Some((name.trim(), count.parse().ok()?))
+        })
+        .collect();
+    counters.iter()
+        .find(|(name, _)| name.starts_with("TOTAL_"))
+        .map(|(_, total)| *total)
+        .unwrap_or_else(|| counters.iter().map(|(_, count)| count).sum())
+}
+
+/// The component whose test exercises a device, from its PCI class code.
+fn component(device: &PciDevice) -> &'static str {
+    let class = device.class.trim_start_matches("0x");
+    if class.starts_with("01") {
+        "storage"
+    } else if class.starts_with("02") {
+        "network"
+    } else {
+        "pcie"
+    }
+}
+
+fn describe(device: &PciDevice) -> String {
+    match &device.driver {
+        Some(driver) => format!("PCIe device {} ({})", device.address, driver),
+        None => format!("PCIe device {}", device.address),
+    }
+}
+
+/// Formats a link as `16 GT/s x8`, with `?` for what is not known.
+pub fn format_link(speed: Option<f32>, width: Option<u32>) -> String {
+    let speed = speed.map_or_else(|| "?".to_string(), |speed| format!("{} GT/s", speed));
+    let width = width.map_or_else(|| "?".to_string(), |width| format!("x{}", width));
+    format!("{} {}", speed, width)
+}
+
+/// Builds an issue for every link trained below the speed or width that
+/// the device and its upstream port support.
+///
+/// Bridges with no device behind them are skipped, and a display
+/// controller that only lowered its speed is reported as low severity,
+/// since GPUs do so while idle.
+pub fn link_issues(devices: &[PciDevice]) -> Vec<TestIssue> {
+    devices.iter()
+        .filter(|device| device.is_downtrained())
+        .filter(|device| !device.is_bridge() || devices.iter().any(|d| d.upstream.as_ref() == Some(&device.address)))
+        .map(|device| TestIssue {
+            component: component(device).to_string(),
+            severity: if device.is_display() && !device.is_narrowed() {
+                IssueSeverity::Low
+            } else {
+                IssueSeverity::Medium
+            },
+            message: format!(
+                "{} link runs at {} but supports {}",
+                describe(device),
+                format_link(device.current_link_speed_gts, device.current_link_width),
+                format_link(device.max_link_speed_gts, device.max_link_width),
+            ),
+            action: Some("Reseat the card, try another slot and check the BIOS link speed settings".to_string()),
+        })
+        .collect()
+}
+
+/// Builds an issue for every AER error counted since `before`.
+pub fn aer_issues(before: &[PciDevice], after: &[PciDevice]) -> Vec<TestIssue> {
+    let mut issues = Vec::new();
+
+    for device in after {
+        // Devices that appeared during the run have no starting counts.
+        let then = before.iter()
+            .find(|b| b.address == device.address)
+            .and_then(|b| b.aer);
+        let (Some(now), Some(then)) = (device.aer, then) else {
+            continue;
+        };
+
+        let kinds = [
+            (now.fatal.saturating_sub(then.fatal), "fatal", IssueSeverity::Critical),
+            (now.nonfatal.saturating_sub(then.nonfatal), "non-fatal uncorrected", IssueSeverity::High),
+            (now.correctable.saturating_sub(then.correctable), "corrected", IssueSeverity::Medium),
+        ];
+        for (delta, kind, severity) in kinds {
+            if delta > 0 {
+                issues.push(TestIssue {
+                    component: component(device).to_string(),
+                    severity,
+                    message: format!(
+                        "{} {} PCIe error{} on {}",
+                        delta,
+                        kind,
+                        if delta == 1 { "" } else { "s" },
+                        describe(device),
+                    ),
+                    action: Some("Reseat the device and check its slot, riser and cabling".to_string()),
+                });
+            }
+        }
+    }
+
+    issues
+}
+
+/// Checks PCIe link training and AER counters across a run.
+#[derive(Debug, Default)]
+pub struct PciMonitor {
+    before: Option<Vec<PciDevice>>,
+}
+
+impl HealthMonitor for PciMonitor {
+    fn start(&mut self) {
+        self.before = Some(read_devices());
+    }
+
+    fn finish(&mut self) -> Vec<TestIssue> {
+        let Some(before) = self.before.take() else {
+            return Vec::new();
+        };
+        let after = read_devices();
+        let mut issues = link_issues(&after);
+        issues.extend(aer_issues(&before, &after));
+        issues
+    }
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+        .filter(|contents| !contents.is_empty())
+}
+
+fn file_name(path: &Path) -> String {
+    path.file_name()
+        .map(|name| name.to_string_lossy().into_owned())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{fixture_dir, write};
+
+    fn write_link(dir: &Path, current: (&str, &str), max: (&str, &str)) {
+        write(dir, "current_link_speed", current.0);
+        write(dir, "current_link_width", current.1);
+        write(dir, "max_link_speed", max.0);
+        write(dir, "max_link_width", max.1);
+    }
+
+    /// Builds `devices/pci0000:00/<port>/<device>` with symlinks under
+    /// `bus`, as the kernel lays it out.
+    fn fake_pci(root: &Path) -> PathBuf {
+        let port = root.join("devices/pci0000:00/0000:00:01.0");
+        write(&port, "class", "0x060400");
+        write_link(&port, ("8.0 GT/s PCIe", "4"), ("8.0 GT/s PCIe", "4"));
+
+        let nvme = port.join("0000:01:00.0");
+        write(&nvme, "class", "0x010802");
+        write(&nvme, "vendor", "0x144d");
+        write(&nvme, "device", "0xa80a");
+        write_link(&nvme, ("8.0 GT/s PCIe", "2"), ("16.0 GT/s PCIe", "4"));
+        write(&nvme, "aer_dev_correctable", "RxErr 2\nBadTLP 1\nTOTAL_ERR_COR 3");
+        write(&nvme, "aer_dev_nonfatal", "Undefined 0\nTOTAL_ERR_NONFATAL 0");
+        write(&nvme, "aer_dev_fatal", "Undefined 0\nTOTAL_ERR_FATAL 0");
+
+        let nic = root.join("devices/pci0000:00/0000:00:02.0");
+        write(&nic, "class", "0x020000");
+        write_link(&nic, ("5.0 GT/s PCIe", "8"), ("8.0 GT/s PCIe", "8"));
+
+        // The root port of an empty slot, with its link down
+        let empty = root.join("devices/pci0000:00/0000:00:1c.0");
+        write(&empty, "class", "0x060400");
+        write_link(&empty, ("2.5 GT/s PCIe", "0"), ("8.0 GT/s PCIe", "1"));
+
+        // A bridge whose link is up but has nothing behind it
+        let idle = root.join("devices/pci0000:00/0000:00:1d.0");
+        write(&idle, "class", "0x060400");
+        write_link(&idle, ("2.5 GT/s PCIe", "1"), ("8.0 GT/s PCIe", "1"));
+
+        // A GPU idling at a lower speed
+        let gpu = root.join("devices/pci0000:00/0000:00:03.0");
+        write(&gpu, "class", "0x030000");
+        write_link(&gpu, ("2.5 GT/s PCIe", "16"), ("16.0 GT/s PCIe", "16"));
+
+        let bus = root.join("bus");
+        fs::create_dir_all(&bus).unwrap();
+        for dir in [&port, &nvme, &nic, &empty, &idle, &gpu] {
+            std::os::unix::fs::symlink(dir, bus.join(file_name(dir))).unwrap();
+        }
+        bus
+    }
+
+    #[test]
+    fn test_read_devices_from_fake_sysfs() {
+        let root = fixture_dir("pci-read");
+        let bus = fake_pci(&root);
+
+        let devices = read_devices_from(&bus);
+        assert_eq!(devices.len(), 6);
+        let nvme = &devices[5];
+        assert_eq!(nvme.address, "0000:01:00.0");
+        assert_eq!(nvme.upstream.as_deref(), Some("0000:00:01.0"));
+        assert_eq!(devices[0].upstream, None);
+        assert_eq!(nvme.vendor_id, "0x144d");
+        assert_eq!(nvme.current_link_speed_gts, Some(8.0));
+        assert_eq!(nvme.current_link_width, Some(2));
+        // Capped by the Gen3 x4 upstream port.
+        assert_eq!(nvme.max_link_speed_gts, Some(8.0));
+        assert_eq!(nvme.max_link_width, Some(4));
+        assert_eq!(nvme.aer, Some(AerCounts { correctable: 3, nonfatal: 0, fatal: 0 }));
+        assert_eq!(devices[0].aer, None);
+
+        let issues = link_issues(&devices);
+        let messages: Vec<_> = issues.iter().map(|i| (i.component.as_str(), i.severity, i.message.as_str())).collect();
+        assert_eq!(messages, [
+            ("network", IssueSeverity::Medium, "PCIe device 0000:00:02.0 link runs at 5 GT/s x8 but supports 8 GT/s x8"),
+            ("pcie", IssueSeverity::Low, "PCIe device 0000:00:03.0 link runs at 2.5 GT/s x16 but supports 16 GT/s x16"),
+            ("storage", IssueSeverity::Medium, "PCIe device 0000:01:00.0 link runs at 8 GT/s x2 but supports 8 GT/s x4"),
+        ]);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_aer_issues() {
+        let root = fixture_dir("pci-aer");
+        let bus = fake_pci(&root);
+        let before = read_devices_from(&bus);
+        assert!(aer_issues(&before, &before).is_empty());
+
+        let nvme = root.join("devices/pci0000:00/0000:00:01.0/0000:01:00.0");
+        write(&nvme, "aer_dev_correctable", "RxErr 6\nBadTLP 1\nTOTAL_ERR_COR 7");
+        write(&nvme, "aer_dev_fatal", "Undefined 0\nTOTAL_ERR_FATAL 1");
+        let after = read_devices_from(&bus);
+
+        let issues = aer_issues(&before, &after);
+        let messages: Vec<_> = issues.iter().map(|i| i.message.as_str()).collect();
+        assert_eq!(messages, [
+            "1 fatal PCIe error on PCIe device 0000:01:00.0",
+            "4 corrected PCIe errors on PCIe device 0000:01:00.0",
+        ]);
+        assert_eq!(issues[0].severity, IssueSeverity::Critical);
+        assert_eq!(issues[0].component, "storage");
+
+        assert_eq!(parse_aer("RxErr 1\nBadDLLP 2"), 3);
+        assert_eq!(parse_speed("Unknown"), None);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
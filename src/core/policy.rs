@@ -0,0 +1,304 @@
+use std::fmt;
+use std::fs;
+use std::path::Path;
+use serde::{Serialize, Deserialize};
+
+use crate::core::error::{Result, BurnInError};
+use crate::core::monitor;
+use crate::core::runner::TestSuite;
+
+/// A comparison between a reported value and a constant.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Equal,
+    NotEqual,
+    Less,
+    LessOrEqual,
+    Greater,
+    GreaterOrEqual,
+}
+
+impl Comparison {
+    /// Operators in the order they are searched for, two-character ones first.
+    const OPERATORS: [(&'static str, Comparison); 6] = [
+        ("==", Comparison::Equal),
+        ("!=", Comparison::NotEqual),
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+    ];
+
+    fn holds(self, actual: f64, expected: f64) -> bool {
+        match self {
+            Comparison::Equal => actual == expected,
+            Comparison::NotEqual => actual != expected,
+            Comparison::Less => actual < expected,
+            Comparison::LessOrEqual => actual <= expected,
+            Comparison::Greater => actual > expected,
+            Comparison::GreaterOrEqual => actual >= expected,
+        }
+    }
+}
+
+/// One acceptance criterion, such as `storage.sequential_write_mbps >= 800`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Rule {
+    /// The criterion as written in the policy file
+    pub check: String,
+    pub description: Option<String>,
+    /// `overall_score`, or a test followed by a metric path
+    pub metric: String,
+    pub comparison: Comparison,
+    pub value: f64,
+}
+
+impl Rule {
+    /// Parses a criterion of the form `<metric> <operator> <number>`.
+    ///
+    /// `true` and `false` are accepted as 1 and 0.
+    pub fn parse(check: &str) -> Result<Self> {
+        let invalid = |reason: &str| BurnInError::ConfigError(format!("Invalid policy rule '{}': {}", check, reason));
+
+        let (position, operator, comparison) = Comparison::OPERATORS.iter()
+            .filter_map(|(operator, comparison)| check.find(operator).map(|position| (position, *operator, *comparison)))
+            .min_by_key(|(position, operator, _)| (*position, std::cmp::Reverse(operator.len())))
+            .ok_or_else(|| invalid("expected one of ==, !=, <, <=, >, >="))?;
+
+        let metric = check[..position].trim();
+        let value = check[position + operator.len()..].trim();
+        if metric.is_empty() || !metric.chars().all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '.') {
+            return Err(invalid("expected a metric name such as memory.memory_errors"));
+        }
+        let value = match value {
+            "true" => 1.0,
+            "false" => 0.0,
+            _ => value.parse().map_err(|_| invalid("expected a number after the operator"))?,
+        };
+
+        Ok(Self {
+            check: check.trim().to_string(),
+            description: None,
+            metric: metric.to_string(),
+            comparison,
+            value,
+        })
+    }
+
+    /// Checks this rule against a finished run.
+    pub fn evaluate(&self, suite: &TestSuite) -> RuleVerdict {
+        let actual = lookup(suite, &self.metric);
+        let verdict = match actual {
+            Some(actual) if self.comparison.holds(actual, self.value) => Verdict::Pass,
+            Some(_) => Verdict::Fail,
+            None => Verdict::Missing,
+        };
+        RuleVerdict {
+            check: self.check.clone(),
+            description: self.description.clone(),
+            verdict,
+            actual,
+        }
+    }
+}
+
+/// Acceptance criteria evaluated after a run.
+///
+/// Every rule must pass for the run to pass. A rule whose value was not
+/// reported, for example because its test did not run, fails.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Policy {
+    pub rules: Vec<Rule>,
+}
+
+/// The on-disk form of a policy.
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct PolicyFile {
+    #[serde(default)]
+    rule: Vec<RuleEntry>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleEntry {
+    check: String,
+    description: Option<String>,
+}
+
+impl Policy {
+    /// Loads a policy from a TOML file of `[[rule]]` tables.
+    pub fn load(path: &Path) -> Result<Self> {
+        let contents = fs::read_to_string(path)?;
+        Self::from_toml(&contents)
+    }
+
+    /// Parses a policy from TOML, checking every rule.
+    pub fn from_toml(contents: &str) -> Result<Self> {
+        let file: PolicyFile = toml::from_str(contents)
+            .map_err(|e| BurnInError::ConfigError(format!("Invalid policy file: {}", e)))?;
+        let rules = file.rule.into_iter()
+            .map(|entry| {
+                let mut rule = Rule::parse(&entry.check)?;
+                rule.description = entry.description;
+                Ok(rule)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if rules.is_empty() {
+            return Err(BurnInError::ConfigError("Policy file contains no rules".to_string()));
+        }
+        Ok(Self { rules })
+    }
+
+    /// Checks every rule against a finished run.
+    pub fn evaluate(&self, suite: &TestSuite) -> PolicyOutcome {
+        let verdicts: Vec<RuleVerdict> = self.rules.iter()
+            .map(|rule| rule.evaluate(suite))
+            .collect();
+        PolicyOutcome {
+            passed: verdicts.iter().all(|v| v.verdict == Verdict::Pass),
+            verdicts,
+        }
+    }
+}
+
+/// The outcome of one rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Verdict {
+    Pass,
+    Fail,
+    /// The value was not reported
+    Missing,
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Verdict::Pass => "PASS",
+            Verdict::Fail => "FAIL",
+            Verdict::Missing => "MISSING",
+        })
+    }
+}
+
+/// A rule's verdict and the value it was checked against.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct RuleVerdict {
+    pub check: String,
+    pub description: Option<String>,
+    pub verdict: Verdict,
+    pub actual: Option<f64>,
+}
+
+/// The verdicts of every rule of a policy.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct PolicyOutcome {
+    pub passed: bool,
+    pub verdicts: Vec<RuleVerdict>,
+}
+
+impl PolicyOutcome {
+    /// Number of rules that passed.
+    pub fn passed_count(&self) -> usize {
+        self.verdicts.iter().filter(|v| v.verdict == Verdict::Pass).count()
+    }
+}
+
+/// Resolves `overall_score`, or `<test>.<path>` where `<test>` is a test
+/// name or the component it exercises (`memory` for `memory_validation`).
+///
+/// The path is `score`, `duration_seconds` or a dotted path into the test's
+/// metrics. Booleans count as 1 and 0.
+fn lookup(suite: &TestSuite, metric: &str) -> Option<f64> {
+    if metric == "overall_score" {
+        return Some(suite.overall_score as f64);
+    }
+
+    let (test, path) = metric.split_once('.')?;
+    let name = monitor::test_for_component(test).unwrap_or(test);
+    let result = suite.results.iter().find(|r| r.name == name)?;
+    match path {
+        "score" => Some(result.score as f64),
+        "duration_seconds" => Some(result.duration.as_secs_f64()),
+        _ => {
+            let value = path.split('.').try_fold(&result.metrics, |value, key| value.get(key))?;
+            value.as_f64().or_else(|| value.as_bool().map(|b| if b { 1.0 } else { 0.0 }))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use serde_json::json;
+    use crate::core::test::{TestResult, TestStatus};
+
+    const POLICY: &str = r#"
+[[rule]]
+check = "memory.memory_errors == 0"
+description = "No memory errors"
+
+[[rule]]
+check = "storage.sequential_write_mbps >= 800"
+
+[[rule]]
+check = "thermal_monitor.max_temperature_celsius < 85"
+
+[[rule]]
+check = "overall_score >= 90"
+"#;
+
+    fn suite() -> TestSuite {
+        let result = |name: &str, metrics| TestResult {
+            name: name.to_string(),
+            status: TestStatus::Completed,
+            score: 95,
+            duration: Duration::from_secs(60),
+            metrics,
+            issues: Vec::new(),
+            deductions: Vec::new(),
+        };
+        let mut suite = TestSuite::new();
+        suite.results = vec![
+            result("memory_validation", json!({ "memory_errors": 0 })),
+            result("storage_io", json!({ "sequential_write_mbps": 612.5 })),
+        ];
+        suite.overall_score = 92;
+        suite
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let rule = Rule::parse("storage.sequential_write_mbps>=800").unwrap();
+        assert_eq!(rule.metric, "storage.sequential_write_mbps");
+        assert_eq!(rule.comparison, Comparison::GreaterOrEqual);
+        assert_eq!(rule.value, 800.0);
+        assert_eq!(Rule::parse("cpu.passed == true").unwrap().value, 1.0);
+        assert_eq!(Rule::parse("overall_score < 5").unwrap().comparison, Comparison::Less);
+
+        assert!(Rule::parse("overall_score 90").is_err());
+        assert!(Rule::parse(">= 90").is_err());
+        assert!(Rule::parse("overall_score >= ninety").is_err());
+        assert!(Policy::from_toml("").is_err());
+        assert!(Policy::from_toml("[[rule]]\ncheck = \"a.b > 1\"\nseverity = \"high\"\n").is_err());
+    }
+
+    #[test]
+    fn test_evaluate_policy() {
+        let policy = Policy::from_toml(POLICY).unwrap();
+        assert_eq!(policy.rules[0].description.as_deref(), Some("No memory errors"));
+
+        let outcome = policy.evaluate(&suite());
+        let verdicts: Vec<_> = outcome.verdicts.iter().map(|v| (v.verdict, v.actual)).collect();
+        assert_eq!(verdicts, [
+            (Verdict::Pass, Some(0.0)),
+            (Verdict::Fail, Some(612.5)),
+            (Verdict::Missing, None),
+            (Verdict::Pass, Some(92.0)),
+        ]);
+        assert!(!outcome.passed);
+        assert_eq!(outcome.passed_count(), 2);
+    }
+}
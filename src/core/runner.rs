@@ -1,11 +1,18 @@
-use std::sync::{Arc, Mutex};
+use std::collections::BTreeSet;
+use std::sync::Arc;
 use std::time::Instant;
 use rayon::prelude::*;
 use crate::core::error::{Result, BurnInError};
 use crate::core::test::{BurnInTest, TestResult, TestStatus};
-use crate::core::hardware::SystemInfo;
+use crate::core::hardware::{self, SystemInfo, SystemProfile, ConfigAdjustment};
 use crate::core::config::TestConfig;
+use crate::core::abort::AbortReason;
+use crate::core::alert::{AlertQueue, Alerter, Delivery};
+use crate::core::monitor::{self, HealthMonitor};
+use crate::core::policy::{Policy, PolicyOutcome};
+use crate::core::scoring::{self, Deduction, ScoreWeights, MAX_SCORE};
 use crate::reporters::Reporter;
+use crate::tests::thermal::{ThermalMonitorTest, ThermalObserver, thermal_result};
 
 /// A test suite contains the results of multiple tests
 #[derive(Debug)]
@@ -17,6 +24,12 @@ pub struct TestSuite {
     pub overall_status: TestStatus,
     pub system_info: Option<SystemInfo>,
     pub duration: std::time::Duration,
+    /// Why the run was stopped early, if it was
+    pub aborted: Option<AbortReason>,
+    /// Parameters changed by adaptive mode before the run
+    pub adjustments: Vec<ConfigAdjustment>,
+    /// Verdicts of the acceptance policy, if one was given
+    pub policy: Option<PolicyOutcome>,
 }
 
 impl Default for TestSuite {
@@ -36,11 +49,19 @@ impl TestSuite {
             overall_status: TestStatus::Pending,
             system_info: None,
             duration: std::time::Duration::from_secs(0),
+            aborted: None,
+            adjustments: Vec::new(),
+            policy: None,
         }
     }
     
     /// Finalize the test suite by calculating the overall score and status
     pub fn finalize(&mut self) {
+        self.finalize_weighted(&ScoreWeights::new());
+    }
+    
+    /// Finalize the test suite, weighting each test's score by `weights`
+    pub fn finalize_weighted(&mut self, weights: &ScoreWeights) {
         
         let end = chrono::Utc::now();
         self.end_time = Some(end);
@@ -53,24 +74,10 @@ impl TestSuite {
         }
         
         
-        let total_duration_secs: u64 = self.results.iter()
-            .map(|r| r.duration.as_secs())
-            .sum();
-        
-        if total_duration_secs == 0 {
-            
-            self.overall_score = (self.results.iter()
-                .map(|r| r.score as u32)
-                .sum::<u32>() / self.results.len() as u32) as u8;
-        } else {
-            
-            self.overall_score = (self.results.iter()
-                .map(|r| (r.score as u64 * r.duration.as_secs()) as u32)
-                .sum::<u32>() / total_duration_secs as u32) as u8;
-        }
+        self.overall_score = scoring::suite_score(&self.results, weights);
         
         
-        if self.results.iter().any(|r| r.status == TestStatus::Failed) {
+        if self.aborted.is_some() || self.results.iter().any(|r| r.status == TestStatus::Failed) {
             self.overall_status = TestStatus::Failed;
         } else if self.results.iter().any(|r| r.status == TestStatus::Partial) {
             self.overall_status = TestStatus::Partial;
@@ -78,6 +85,16 @@ impl TestSuite {
             self.overall_status = TestStatus::Completed;
         }
     }
+    
+    /// Evaluates `policy` against the finalized results. A run that does
+    /// not meet the policy fails.
+    pub fn apply_policy(&mut self, policy: &Policy) {
+        let outcome = policy.evaluate(self);
+        if !outcome.passed {
+            self.overall_status = TestStatus::Failed;
+        }
+        self.policy = Some(outcome);
+    }
 }
 
 /// A burn-in test runner
@@ -85,7 +102,9 @@ pub struct BurnInRunner {
     tests: Vec<Box<dyn BurnInTest + Send + Sync>>,
     config: TestConfig,
     reporter: Box<dyn Reporter + Send + Sync>,
-    interrupted: Arc<Mutex<bool>>,
+    thermal_in_background: bool,
+    adjustments: Vec<ConfigAdjustment>,
+    alerter: Option<Arc<Alerter>>,
 }
 
 impl BurnInRunner {
@@ -99,20 +118,41 @@ impl BurnInRunner {
             tests,
             config,
             reporter,
-            interrupted: Arc::new(Mutex::new(false)),
+            thermal_in_background: false,
+            adjustments: Vec::new(),
+            alerter: None,
+        }
+    }
+    
+    
+    /// Adapts the configuration to the system described by `profile` and
+    /// records the adjusted parameters for the report.
+    pub fn adapt_to(&mut self, profile: &SystemProfile) {
+        let (config, adjustments) = profile.adapt_test_config(&self.config);
+        for adjustment in &adjustments {
+            self.reporter.report_info(&format!("Adaptive mode set {} to {} ({})",
+                adjustment.parameter, adjustment.to, adjustment.reason));
         }
+        self.config = config;
+        self.adjustments = adjustments;
+    }
+    
+    
+    /// Sends alerts for critical issues, thermal cutoffs and the end of the
+    /// run through `alerter`, from a background thread.
+    pub fn alert_with(&mut self, alerter: Alerter) {
+        self.alerter = Some(Arc::new(alerter));
     }
     
     
     pub fn setup_interrupt_handler(&self) -> Result<()> {
-        let interrupted = self.interrupted.clone();
+        let abort = self.config.abort.clone();
         
         ctrlc::set_handler(move || {
-            let mut flag = interrupted.lock().unwrap();
-            *flag = true;
-            println!("\nReceived interrupt signal...");
-            println!("Stopping current tests gracefully...");
-            println!("This may take a moment to clean up resources safely.");
+            abort.trigger(AbortReason::Interrupted);
+            eprintln!("\nReceived interrupt signal...");
+            eprintln!("Stopping current tests gracefully...");
+            eprintln!("This may take a moment to clean up resources safely.");
         })
         .map_err(|e| BurnInError::UnexpectedError(format!("Failed to set Ctrl-C handler: {}", e)))?;
         
@@ -120,26 +160,96 @@ impl BurnInRunner {
     }
     
     
-    fn is_interrupted(&self) -> bool {
-        *self.interrupted.lock().unwrap()
+    /// Starts the thermal observer when background monitoring or the thermal
+    /// cutoff is enabled. In background mode the thermal test is taken out of
+    /// the regular run and reported from the observer instead.
+    fn start_thermal_observer(&mut self) -> Option<ThermalObserver> {
+        if !self.config.thermal_monitoring {
+            return None;
+        }
+        
+        if self.config.thermal_background {
+            let thermal_name = ThermalMonitorTest.name();
+            let count = self.tests.len();
+            self.tests.retain(|test| test.name() != thermal_name);
+            self.thermal_in_background = self.tests.len() != count;
+        }
+        
+        if self.thermal_in_background {
+            self.reporter.report_info("Monitoring temperatures in the background");
+        } else if !self.config.thermal_cutoff {
+            return None;
+        }
+        
+        Some(ThermalObserver::start(&self.config))
+    }
+    
+    /// Stops the thermal observer, adding its result to the suite in
+    /// background mode.
+    fn finish_thermal_observer(&self, observer: Option<ThermalObserver>, suite: &mut TestSuite) {
+        let Some(observer) = observer else {
+            return;
+        };
+        
+        let duration = (chrono::Utc::now() - suite.start_time).to_std().unwrap_or_default();
+        let history = observer.stop();
+        if !self.thermal_in_background {
+            return;
+        }
+        
+        let result = thermal_result(ThermalMonitorTest.name(), &history, &self.config, "background", duration);
+        self.reporter.report_test_result(&result);
+        suite.results.push(result);
+    }
+    
+    
+    /// Adds the errors the health monitors saw during the run to the
+    /// results, alerting for critical ones and reporting every result they
+    /// changed again, including the system health result if one was needed.
+    fn finish_health_monitors(&self, monitors: Vec<Box<dyn HealthMonitor>>, alerts: Option<&AlertQueue>, suite: &mut TestSuite) {
+        let mut changed = BTreeSet::new();
+        for mut monitor in monitors {
+            let issues = monitor.finish();
+            if let Some(alerts) = alerts {
+                for issue in &issues {
+                    let test = monitor::result_for_component(&suite.results, &issue.component);
+                    alerts.issues_found(test, std::slice::from_ref(issue));
+                }
+            }
+            changed.extend(monitor::attach_issues(&mut suite.results, issues));
+        }
+        
+        for result in suite.results.iter().filter(|r| changed.contains(&r.name)) {
+            self.reporter.report_test_result(result);
+        }
     }
     
     
     pub fn execute_sequential(&mut self) -> Result<TestSuite> {
         let mut suite = TestSuite::new();
+        suite.system_info = Some(hardware::detect().system_info);
         
         self.reporter.report_start(&self.config);
+        let observer = self.start_thermal_observer();
+        let monitors = start_health_monitors();
+        let alerts = self.alerter.clone().map(AlertQueue::start);
         
         for test in &self.tests {
-            if self.is_interrupted() {
-                break;
+            if let Some(reason) = self.config.abort.reason() {
+                let result = skipped_result(test.name(), &reason);
+                self.reporter.report_test_result(&result);
+                suite.results.push(result);
+                continue;
             }
             
             let name = test.name();
             self.reporter.report_test_start(name);
+            if let Some(observer) = &observer {
+                observer.enter(name);
+            }
             
             let start_time = Instant::now();
-            let result = match test.execute(&self.config) {
+            let mut result = match test.execute(&self.config) {
                 Ok(result) => result,
                 Err(e) => {
                     let mut result = TestResult {
@@ -149,6 +259,10 @@ impl BurnInRunner {
                         duration: start_time.elapsed(),
                         metrics: serde_json::json!({}),
                         issues: Vec::new(),
+                        deductions: vec![Deduction {
+                            reason: format!("Test failed: {}", e),
+                            points: MAX_SCORE,
+                        }],
                     };
                     
                     
@@ -164,7 +278,16 @@ impl BurnInRunner {
                 }
             };
             
+            annotate_result(&self.config, &mut result);
+            
+            if let Some(observer) = &observer {
+                observer.leave(name);
+            }
+            
             self.reporter.report_test_result(&result);
+            if let Some(alerts) = &alerts {
+                alerts.test_finished(&result);
+            }
             suite.results.push(result);
             
             
@@ -173,8 +296,19 @@ impl BurnInRunner {
             }
         }
         
-        suite.finalize();
+        self.finish_thermal_observer(observer, &mut suite);
+        self.finish_health_monitors(monitors, alerts.as_ref(), &mut suite);
+        suite.aborted = self.config.abort.reason();
+        suite.adjustments = self.adjustments.clone();
+        suite.finalize_weighted(&self.config.score_weights);
+        if let Some(policy) = &self.config.policy {
+            suite.apply_policy(policy);
+        }
         self.reporter.report_suite_result(&suite);
+        if let Some(alerts) = alerts {
+            alerts.suite_finished(&suite);
+            report_deliveries(self.reporter.as_ref(), alerts.finish());
+        }
         
         Ok(suite)
     }
@@ -182,8 +316,12 @@ impl BurnInRunner {
     
     pub fn execute_parallel(&mut self) -> Result<TestSuite> {
         let mut suite = TestSuite::new();
+        suite.system_info = Some(hardware::detect().system_info);
         
         self.reporter.report_start(&self.config);
+        let observer = self.start_thermal_observer();
+        let monitors = start_health_monitors();
+        let alerts = self.alerter.clone().map(AlertQueue::start);
         
         
         
@@ -202,22 +340,28 @@ impl BurnInRunner {
         
         if !cpu_memory_tests.is_empty() {
             let config = self.config.clone();
-            let interrupted = self.interrupted.clone();
             let reporter = &self.reporter;
+            let alerts = alerts.as_ref();
+            let observer = observer.as_ref();
             
             reporter.report_info("Running CPU and memory tests in parallel...");
             
             let results: Vec<TestResult> = cpu_memory_tests.par_iter()
                 .map(|test| {
-                    if *interrupted.lock().unwrap() {
-                        return None;
+                    if let Some(reason) = config.abort.reason() {
+                        let result = skipped_result(test.name(), &reason);
+                        reporter.report_test_result(&result);
+                        return result;
                     }
                     
                     let name = test.name();
                     reporter.report_test_start(name);
+                    if let Some(observer) = observer {
+                        observer.enter(name);
+                    }
                     
                     let start_time = Instant::now();
-                    let result = match test.execute(&config) {
+                    let mut result = match test.execute(&config) {
                         Ok(result) => result,
                         Err(e) => {
                             let mut result = TestResult {
@@ -227,6 +371,10 @@ impl BurnInRunner {
                                 duration: start_time.elapsed(),
                                 metrics: serde_json::json!({}),
                                 issues: Vec::new(),
+                                deductions: vec![Deduction {
+                                    reason: format!("Test failed: {}", e),
+                                    points: MAX_SCORE,
+                                }],
                             };
                             
                             
@@ -242,16 +390,24 @@ impl BurnInRunner {
                         }
                     };
                     
+                    annotate_result(&config, &mut result);
+                    
+                    if let Some(observer) = observer {
+                        observer.leave(name);
+                    }
+                    
                     reporter.report_test_result(&result);
+                    if let Some(alerts) = alerts {
+                        alerts.test_finished(&result);
+                    }
                     
                     
                     if let Err(e) = test.cleanup() {
                         reporter.report_warning(&format!("Failed to clean up after test {}: {}", name, e));
                     }
                     
-                    Some(result)
+                    result
                 })
-                .filter_map(|r| r)
                 .collect();
             
             suite.results.extend(results);
@@ -259,15 +415,21 @@ impl BurnInRunner {
         
         
         for test in other_tests {
-            if self.is_interrupted() {
-                break;
+            if let Some(reason) = self.config.abort.reason() {
+                let result = skipped_result(test.name(), &reason);
+                self.reporter.report_test_result(&result);
+                suite.results.push(result);
+                continue;
             }
             
             let name = test.name();
             self.reporter.report_test_start(name);
+            if let Some(observer) = &observer {
+                observer.enter(name);
+            }
             
             let start_time = Instant::now();
-            let result = match test.execute(&self.config) {
+            let mut result = match test.execute(&self.config) {
                 Ok(result) => result,
                 Err(e) => {
                     let mut result = TestResult {
@@ -277,6 +439,10 @@ impl BurnInRunner {
                         duration: start_time.elapsed(),
                         metrics: serde_json::json!({}),
                         issues: Vec::new(),
+                        deductions: vec![Deduction {
+                            reason: format!("Test failed: {}", e),
+                            points: MAX_SCORE,
+                        }],
                     };
                     
                     
@@ -292,7 +458,16 @@ impl BurnInRunner {
                 }
             };
             
+            annotate_result(&self.config, &mut result);
+            
+            if let Some(observer) = &observer {
+                observer.leave(name);
+            }
+            
             self.reporter.report_test_result(&result);
+            if let Some(alerts) = &alerts {
+                alerts.test_finished(&result);
+            }
             suite.results.push(result);
             
             
@@ -301,8 +476,19 @@ impl BurnInRunner {
             }
         }
         
-        suite.finalize();
+        self.finish_thermal_observer(observer, &mut suite);
+        self.finish_health_monitors(monitors, alerts.as_ref(), &mut suite);
+        suite.aborted = self.config.abort.reason();
+        suite.adjustments = self.adjustments.clone();
+        suite.finalize_weighted(&self.config.score_weights);
+        if let Some(policy) = &self.config.policy {
+            suite.apply_policy(policy);
+        }
         self.reporter.report_suite_result(&suite);
+        if let Some(alerts) = alerts {
+            alerts.suite_finished(&suite);
+            report_deliveries(self.reporter.as_ref(), alerts.finish());
+        }
         
         Ok(suite)
     }
@@ -353,3 +539,229 @@ impl BurnInRunner {
         result
     }
 }
+
+/// Starts every health monitor before the first test.
+fn start_health_monitors() -> Vec<Box<dyn HealthMonitor>> {
+    let mut monitors = monitor::default_monitors();
+    for monitor in &mut monitors {
+        monitor.start();
+    }
+    monitors
+}
+
+/// Reports the outcome of sending an alert. Dry-run payloads go to the log
+/// so they stay out of machine-readable reports.
+fn report_deliveries(reporter: &(dyn Reporter + Send + Sync), deliveries: Vec<Delivery>) {
+    for delivery in deliveries {
+        match delivery {
+            Delivery::Sent { .. } => reporter.report_info(&delivery.to_string()),
+            Delivery::DryRun { .. } => log::info!("{}", delivery),
+            Delivery::Failed { .. } => reporter.report_warning(&delivery.to_string()),
+        }
+    }
+}
+
+/// Applies run-wide state to a finished test result: scoring against the
+/// baseline, and marking the result as cut short if the run was aborted.
+fn annotate_result(config: &TestConfig, result: &mut TestResult) {
+    use crate::core::test::{TestIssue, IssueSeverity};
+    
+    if let Some(baseline) = &config.baseline {
+        baseline.apply(result, config.baseline_tolerance_percent);
+    }
+    
+    if let Some(reason) = config.abort.reason() {
+        if result.status == TestStatus::Completed {
+            result.status = TestStatus::Partial;
+        }
+        result.issues.push(TestIssue {
+            component: result.name.clone(),
+            severity: IssueSeverity::High,
+            message: format!("Test stopped early: {}", reason),
+            action: Some(match reason {
+                AbortReason::ThermalCutoff { .. } => "Fix cooling before running the test again".to_string(),
+                AbortReason::Interrupted => "Run the test again to completion".to_string(),
+            }),
+        });
+    }
+}
+
+/// The result of a test that did not run because the run was stopped
+/// before its turn.
+fn skipped_result(name: &str, reason: &AbortReason) -> TestResult {
+    use crate::core::test::{TestIssue, IssueSeverity};
+    
+    TestResult {
+        name: name.to_string(),
+        status: TestStatus::Skipped,
+        score: 0,
+        duration: std::time::Duration::ZERO,
+        metrics: serde_json::json!({}),
+        issues: vec![TestIssue {
+            component: name.to_string(),
+            severity: IssueSeverity::High,
+            message: format!("Test not run: {}", reason),
+            action: Some("Run the test again to completion".to_string()),
+        }],
+        deductions: vec![Deduction {
+            reason: format!("Test not run: {}", reason),
+            points: MAX_SCORE,
+        }],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::Mutex;
+    use std::time::Duration;
+    use crate::core::test::{TestIssue, IssueSeverity};
+
+    fn result(name: &str, status: TestStatus) -> TestResult {
+        TestResult {
+            name: name.to_string(),
+            status,
+            score: 90,
+            duration: Duration::from_secs(10),
+            metrics: serde_json::json!({}),
+            issues: Vec::new(),
+            deductions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_annotate_result_after_abort() {
+        let config = TestConfig::default();
+        let mut before = result("cpu_stress", TestStatus::Completed);
+        annotate_result(&config, &mut before);
+        assert_eq!(before.status, TestStatus::Completed);
+        assert!(before.issues.is_empty());
+        
+        config.abort.trigger(AbortReason::ThermalCutoff {
+            sensor: "cpu".to_string(),
+            temp_celsius: 96.0,
+            critical_celsius: 90.0,
+        });
+        let mut after = result("cpu_stress", TestStatus::Completed);
+        annotate_result(&config, &mut after);
+        assert_eq!(after.status, TestStatus::Partial);
+        assert!(after.issues[0].message.starts_with("Test stopped early: Thermal cutoff"));
+    }
+
+    /// Records the results it is given.
+    #[derive(Clone, Default)]
+    struct Recorder(Arc<Mutex<Vec<TestResult>>>);
+
+    impl Reporter for Recorder {
+        fn report_start(&self, _config: &TestConfig) {}
+        fn report_test_start(&self, _test_name: &str) {}
+        fn report_test_result(&self, result: &TestResult) {
+            self.0.lock().unwrap().push(result.clone());
+        }
+        fn report_suite_result(&self, _suite: &TestSuite) {}
+        fn report_warning(&self, _message: &str) {}
+        fn report_info(&self, _message: &str) {}
+    }
+
+    struct FixedMonitor(Vec<TestIssue>);
+
+    impl HealthMonitor for FixedMonitor {
+        fn start(&mut self) {}
+        fn finish(&mut self) -> Vec<TestIssue> {
+            std::mem::take(&mut self.0)
+        }
+    }
+
+    #[test]
+    fn test_monitor_issues_reported_again() {
+        let recorder = Recorder::default();
+        let runner = BurnInRunner::new(Vec::new(), TestConfig::default(), Box::new(recorder.clone()));
+        let mut suite = TestSuite::new();
+        suite.results.push(result("cpu_stress", TestStatus::Completed));
+        suite.results.push(result("memory_validation", TestStatus::Completed));
+        
+        let monitors: Vec<Box<dyn HealthMonitor>> = vec![Box::new(FixedMonitor(vec![TestIssue {
+            component: "memory".to_string(),
+            severity: IssueSeverity::Critical,
+            message: "Uncorrectable ECC error on DIMM_A1".to_string(),
+            action: None,
+        }]))];
+        runner.finish_health_monitors(monitors, None, &mut suite);
+        
+        let reported = recorder.0.lock().unwrap();
+        assert_eq!(reported.len(), 1);
+        assert_eq!(reported[0].name, "memory_validation");
+        assert_eq!(reported[0].status, TestStatus::Failed);
+        assert_eq!(reported[0].issues[0].message, "Uncorrectable ECC error on DIMM_A1");
+    }
+
+    /// A test that interrupts the run when `interrupts` is set.
+    struct Interrupting {
+        name: &'static str,
+        interrupts: bool,
+    }
+
+    impl BurnInTest for Interrupting {
+        fn name(&self) -> &'static str {
+            self.name
+        }
+        fn detect_hardware(&self) -> Result<hardware::HardwareInfo> {
+            Ok(hardware::detect())
+        }
+        fn estimate_duration(&self, _config: &TestConfig) -> Duration {
+            Duration::ZERO
+        }
+        fn execute(&self, config: &TestConfig) -> Result<TestResult> {
+            if self.interrupts {
+                config.abort.trigger(AbortReason::Interrupted);
+            }
+            Ok(result(self.name, TestStatus::Completed))
+        }
+        fn cleanup(&self) -> Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_tests_after_abort_are_skipped() {
+        let recorder = Recorder::default();
+        let config = TestConfig { thermal_monitoring: false, ..TestConfig::default() };
+        let tests: Vec<Box<dyn BurnInTest + Send + Sync>> = vec![
+            Box::new(Interrupting { name: "cpu_stress", interrupts: true }),
+            Box::new(Interrupting { name: "storage_io", interrupts: false }),
+        ];
+        let mut runner = BurnInRunner::new(tests, config, Box::new(recorder.clone()));
+        let suite = runner.execute_sequential().unwrap();
+        
+        let statuses: Vec<_> = suite.results.iter().map(|r| (r.name.as_str(), r.status)).collect();
+        assert_eq!(statuses[..2], [("cpu_stress", TestStatus::Partial), ("storage_io", TestStatus::Skipped)]);
+        assert_eq!(suite.results[1].issues[0].message, "Test not run: Interrupted by user");
+        assert_eq!(suite.overall_status, TestStatus::Failed);
+        assert!(recorder.0.lock().unwrap().iter().any(|r| r.name == "storage_io" && r.status == TestStatus::Skipped));
+    }
+
+    #[test]
+    fn test_aborted_suite_fails() {
+        let mut suite = TestSuite::new();
+        suite.results.push(result("cpu_stress", TestStatus::Partial));
+        suite.aborted = Some(AbortReason::Interrupted);
+        suite.finalize();
+        assert_eq!(suite.overall_status, TestStatus::Failed);
+    }
+
+    #[test]
+    fn test_failed_policy_fails_suite() {
+        let mut suite = TestSuite::new();
+        suite.results.push(result("cpu_stress", TestStatus::Completed));
+        suite.finalize();
+        assert_eq!(suite.overall_status, TestStatus::Completed);
+        
+        suite.apply_policy(&Policy::from_toml("[[rule]]\ncheck = \"cpu.score >= 80\"\n").unwrap());
+        assert_eq!(suite.overall_status, TestStatus::Completed);
+        assert!(suite.policy.as_ref().unwrap().passed);
+        
+        suite.apply_policy(&Policy::from_toml("[[rule]]\ncheck = \"overall_score >= 95\"\n").unwrap());
+        assert_eq!(suite.overall_status, TestStatus::Failed);
+        assert!(!suite.policy.as_ref().unwrap().passed);
+    }
+}
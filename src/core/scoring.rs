@@ -0,0 +1,190 @@
+use std::collections::BTreeMap;
+use serde::{Serialize, Deserialize};
+
+use crate::core::monitor;
+use crate::core::test::TestResult;
+
+/// The best possible score.
+pub const MAX_SCORE: u8 = 100;
+
+/// Points taken off a score, and why.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Deduction {
+    pub reason: String,
+    pub points: u8,
+}
+
+/// Builds a score from 100 down, recording every deduction.
+///
+/// Deductions saturate at zero and only the points actually lost are
+/// recorded, so the deductions of a finished scorecard always add up to
+/// `100 - score`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Scorecard {
+    score: u8,
+    deductions: Vec<Deduction>,
+}
+
+impl Default for Scorecard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Scorecard {
+    /// Starts a scorecard at the full score.
+    pub fn new() -> Self {
+        Self { score: MAX_SCORE, deductions: Vec::new() }
+    }
+
+    pub fn score(&self) -> u8 {
+        self.score
+    }
+
+    pub fn deductions(&self) -> &[Deduction] {
+        &self.deductions
+    }
+
+    /// Takes off `points`, rounded down and capped at `max_points`.
+    /// Negative and NaN penalties take off nothing.
+    pub fn deduct(&mut self, reason: impl Into<String>, points: f64, max_points: u8) {
+        let points = if points.is_nan() { 0.0 } else { points.clamp(0.0, max_points as f64) } as u8;
+        let points = points.min(self.score);
+        if points == 0 {
+            return;
+        }
+        self.score -= points;
+        self.deductions.push(Deduction { reason: reason.into(), points });
+    }
+
+    /// Takes off `points_per_unit` for every unit `value` falls short of
+    /// `threshold`, up to `max_points`.
+    pub fn below(&mut self, metric: &str, value: f64, threshold: f64, points_per_unit: f64, max_points: u8) {
+        if value < threshold {
+            self.deduct(
+                format!("{} {:.1} is below {}", metric, value, threshold),
+                (threshold - value) * points_per_unit,
+                max_points,
+            );
+        }
+    }
+
+    /// Takes off `points_per_unit` for every unit `value` exceeds
+    /// `threshold`, up to `max_points`.
+    pub fn above(&mut self, metric: &str, value: f64, threshold: f64, points_per_unit: f64, max_points: u8) {
+        if value > threshold {
+            self.deduct(
+                format!("{} {:.1} is above {}", metric, value, threshold),
+                (value - threshold) * points_per_unit,
+                max_points,
+            );
+        }
+    }
+
+    /// Takes off every remaining point.
+    pub fn zero(&mut self, reason: impl Into<String>) {
+        self.deduct(reason, MAX_SCORE as f64, MAX_SCORE);
+    }
+
+    /// Returns the final score and the deductions that led to it.
+    pub fn finish(self) -> (u8, Vec<Deduction>) {
+        (self.score, self.deductions)
+    }
+}
+
+/// Relative weight of each test in the suite score, keyed by test name or by
+/// the component it exercises (`memory` for `memory_validation`).
+///
+/// Tests without an entry weigh 1; a weight of 0 leaves a test out of the
+/// suite score.
+pub type ScoreWeights = BTreeMap<String, f64>;
+
+/// Returns the weight of the named test.
+pub fn weight(weights: &ScoreWeights, test: &str) -> f64 {
+    weights.iter()
+        .find(|(key, _)| key.as_str() == test || monitor::test_for_component(key) == Some(test))
+        .map_or(1.0, |(_, weight)| weight.max(0.0))
+}
+
+/// The suite score: the mean of the test scores, each weighted by its
+/// duration and its configured weight.
+///
+/// Tests that took no measurable time count as one second, so a suite of
+/// instant tests averages the scores by weight alone.
+pub fn suite_score(results: &[TestResult], weights: &ScoreWeights) -> u8 {
+    let (total, weight_sum) = results.iter()
+        .map(|result| {
+            let weight = weight(weights, &result.name) * result.duration.as_secs_f64().max(1.0);
+            (result.score as f64 * weight, weight)
+        })
+        .fold((0.0, 0.0), |(total, weight_sum), (score, weight)| (total + score, weight_sum + weight));
+
+    if weight_sum <= 0.0 {
+        return 0;
+    }
+    (total / weight_sum).round().clamp(0.0, MAX_SCORE as f64) as u8
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use serde_json::json;
+    use crate::core::test::TestStatus;
+
+    fn result(name: &str, score: u8, secs: u64) -> TestResult {
+        TestResult {
+            name: name.to_string(),
+            status: TestStatus::Completed,
+            score,
+            duration: Duration::from_secs(secs),
+            metrics: json!({}),
+            issues: Vec::new(),
+            deductions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_scorecard_saturates() {
+        let mut card = Scorecard::new();
+        card.below("sequential_read_mbps", 20.0, 50.0, 0.2, 10);
+        card.above("latency_ms", 600.0, 100.0, 0.1, 20);
+        card.deduct("5 storage errors", 250.0, 50);
+        card.deduct("negative", -3.0, 10);
+        card.deduct("not a number", f64::NAN, 10);
+        card.zero("SIMD results did not match");
+        card.deduct("after zero", 10.0, 10);
+
+        let (score, deductions) = card.finish();
+        assert_eq!(score, 0);
+        let points: Vec<_> = deductions.iter().map(|d| (d.reason.as_str(), d.points)).collect();
+        assert_eq!(points, [
+            ("sequential_read_mbps 20.0 is below 50", 6),
+            ("latency_ms 600.0 is above 100", 20),
+            ("5 storage errors", 50),
+            ("SIMD results did not match", 24),
+        ]);
+        assert_eq!(deductions.iter().map(|d| d.points as u32).sum::<u32>(), 100);
+    }
+
+    #[test]
+    fn test_suite_score_weights() {
+        let results = [
+            result("cpu_stress", 100, 3600),
+            result("storage_io", 40, 3600),
+        ];
+        assert_eq!(suite_score(&results, &ScoreWeights::new()), 70);
+
+        let weights = ScoreWeights::from([("storage".to_string(), 3.0)]);
+        assert_eq!(suite_score(&results, &weights), 55);
+
+        let weights = ScoreWeights::from([("storage_io".to_string(), 0.0)]);
+        assert_eq!(suite_score(&results, &weights), 100);
+
+        // A week-long run would overflow a u32 sum of score * seconds.
+        let week = 7 * 24 * 3600;
+        assert_eq!(suite_score(&[result("cpu_stress", 100, week), result("memory_validation", 100, week)], &ScoreWeights::new()), 100);
+        assert_eq!(suite_score(&[result("cpu_stress", 90, 0), result("memory_validation", 60, 0)], &ScoreWeights::new()), 75);
+        assert_eq!(suite_score(&[], &ScoreWeights::new()), 0);
+    }
+}
@@ -3,6 +3,7 @@ use serde::{Serialize, Deserialize};
 use crate::core::error::Result;
 use crate::core::hardware::HardwareInfo;
 use crate::core::config::TestConfig;
+use crate::core::scoring::Deduction;
 
 /// The status of a test.
 #[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
@@ -31,6 +32,22 @@ pub struct TestResult {
     pub duration: Duration,
     pub metrics: serde_json::Value,
     pub issues: Vec<TestIssue>,
+    /// Why the score is below 100, in the order the points were lost
+    #[serde(default)]
+    pub deductions: Vec<Deduction>,
+}
+
+impl TestResult {
+    /// Lowers the score by up to `points`, recording why. The recorded
+    /// deduction is the number of points actually lost.
+    pub fn deduct(&mut self, reason: impl Into<String>, points: u8) {
+        let points = points.min(self.score);
+        if points == 0 {
+            return;
+        }
+        self.score -= points;
+        self.deductions.push(Deduction { reason: reason.into(), points });
+    }
 }
 
 /// An issue detected during a test.
@@ -97,6 +114,7 @@ mod tests {
             duration: Duration::from_secs(60),
             metrics: serde_json::json!({}),
             issues: vec![],
+            deductions: Vec::new(),
         };
         
         assert_eq!(result.name, "test_cpu");
@@ -0,0 +1,24 @@
+//! Fixtures shared by the tests of the sysfs and procfs readers.
+
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Returns an empty directory for a fixture, unique to the test process.
+pub fn fixture_dir(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("burnin-{}-{}", name, std::process::id()));
+    let _ = fs::remove_dir_all(&dir);
+    fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+/// Writes an attribute the way sysfs presents it, followed by a newline.
+pub fn write(dir: &Path, file: &str, contents: &str) {
+    write_bytes(dir, file, format!("{}\n", contents).as_bytes());
+}
+
+/// Writes `contents` as is, creating the directories `file` lives in.
+pub fn write_bytes(dir: &Path, file: &str, contents: &[u8]) {
+    let path = dir.join(file);
+    fs::create_dir_all(path.parent().unwrap()).unwrap();
+    fs::write(path, contents).unwrap();
+}
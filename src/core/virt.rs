@@ -0,0 +1,220 @@
+use std::fs;
+use std::path::Path;
+
+use crate::core::hardware::VirtualizationType;
+
+/// Hypervisor vendor signatures reported in CPUID leaf `0x4000_0000`.
+const CPUID_VENDORS: &[(&str, VirtualizationType)] = &[
+    ("KVMKVMKVM", VirtualizationType::Kvm),
+    ("TCGTCGTCGTCG", VirtualizationType::Kvm),
+    ("VMwareVMware", VirtualizationType::Vmware),
+    ("VBoxVBoxVBox", VirtualizationType::Virtualbox),
+    ("XenVMMXenVMM", VirtualizationType::Xen),
+    ("Microsoft Hv", VirtualizationType::Hyperv),
+];
+
+/// Substrings of the DMI vendor and product strings that identify a hypervisor.
+///
+/// Checked in order, so the more specific Hyper-V product name comes before
+/// anything a Microsoft-branded guest might share with it.
+const DMI_VENDORS: &[(&str, VirtualizationType)] = &[
+    ("Microsoft Corporation Virtual Machine", VirtualizationType::Hyperv),
+    ("KVM", VirtualizationType::Kvm),
+    ("QEMU", VirtualizationType::Kvm),
+    ("Amazon EC2", VirtualizationType::Kvm),
+    ("Google Compute Engine", VirtualizationType::Kvm),
+    ("VMware", VirtualizationType::Vmware),
+    ("innotek GmbH", VirtualizationType::Virtualbox),
+    ("VirtualBox", VirtualizationType::Virtualbox),
+    ("Xen", VirtualizationType::Xen),
+];
+
+/// DMI attributes under `/sys/class/dmi/id` that name the platform vendor.
+const DMI_FILES: &[&str] = &["sys_vendor", "product_name", "board_vendor", "bios_vendor"];
+
+/// Detects whether the running system is a container or virtual machine.
+pub fn detect_virtualization() -> VirtualizationType {
+    detect_virtualization_from(Path::new("/"), cpuid_hypervisor())
+}
+
+/// Detects virtualization from the filesystem under `root` and the CPUID
+/// hypervisor vendor, if the hypervisor bit is set.
+///
+/// Like `systemd-detect-virt`, containers are checked first because a
+/// container on a virtual machine still sees the host's CPUID and DMI data.
+/// Returns [`VirtualizationType::Unknown`] if a hypervisor is present but not
+/// recognized, and [`VirtualizationType::None`] on bare metal.
+pub fn detect_virtualization_from(root: &Path, cpuid_vendor: Option<String>) -> VirtualizationType {
+    if let Some(container) = detect_container(root) {
+        return container;
+    }
+
+    let cpuid_vendor = cpuid_vendor.or_else(|| cpuinfo_hypervisor(root));
+    if let Some(vendor) = &cpuid_vendor {
+        if let Some((_, virt)) = CPUID_VENDORS.iter().find(|(signature, _)| vendor.starts_with(signature)) {
+            return *virt;
+        }
+    }
+
+    if let Some(virt) = detect_dmi(root) {
+        return virt;
+    }
+
+    if read_trimmed(&root.join("sys/hypervisor/type")).as_deref() == Some("xen")
+        || root.join("proc/xen").exists()
+    {
+        return VirtualizationType::Xen;
+    }
+
+    if cpuid_vendor.is_some() {
+        VirtualizationType::Unknown
+    } else {
+        VirtualizationType::None
+    }
+}
+
+fn detect_container(root: &Path) -> Option<VirtualizationType> {
+    if root.join(".dockerenv").exists() || root.join("run/.containerenv").exists() {
+        return Some(VirtualizationType::Docker);
+    }
+
+    let environ = fs::read(root.join("proc/1/environ")).unwrap_or_default();
+    let container = environ.split(|byte| *byte == 0)
+        .find_map(|var| var.strip_prefix(b"container="))
+        .map(|value| String::from_utf8_lossy(value).into_owned());
+    match container.as_deref() {
+        Some("lxc") | Some("lxc-libvirt") => return Some(VirtualizationType::Lxc),
+        Some("docker") | Some("podman") | Some("oci") => return Some(VirtualizationType::Docker),
+        Some(_) => return Some(VirtualizationType::Unknown),
+        None => {}
+    }
+
+    let cgroup = fs::read_to_string(root.join("proc/1/cgroup")).unwrap_or_default();
+    for line in cgroup.lines() {
+        let path = line.splitn(3, ':').nth(2).unwrap_or_default();
+        if path.contains("/docker") || path.contains("/kubepods") || path.contains("/containerd") || path.contains("libpod") {
+            return Some(VirtualizationType::Docker);
+        }
+        if path.contains("/lxc") {
+            return Some(VirtualizationType::Lxc);
+        }
+    }
+
+    None
+}
+
+fn detect_dmi(root: &Path) -> Option<VirtualizationType> {
+    let dmi = root.join("sys/class/dmi/id");
+    let strings: Vec<String> = DMI_FILES.iter()
+        .filter_map(|file| read_trimmed(&dmi.join(file)))
+        .collect();
+    let combined = strings.join(" ");
+
+    DMI_VENDORS.iter()
+        .find(|(signature, _)| combined.contains(signature))
+        .map(|(_, virt)| *virt)
+}
+
+/// Falls back to the `hypervisor` flag in `/proc/cpuinfo` when CPUID is not
+/// available; the vendor is then unknown.
+fn cpuinfo_hypervisor(root: &Path) -> Option<String> {
+    let cpuinfo = fs::read_to_string(root.join("proc/cpuinfo")).ok()?;
+    cpuinfo.lines()
+        .filter(|line| line.starts_with("flags"))
+        .any(|line| line.split_whitespace().any(|flag| flag == "hypervisor"))
+        .then(String::new)
+}
+
+/// Returns the CPUID hypervisor vendor string if the hypervisor bit is set.
+#[cfg(target_arch = "x86_64")]
+#[allow(unused_unsafe)]
+fn cpuid_hypervisor() -> Option<String> {
+    use std::arch::x86_64::__cpuid;
+
+    let features = unsafe { __cpuid(1) };
+    if features.ecx & (1 << 31) == 0 {
+        return None;
+    }
+
+    let leaf = unsafe { __cpuid(0x4000_0000) };
+    let bytes: Vec<u8> = [leaf.ebx, leaf.ecx, leaf.edx].iter()
+        .flat_map(|reg| reg.to_le_bytes())
+        .collect();
+    Some(String::from_utf8_lossy(&bytes).trim_end_matches('\0').to_string())
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn cpuid_hypervisor() -> Option<String> {
+    None
+}
+
+fn read_trimmed(path: &Path) -> Option<String> {
+    fs::read_to_string(path)
+        .ok()
+        .map(|contents| contents.trim().to_string())
+        .filter(|contents| !contents.is_empty())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test_support::{fixture_dir, write_bytes};
+
+    #[test]
+    fn test_detect_containers() {
+        let root = fixture_dir("virt-containers");
+
+        let docker = root.join("docker");
+        write_bytes(&docker, ".dockerenv", b"");
+        assert_eq!(detect_virtualization_from(&docker, Some("KVMKVMKVM".to_string())), VirtualizationType::Docker);
+
+        let lxc = root.join("lxc");
+        write_bytes(&lxc, "proc/1/environ", b"PATH=/usr/bin\0container=lxc\0");
+        assert_eq!(detect_virtualization_from(&lxc, None), VirtualizationType::Lxc);
+
+        let kubernetes = root.join("kubernetes");
+        write_bytes(&kubernetes, "proc/1/cgroup", b"12:memory:/kubepods/besteffort/pod1234/abcd\n0::/\n");
+        assert_eq!(detect_virtualization_from(&kubernetes, None), VirtualizationType::Docker);
+
+        let host = root.join("host");
+        write_bytes(&host, "proc/1/cgroup", b"0::/init.scope\n");
+        write_bytes(&host, "proc/1/environ", b"HOME=/\0");
+        assert_eq!(detect_virtualization_from(&host, None), VirtualizationType::None);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_detect_virtual_machines() {
+        let root = fixture_dir("virt-machines");
+
+        assert_eq!(detect_virtualization_from(&root, Some("VMwareVMware".to_string())), VirtualizationType::Vmware);
+        assert_eq!(detect_virtualization_from(&root, Some("Microsoft Hv".to_string())), VirtualizationType::Hyperv);
+        assert_eq!(detect_virtualization_from(&root, Some("NoSuchHyperv".to_string())), VirtualizationType::Unknown);
+
+        let vbox = root.join("vbox");
+        write_bytes(&vbox, "sys/class/dmi/id/sys_vendor", b"innotek GmbH\n");
+        write_bytes(&vbox, "sys/class/dmi/id/product_name", b"VirtualBox\n");
+        assert_eq!(detect_virtualization_from(&vbox, Some(String::new())), VirtualizationType::Virtualbox);
+
+        let hyperv = root.join("hyperv");
+        write_bytes(&hyperv, "sys/class/dmi/id/sys_vendor", b"Microsoft Corporation\n");
+        write_bytes(&hyperv, "sys/class/dmi/id/product_name", b"Virtual Machine\n");
+        assert_eq!(detect_virtualization_from(&hyperv, None), VirtualizationType::Hyperv);
+
+        let xen = root.join("xen");
+        write_bytes(&xen, "sys/hypervisor/type", b"xen\n");
+        assert_eq!(detect_virtualization_from(&xen, None), VirtualizationType::Xen);
+
+        let cpuinfo = root.join("cpuinfo");
+        write_bytes(&cpuinfo, "proc/cpuinfo", b"processor\t: 0\nflags\t\t: fpu vme hypervisor lahf_lm\n");
+        assert_eq!(detect_virtualization_from(&cpuinfo, None), VirtualizationType::Unknown);
+
+        let metal = root.join("metal");
+        write_bytes(&metal, "sys/class/dmi/id/sys_vendor", b"Dell Inc.\n");
+        write_bytes(&metal, "proc/cpuinfo", b"flags\t\t: fpu vme lahf_lm\n");
+        assert_eq!(detect_virtualization_from(&metal, None), VirtualizationType::None);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+}
@@ -1,29 +1,32 @@
 use std::process;
 use std::path::PathBuf;
+use std::time::Duration;
 use clap::{Parser, Subcommand, ValueEnum};
 use anyhow::{Result, Context};
 use log::{info, error};
 use simple_logger::SimpleLogger;
 
-mod core;
-mod tests;
-mod reporters;
-
-use crate::core::config::TestConfig;
-use crate::core::runner::BurnInRunner;
-use crate::core::test::BurnInTest;
-use crate::reporters::{Reporter, text::TextReporter, json::JsonReporter, csv::CsvReporter};
+use burnin::core;
+use burnin::tests;
+use burnin::core::config::TestConfig;
+use burnin::core::abort::AbortReason;
+use burnin::core::alert::Alerter;
+use burnin::core::baseline::{BaselineDb, HardwareFingerprint, DEFAULT_BASELINE_FILE};
+use burnin::core::policy::Policy;
+use burnin::core::runner::BurnInRunner;
+use burnin::core::hardware::SystemProfile;
+use burnin::reporters::{Reporter, text::TextReporter, json::JsonReporter, csv::CsvReporter, junit::JunitReporter, html::HtmlReporter, prometheus::PrometheusReporter, ndjson::EventLog, MultiReporter};
 
 
 #[derive(Parser)]
 #[command(author, version, about, long_about = None)]
 struct Cli {
     
-    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text)]
+    #[arg(short, long, value_enum, default_value_t = OutputFormat::Text, global = true)]
     format: OutputFormat,
     
     
-    #[arg(short, long)]
+    #[arg(short, long, global = true)]
     output: Option<String>,
     
     
@@ -35,10 +38,66 @@ struct Cli {
     quiet: bool,
     
     
-    #[arg(short, long)]
+    #[arg(long, global = true)]
     config: Option<PathBuf>,
     
     
+    #[arg(long, global = true)]
+    baseline: Option<PathBuf>,
+    
+    
+    #[arg(long, global = true)]
+    policy: Option<PathBuf>,
+    
+    
+    #[arg(long, global = true)]
+    thermal_background: bool,
+    
+    
+    #[arg(long, global = true)]
+    adaptive: bool,
+    
+    
+    #[arg(long, global = true)]
+    alert_webhook: Option<String>,
+    
+    
+    #[arg(long, global = true)]
+    alert_email: Option<String>,
+    
+    
+    #[arg(long, global = true)]
+    alert_smtp_server: Option<String>,
+    
+    
+    #[arg(long, global = true)]
+    alert_email_from: Option<String>,
+    
+    
+    #[arg(long, global = true)]
+    alert_retries: Option<u32>,
+    
+    
+    #[arg(long, global = true, value_parser = clap::value_parser!(u8).range(0..=100))]
+    alert_threshold: Option<u8>,
+    
+    
+    #[arg(long, global = true)]
+    alert_dry_run: bool,
+    
+    
+    #[arg(long, global = true)]
+    metrics_listen: Option<String>,
+    
+    
+    #[arg(long, global = true)]
+    metrics_textfile: Option<PathBuf>,
+    
+    
+    #[arg(long, global = true)]
+    event_log: Option<String>,
+    
+    
     #[command(subcommand)]
     command: Commands,
 }
@@ -52,6 +111,10 @@ enum OutputFormat {
     Json,
     
     Csv,
+    
+    Junit,
+    
+    Html,
 }
 
 
@@ -101,8 +164,8 @@ enum Commands {
     
     Custom {
         
-        #[arg(short, long)]
-        duration: String,
+        #[arg(short, long, value_parser = humantime::parse_duration)]
+        duration: Duration,
         
         
         #[arg(short, long, value_enum)]
@@ -131,6 +194,31 @@ enum Commands {
     
     
     Hardware,
+    
+    
+    Baseline {
+        #[command(subcommand)]
+        action: BaselineCommand,
+    },
+}
+
+
+#[derive(Subcommand)]
+enum BaselineCommand {
+    
+    Record {
+        
+        #[arg(short, long, default_value = "5m", value_parser = humantime::parse_duration)]
+        duration: Duration,
+        
+        
+        #[arg(short, long, value_enum)]
+        components: Option<Vec<Component>>,
+        
+        
+        #[arg(short, long)]
+        threads: Option<usize>,
+    },
 }
 
 
@@ -167,16 +255,66 @@ fn main() -> Result<()> {
     info!("Burnin v{}", env!("CARGO_PKG_VERSION"));
     
     
-    let mut config = if let Some(_path) = &cli.config {
-        
-        
-        TestConfig::default()
+    let mut config = if let Some(path) = &cli.config {
+        let config = TestConfig::from_file(&path.to_string_lossy())
+            .map_err(anyhow::Error::msg)
+            .with_context(|| format!("Failed to load config {}", path.display()))?;
+        info!("Loaded configuration from {}", path.display());
+        config
     } else {
-        
         TestConfig::default()
     };
     
     
+    if cli.thermal_background {
+        config.thermal_background = true;
+    }
+    
+    if cli.adaptive {
+        config.adaptive = true;
+    }
+    
+    if let Some(url) = &cli.alert_webhook {
+        config.alert_webhook_url = Some(url.clone());
+    }
+    
+    if let Some(recipients) = &cli.alert_email {
+        config.alert_email = Some(recipients.clone());
+    }
+    
+    if let Some(server) = &cli.alert_smtp_server {
+        config.alert_smtp_server = Some(server.clone());
+    }
+    
+    if let Some(sender) = &cli.alert_email_from {
+        config.alert_email_from = Some(sender.clone());
+    }
+    
+    if let Some(retries) = cli.alert_retries {
+        config.alert_retries = retries;
+    }
+    
+    if let Some(threshold) = cli.alert_threshold {
+        config.alert_threshold = threshold;
+    }
+    
+    if cli.alert_dry_run {
+        config.alert_dry_run = true;
+    }
+    
+    if let Some(address) = &cli.metrics_listen {
+        config.metrics_listen = Some(address.clone());
+    }
+    
+    if let Some(path) = &cli.metrics_textfile {
+        config.metrics_textfile = Some(path.clone());
+    }
+    
+    if let Some(destination) = &cli.event_log {
+        config.event_log = Some(destination.clone());
+    }
+    
+    let mut recording = false;
     match &cli.command {
         Commands::Quick { components, threads } => {
             config.apply_preset_quick();
@@ -195,8 +333,7 @@ fn main() -> Result<()> {
         
         Commands::Custom { duration, components, threads, stress, memory_size, storage_path, storage_size } => {
             
-            config.duration = humantime::parse_duration(duration)
-                .context("Failed to parse duration")?;
+            config.duration = *duration;
             
             update_config_from_args(
                 &mut config,
@@ -210,15 +347,83 @@ fn main() -> Result<()> {
         }
         
         Commands::Hardware => {
-            return print_hardware_info();
+            return print_hardware_info(cli.format, cli.output.as_deref());
         }
+        
+        Commands::Baseline { action: BaselineCommand::Record { duration, components, threads } } => {
+            config.duration = *duration;
+            update_config_from_args(&mut config, components, *threads, None, None, None, None);
+            recording = true;
+        }
+    }
+    
+    config.validate()
+        .map_err(anyhow::Error::msg)
+        .context("Invalid configuration")?;
+    
+    
+    let baseline_path = cli.baseline.clone()
+        .or_else(|| config.baseline_file.clone())
+        .unwrap_or_else(|| PathBuf::from(DEFAULT_BASELINE_FILE));
+    let mut baselines = BaselineDb::load(&baseline_path)
+        .context("Failed to load baseline database")?;
+    let hardware = core::hardware::detect();
+    let fingerprint = HardwareFingerprint::from_hardware(&hardware);
+    
+    if !recording {
+        config.baseline = baselines.baseline_for(&fingerprint);
+        if let Some(baseline) = &config.baseline {
+            info!("Scoring against baseline recorded {}", baseline.recorded_at);
+        }
+    }
+    
+    if let Some(path) = cli.policy.clone().or_else(|| config.policy_file.clone()) {
+        let policy = Policy::load(&path)
+            .with_context(|| format!("Failed to load policy {}", path.display()))?;
+        info!("Evaluating {} policy rules from {}", policy.rules.len(), path.display());
+        config.policy = Some(policy);
     }
     
+    let alerter = Alerter::from_config(&config).context("Failed to configure alerts")?;
+    
     
     let reporter: Box<dyn Reporter + Send + Sync> = match cli.format {
         OutputFormat::Text => Box::new(TextReporter::new(cli.verbose, cli.quiet)),
         OutputFormat::Json => Box::new(JsonReporter::new(cli.output.clone(), cli.verbose)),
         OutputFormat::Csv => Box::new(CsvReporter::new(cli.output.clone())),
+        OutputFormat::Junit => Box::new(JunitReporter::new(cli.output.clone())),
+        OutputFormat::Html => Box::new(HtmlReporter::new(cli.output.clone())),
+    };
+    
+    let mut reporters = vec![reporter];
+    
+    if config.metrics_listen.is_some() || config.metrics_textfile.is_some() {
+        let mut exporter = PrometheusReporter::new();
+        if let Some(path) = &config.metrics_textfile {
+            exporter = exporter.with_textfile(path.clone());
+            info!("Writing metrics to {}", path.display());
+        }
+        if let Some(address) = &config.metrics_listen {
+            let bound = exporter.serve(address)
+                .with_context(|| format!("Failed to serve metrics on {}", address))?;
+            info!("Serving metrics at http://{}/metrics", bound);
+        }
+        reporters.push(Box::new(exporter));
+    }
+    
+    if let Some(destination) = &config.event_log {
+        if destination == "-" && (cli.format == OutputFormat::Text || cli.output.is_none()) {
+            anyhow::bail!("--event-log - needs a report format written to a file with --output");
+        }
+        let log = EventLog::open(destination)
+            .with_context(|| format!("Failed to open event log {}", destination))?;
+        reporters.push(Box::new(log));
+    }
+    
+    let reporter: Box<dyn Reporter + Send + Sync> = if reporters.len() == 1 {
+        reporters.remove(0)
+    } else {
+        Box::new(MultiReporter::new(reporters))
     };
     
     
@@ -250,11 +455,42 @@ fn main() -> Result<()> {
     }
     
     
+    let adaptive = config.adaptive;
     let mut runner = BurnInRunner::new(tests, config, reporter);
     
+    if adaptive {
+        runner.adapt_to(&SystemProfile::new(hardware));
+    }
+    
+    if let Some(alerter) = alerter {
+        runner.alert_with(alerter);
+    }
+    
     
     match runner.execute_all() {
         Ok(suite) => {
+            match &suite.aborted {
+                Some(reason @ AbortReason::ThermalCutoff { .. }) => {
+                    error!("{}", reason);
+                    process::exit(5);
+                }
+                Some(AbortReason::Interrupted) => {
+                    process::exit(4);
+                }
+                None => {}
+            }
+            
+            if recording {
+                if suite.overall_status == core::test::TestStatus::Failed {
+                    error!("Not recording a baseline from a failed run");
+                    process::exit(1);
+                }
+                baselines.record(&fingerprint, &suite.results);
+                baselines.save(&baseline_path)
+                    .context("Failed to save baseline database")?;
+                info!("Recorded baseline for {} in {}", fingerprint.cpu_model, baseline_path.display());
+            }
+            
             if suite.overall_status == core::test::TestStatus::Failed {
                 process::exit(1);
             }
@@ -330,57 +566,174 @@ fn update_config_from_args(
 }
 
 
-fn print_hardware_info() -> Result<()> {
+fn print_hardware_info(format: OutputFormat, output: Option<&str>) -> Result<()> {
+    let hardware = core::hardware::detect();
+    
+    if format == OutputFormat::Json {
+        let json = serde_json::to_string_pretty(&hardware)
+            .context("Failed to serialize hardware information")?;
+        match output {
+            Some(path) => std::fs::write(path, json + "\n")
+                .with_context(|| format!("Failed to write {}", path))?,
+            None => println!("{}", json),
+        }
+        return Ok(());
+    }
+    
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    
     println!("System Hardware Information:");
     println!("============================");
+    println!("Host: {}", hardware.system_info.hostname);
+    println!("OS: {} {} (kernel {})", hardware.system_info.os_name, hardware.system_info.os_version, hardware.system_info.kernel_version);
+    
+    let dmi = &hardware.dmi;
+    let unknown = |value: &Option<String>| value.clone().unwrap_or_else(|| "Unknown".to_string());
+    if dmi.system_vendor.is_some() || dmi.system_product.is_some() {
+        println!("\nSystem:");
+        println!("  Product: {} {}", unknown(&dmi.system_vendor), unknown(&dmi.system_product));
+        println!("  Serial: {}", unknown(&dmi.system_serial));
+        println!("  Board: {} {} (serial {})", unknown(&dmi.board_vendor), unknown(&dmi.board_product), unknown(&dmi.board_serial));
+        println!("  BIOS: {} {} ({})", unknown(&dmi.bios_vendor), unknown(&dmi.bios_version), unknown(&dmi.bios_date));
+    }
+    
+    println!("\nCPU Information:");
+    println!("  Model: {}", hardware.cpu_info.model_name);
+    println!("  Vendor: {}", hardware.cpu_info.vendor);
+    println!("  Cores: {} physical, {} logical", hardware.cpu_info.physical_cores, hardware.cpu_info.logical_cores);
+    if hardware.cpu_info.frequency_mhz > 0.0 {
+        println!("  Frequency: {:.2} GHz", hardware.cpu_info.frequency_mhz / 1000.0);
+    }
+    if !hardware.cpu_info.cache_sizes.is_empty() {
+        let mut caches: Vec<_> = hardware.cpu_info.cache_sizes.iter().collect();
+        caches.sort();
+        let caches: Vec<String> = caches.iter()
+            .map(|(level, size)| format!("{} {} KiB", level, *size / 1024))
+            .collect();
+        println!("  Cache: {}", caches.join(", "));
+    }
+    if !hardware.cpu_info.features.is_empty() {
+        println!("  Features: {}", hardware.cpu_info.features.join(" "));
+    }
     
+    println!("\nMemory Information:");
+    println!("  Total: {:.2} GB", hardware.memory_info.total_bytes as f64 / GIB);
+    println!("  Available: {:.2} GB", hardware.memory_info.available_bytes as f64 / GIB);
+    if let Some(speed) = hardware.memory_info.speed_mhz {
+        println!("  Speed: {} MT/s", speed);
+    }
+    if let Some(ecc) = hardware.memory_info.ecc_enabled {
+        println!("  ECC: {}", if ecc { "enabled" } else { "disabled" });
+    }
+    for module in &hardware.memory_info.modules {
+        println!("  {}: {:.0} GB {} {} {} ({})",
+            module.locator,
+            module.size_bytes as f64 / GIB,
+            unknown(&module.memory_type),
+            module.configured_speed_mhz.or(module.speed_mhz).map_or_else(|| "Unknown".to_string(), |speed| format!("{} MT/s", speed)),
+            unknown(&module.manufacturer),
+            unknown(&module.part_number));
+    }
     
-    let cpu_test = tests::cpu::CpuStressTest;
+    let limits = &hardware.resource_limits;
+    println!("\nResource Limits:");
+    if limits.is_limited() {
+        if let Some(quota) = limits.cpu_quota_cores {
+            println!("  CPU quota: {:.2} CPUs", quota);
+        }
+        if let Some(cpus) = limits.cpuset_cpus {
+            println!("  CPU set: {} CPUs", cpus);
+        }
+        if let Some(limit) = limits.memory_limit_bytes {
+            println!("  Memory limit: {:.2} GB", limit as f64 / GIB);
+        }
+        println!("  Effective: {} CPUs, {:.2} GB available",
+            limits.effective_cpus(hardware.cpu_info.logical_cores as usize),
+            limits.available_memory(hardware.memory_info.available_bytes) as f64 / GIB);
+    } else {
+        println!("  No cgroup limits");
+    }
     
+    println!("\nStorage Devices:");
+    if hardware.storage_devices.is_empty() {
+        println!("  No storage devices detected");
+    }
+    for (i, device) in hardware.storage_devices.iter().enumerate() {
+        println!("  Device #{}:", i + 1);
+        println!("    Name: {}", device.name);
+        println!("    Model: {}", device.model);
+        println!("    Type: {:?}", device.device_type);
+        println!("    Size: {:.2} GB", device.size_bytes as f64 / GIB);
+        if let Some(mount) = &device.mount_point {
+            println!("    Mount: {}", mount);
+        }
+        if let Some(filesystem) = &device.filesystem {
+            println!("    Filesystem: {}", filesystem);
+        }
+    }
     
-    match cpu_test.detect_hardware() {
-        Ok(hardware) => {
-            println!("CPU Information:");
-            println!("  Model: {}", hardware.cpu_info.model_name);
-            println!("  Cores: {} physical, {} logical", hardware.cpu_info.physical_cores, hardware.cpu_info.logical_cores);
-            if hardware.cpu_info.frequency_mhz > 0.0 {
-                println!("  Frequency: {:.2} GHz", hardware.cpu_info.frequency_mhz / 1000.0);
-            }
-            
-            println!("\nMemory Information:");
-            println!("  Total: {:.2} GB", hardware.memory_info.total_bytes as f64 / 1024.0 / 1024.0 / 1024.0);
-            println!("  Available: {:.2} GB", hardware.memory_info.available_bytes as f64 / 1024.0 / 1024.0 / 1024.0);
-            
-            println!("\nStorage Devices:");
-            for (i, device) in hardware.storage_devices.iter().enumerate() {
-                println!("  Device #{}:", i + 1);
-                println!("    Name: {}", device.name);
-                println!("    Type: {:?}", device.device_type);
-                println!("    Size: {:.2} GB", device.size_bytes as f64 / 1024.0 / 1024.0 / 1024.0);
-                if let Some(mount) = &device.mount_point {
-                    println!("    Mount: {}", mount);
-                }
-            }
-            
-            println!("\nVirtualization:");
-            println!("  Type: {:?}", hardware.virtualization);
-            
-            println!("\nThermal Sensors:");
-            if hardware.thermal_sensors.is_empty() {
-                println!("  No thermal sensors detected");
-            } else {
-                for (i, sensor) in hardware.thermal_sensors.iter().enumerate() {
-                    println!("  Sensor #{}:", i + 1);
-                    println!("    Name: {}", sensor.name);
-                    println!("    Temperature: {:.1}°C", sensor.current_temp_celsius);
-                    if let Some(critical) = sensor.critical_temp_celsius {
-                        println!("    Critical: {:.1}°C", critical);
-                    }
-                }
-            }
+    let links: Vec<_> = hardware.pci_devices.iter()
+        .filter(|device| device.current_link_width.is_some() || device.current_link_speed_gts.is_some())
+        .collect();
+    println!("\nPCIe Links:");
+    if links.is_empty() {
+        println!("  No PCIe links detected");
+    }
+    for device in links {
+        println!("  {} [{}] {}: {} (max {}){}",
+            device.address,
+            device.class,
+            device.driver.as_deref().unwrap_or("no driver"),
+            core::pci::format_link(device.current_link_speed_gts, device.current_link_width),
+            core::pci::format_link(device.max_link_speed_gts, device.max_link_width),
+            if device.is_downtrained() { " DOWNTRAINED" } else { "" });
+        if let Some(aer) = device.aer.filter(|aer| aer.correctable + aer.nonfatal + aer.fatal > 0) {
+            println!("    AER errors since boot: {} corrected, {} non-fatal, {} fatal", aer.correctable, aer.nonfatal, aer.fatal);
         }
-        Err(e) => {
-            println!("Failed to detect hardware: {}", e);
+    }
+    
+    println!("\nVirtualization:");
+    match hardware.virtualization {
+        Some(virt) => println!("  Type: {:?}", virt),
+        None => println!("  Type: Unknown"),
+    }
+    
+    println!("\nThermal Sensors:");
+    if hardware.thermal_sensors.is_empty() {
+        println!("  No thermal sensors detected");
+    }
+    for (i, sensor) in hardware.thermal_sensors.iter().enumerate() {
+        println!("  Sensor #{}:", i + 1);
+        println!("    Name: {}", sensor.name);
+        println!("    Location: {}", sensor.location);
+        println!("    Temperature: {:.1}°C", sensor.current_temp_celsius);
+        if let Some(max) = sensor.max_temp_celsius {
+            println!("    Max: {:.1}°C", max);
+        }
+        if let Some(critical) = sensor.critical_temp_celsius {
+            println!("    Critical: {:.1}°C", critical);
+        }
+    }
+    
+    println!("\nFans:");
+    if hardware.fans.is_empty() {
+        println!("  No fans detected");
+    }
+    for fan in &hardware.fans {
+        match fan.min_rpm {
+            Some(min) => println!("  {}: {} RPM (min {} RPM)", fan.name, fan.rpm, min),
+            None => println!("  {}: {} RPM", fan.name, fan.rpm),
+        }
+    }
+    
+    println!("\nVoltage Rails:");
+    if hardware.voltage_sensors.is_empty() {
+        println!("  No voltage sensors detected");
+    }
+    for rail in &hardware.voltage_sensors {
+        match (rail.min_volts, rail.max_volts) {
+            (Some(min), Some(max)) => println!("  {}: {:.3} V ({:.3}-{:.3} V)", rail.name, rail.volts, min, max),
+            _ => println!("  {}: {:.3} V", rail.name, rail.volts),
         }
     }
     
@@ -5,6 +5,7 @@ use csv::Writer;
 use crate::core::test::{TestResult, TestStatus, IssueSeverity};
 use crate::core::config::TestConfig;
 use crate::core::runner::TestSuite;
+use crate::core::hardware;
 use crate::reporters::Reporter;
 
 /// A reporter that outputs test results in CSV format.
@@ -133,6 +134,84 @@ impl Reporter for CsvReporter {
             }
         }
 
+        let dmi = hardware::detect().dmi;
+        for (label, value) in [("Machine", dmi.machine()), ("Serial Number", dmi.system_serial)] {
+            if let Some(value) = value {
+                if let Err(e) = writer.write_record([label, &value, "", "", ""]) {
+                    eprintln!("Failed to write summary record: {}", e);
+                }
+            }
+        }
+
+        if let Some(reason) = &suite.aborted {
+            if let Err(e) = writer.write_record(["Aborted", &reason.to_string(), "", "", ""]) {
+                eprintln!("Failed to write summary record: {}", e);
+            }
+        }
+
+        for adjustment in &suite.adjustments {
+            if let Err(e) = writer.write_record([
+                "Adjusted",
+                &adjustment.parameter,
+                &adjustment.from,
+                &adjustment.to,
+                &adjustment.reason,
+            ]) {
+                eprintln!("Failed to write summary record: {}", e);
+            }
+        }
+
+        if let Some(policy) = &suite.policy {
+            let summary = format!("{}/{} rules passed", policy.passed_count(), policy.verdicts.len());
+            if let Err(e) = writer.write_record([
+                "Policy",
+                if policy.passed { "PASS" } else { "FAIL" },
+                &summary,
+                "",
+                "",
+            ]) {
+                eprintln!("Failed to write summary record: {}", e);
+            }
+            for verdict in &policy.verdicts {
+                if let Err(e) = writer.write_record([
+                    "Rule",
+                    &verdict.check,
+                    &verdict.verdict.to_string(),
+                    &verdict.actual.map_or_else(String::new, |actual| actual.to_string()),
+                    verdict.description.as_deref().unwrap_or(""),
+                ]) {
+                    eprintln!("Failed to write summary record: {}", e);
+                }
+            }
+        }
+
+        // Add score deductions section if any points were lost
+        if suite.results.iter().any(|r| !r.deductions.is_empty()) {
+            if let Err(e) = writer.write_record([""; 5]) {
+                eprintln!("Failed to write separator: {}", e);
+            }
+
+            if let Err(e) = writer.write_record([
+                "Test Name", "Points Lost", "Reason", "", ""
+            ]) {
+                eprintln!("Failed to write deductions columns: {}", e);
+            }
+
+            for result in &suite.results {
+                for deduction in &result.deductions {
+                    if let Err(e) = writer.write_record([
+                        &result.name,
+                        &deduction.points.to_string(),
+                        &deduction.reason,
+                        "",
+                        "",
+                    ]) {
+                        eprintln!("Failed to write deductions record: {}", e);
+                    }
+                }
+            }
+        }
+
         // Add metrics section if available
         if suite.results.iter().any(|r| match &r.metrics {
             serde_json::Value::Object(map) => !map.is_empty(),
@@ -0,0 +1,631 @@
+use std::fmt::Write as _;
+use std::fs;
+use serde_json::Value;
+
+use crate::core::test::{TestResult, TestStatus, IssueSeverity};
+use crate::core::config::TestConfig;
+use crate::core::runner::TestSuite;
+use crate::core::hardware::{self, HardwareInfo};
+use crate::core::policy::Verdict;
+use crate::reporters::Reporter;
+
+/// Metrics charted as time series: the metric, the chart title and the unit
+/// of its values. Each metric maps a series name to `[seconds, value]` pairs.
+const CHARTS: [(&str, &str, &str); 2] = [
+    ("series", "Temperature", "°C"),
+    ("throughput_series", "Sequential throughput", "MB/s"),
+];
+
+/// Points drawn per series; longer series keep the low and high of each
+/// stretch of samples so that peaks survive.
+const MAX_POINTS: usize = 800;
+
+const PALETTE: [&str; 8] = ["#2563eb", "#dc2626", "#16a34a", "#d97706", "#7c3aed", "#0891b2", "#db2777", "#4b5563"];
+
+const STYLE: &str = r#"
+body { font-family: -apple-system, "Segoe UI", Roboto, Helvetica, Arial, sans-serif; color: #1f2937; margin: 0; background: #f3f4f6; }
+main { max-width: 1000px; margin: 0 auto; padding: 24px; }
+header h1 { margin: 0 0 4px; font-size: 26px; }
+header p { margin: 0; color: #6b7280; }
+section { background: #fff; border-radius: 8px; padding: 16px 20px; margin-top: 20px; box-shadow: 0 1px 2px rgba(0,0,0,.06); }
+h2 { margin: 0 0 12px; font-size: 19px; }
+h3 { margin: 20px 0 8px; font-size: 16px; }
+h4 { margin: 12px 0 6px; font-size: 14px; color: #4b5563; }
+table { border-collapse: collapse; width: 100%; font-size: 14px; }
+th, td { text-align: left; padding: 5px 8px; border-bottom: 1px solid #e5e7eb; vertical-align: top; }
+th { background: #f9fafb; font-weight: 600; }
+td.num { font-variant-numeric: tabular-nums; }
+.cards { display: flex; flex-wrap: wrap; gap: 12px; }
+.card { flex: 1 1 140px; border: 1px solid #e5e7eb; border-radius: 6px; padding: 10px 14px; }
+.card .label { color: #6b7280; font-size: 12px; text-transform: uppercase; letter-spacing: .04em; }
+.card .value { font-size: 22px; font-weight: 600; margin-top: 4px; }
+.badge { display: inline-block; border-radius: 4px; padding: 1px 8px; font-size: 12px; font-weight: 700; color: #fff; vertical-align: middle; }
+.pass { background: #16a34a; } .fail { background: #dc2626; } .partial { background: #d97706; } .skipped { background: #6b7280; }
+.critical { background: #7f1d1d; } .high { background: #dc2626; } .medium { background: #d97706; } .low { background: #6b7280; }
+.notice { border-left: 4px solid #dc2626; background: #fef2f2; padding: 8px 12px; margin-top: 12px; }
+.test { border-top: 1px solid #e5e7eb; }
+.test:first-of-type { border-top: none; }
+.muted { color: #6b7280; }
+ul.deductions { margin: 4px 0; padding-left: 20px; font-size: 14px; }
+svg.chart { width: 100%; height: auto; font-size: 11px; }
+svg.chart .grid { stroke: #e5e7eb; }
+svg.chart .axis { fill: #6b7280; }
+svg.chart polyline { fill: none; stroke-width: 1.5; }
+.off { opacity: .15; }
+.legend button { border: 1px solid #d1d5db; background: #fff; border-radius: 4px; margin: 2px; padding: 2px 8px; cursor: pointer; font-size: 12px; }
+.legend .swatch { display: inline-block; width: 10px; height: 10px; border-radius: 2px; margin-right: 4px; }
+footer { color: #9ca3af; font-size: 12px; text-align: center; margin: 20px 0; }
+@media print { body { background: #fff; } section { box-shadow: none; } .legend button { display: none; } }
+"#;
+
+/// Clicking a legend entry hides or shows its series.
+const SCRIPT: &str = r#"
+document.querySelectorAll('.legend button').forEach(function (button) {
+  button.addEventListener('click', function () {
+    button.classList.toggle('off');
+    document.getElementById(button.dataset.line).classList.toggle('off');
+  });
+});
+"#;
+
+/// A reporter that writes the suite as a self-contained HTML page for
+/// hand-offs, with no external resources.
+pub struct HtmlReporter {
+    output_file: Option<String>,
+}
+
+impl HtmlReporter {
+    /// Creates a new `HtmlReporter` instance.
+    pub fn new(output_file: Option<String>) -> Self {
+        Self { output_file }
+    }
+
+    /// Renders the suite and the hardware it ran on as an HTML page.
+    pub fn render(suite: &TestSuite, hardware: &HardwareInfo) -> String {
+        let hostname = &hardware.system_info.hostname;
+        let mut html = String::new();
+        let _ = write!(
+            html,
+            "<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n<meta name=\"viewport\" content=\"width=device-width, initial-scale=1\">\n<title>Burn-in report: {}</title>\n<style>{}</style>\n</head>\n<body>\n<main>\n",
+            escape(hostname), STYLE,
+        );
+        let _ = writeln!(
+            html,
+            "<header><h1>Burn-in report: {}</h1><p>Started {} UTC</p></header>",
+            escape(hostname),
+            suite.start_time.format("%Y-%m-%d %H:%M:%S"),
+        );
+
+        write_summary(&mut html, suite);
+        write_hardware(&mut html, hardware);
+
+        let mut charts = 0;
+        html.push_str("<section>\n<h2>Tests</h2>\n");
+        for result in &suite.results {
+            write_test(&mut html, result, &mut charts);
+        }
+        html.push_str("</section>\n");
+
+        write_issues(&mut html, suite);
+
+        let _ = write!(
+            html,
+            "<footer>Generated by burnin {} at {} UTC</footer>\n</main>\n<script>{}</script>\n</body>\n</html>\n",
+            env!("CARGO_PKG_VERSION"),
+            chrono::Utc::now().format("%Y-%m-%d %H:%M:%S"),
+            SCRIPT,
+        );
+        html
+    }
+}
+
+fn write_summary(html: &mut String, suite: &TestSuite) {
+    let passed = suite.results.iter().filter(|r| r.status == TestStatus::Completed).count();
+    let critical = suite.results.iter()
+        .flat_map(|r| &r.issues)
+        .filter(|i| i.severity == IssueSeverity::Critical)
+        .count();
+
+    html.push_str("<section>\n<h2>Summary</h2>\n<div class=\"cards\">\n");
+    let cards = [
+        ("Result", status_badge(suite.overall_status)),
+        ("Score", format!("{}/100", suite.overall_score)),
+        ("Duration", format_duration(suite.duration.as_secs())),
+        ("Tests passed", format!("{}/{}", passed, suite.results.len())),
+        ("Critical issues", critical.to_string()),
+    ];
+    for (label, value) in cards {
+        let _ = writeln!(html, "<div class=\"card\"><div class=\"label\">{}</div><div class=\"value\">{}</div></div>", label, value);
+    }
+    html.push_str("</div>\n");
+
+    if let Some(reason) = &suite.aborted {
+        let _ = writeln!(html, "<div class=\"notice\">Run stopped early: {}</div>", escape(&reason.to_string()));
+    }
+
+    if let Some(policy) = &suite.policy {
+        let _ = writeln!(
+            html,
+            "<h3>Acceptance policy {}</h3>\n<p class=\"muted\">{}/{} rules passed</p>",
+            if policy.passed { "<span class=\"badge pass\">PASS</span>" } else { "<span class=\"badge fail\">FAIL</span>" },
+            policy.passed_count(),
+            policy.verdicts.len(),
+        );
+        html.push_str("<table>\n<tr><th>Rule</th><th>Description</th><th>Actual</th><th>Verdict</th></tr>\n");
+        for verdict in &policy.verdicts {
+            let class = match verdict.verdict {
+                Verdict::Pass => "pass",
+                Verdict::Fail => "fail",
+                Verdict::Missing => "skipped",
+            };
+            let _ = writeln!(
+                html,
+                "<tr><td><code>{}</code></td><td>{}</td><td class=\"num\">{}</td><td><span class=\"badge {}\">{}</span></td></tr>",
+                escape(&verdict.check),
+                escape(verdict.description.as_deref().unwrap_or("")),
+                verdict.actual.map_or_else(|| "not reported".to_string(), format_number),
+                class,
+                verdict.verdict,
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    if !suite.adjustments.is_empty() {
+        html.push_str("<h3>Adaptive adjustments</h3>\n<table>\n<tr><th>Parameter</th><th>From</th><th>To</th><th>Reason</th></tr>\n");
+        for adjustment in &suite.adjustments {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                escape(&adjustment.parameter), escape(&adjustment.from), escape(&adjustment.to), escape(&adjustment.reason),
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</section>\n");
+}
+
+fn write_hardware(html: &mut String, hardware: &HardwareInfo) {
+    const GIB: f64 = 1024.0 * 1024.0 * 1024.0;
+    let system = &hardware.system_info;
+    let cpu = &hardware.cpu_info;
+    let memory = &hardware.memory_info;
+    let dmi = &hardware.dmi;
+
+    let mut rows: Vec<(&str, String)> = Vec::new();
+    if let Some(machine) = dmi.machine() {
+        rows.push(("Machine", machine));
+    }
+    if let Some(serial) = &dmi.system_serial {
+        rows.push(("Serial number", serial.clone()));
+    }
+    if let (Some(vendor), Some(product)) = (&dmi.board_vendor, &dmi.board_product) {
+        rows.push(("Board", format!("{} {}", vendor, product)));
+    }
+    if let Some(version) = &dmi.bios_version {
+        rows.push(("BIOS", match &dmi.bios_date {
+            Some(date) => format!("{} ({})", version, date),
+            None => version.clone(),
+        }));
+    }
+    rows.push(("Operating system", format!("{} {}", system.os_name, system.os_version)));
+    rows.push(("Kernel", system.kernel_version.clone()));
+    if let Some(virt) = &hardware.virtualization {
+        rows.push(("Virtualization", format!("{:?}", virt)));
+    }
+    rows.push(("CPU", format!("{} ({} cores, {} threads, {:.0} MHz)", cpu.model_name, cpu.physical_cores, cpu.logical_cores, cpu.frequency_mhz)));
+    rows.push(("Memory", format!("{:.1} GiB", memory.total_bytes as f64 / GIB)));
+    if let Some(ecc) = memory.ecc_enabled {
+        rows.push(("ECC", if ecc { "enabled" } else { "disabled" }.to_string()));
+    }
+    for module in &memory.modules {
+        let details = [module.memory_type.clone(), module.speed_mhz.map(|mhz| format!("{} MT/s", mhz)), module.manufacturer.clone(), module.part_number.clone()]
+            .into_iter()
+            .flatten()
+            .collect::<Vec<_>>()
+            .join(", ");
+        rows.push(("Memory module", format!("{}: {:.0} GiB {}", module.locator, module.size_bytes as f64 / GIB, details)));
+    }
+
+    html.push_str("<section>\n<h2>Hardware</h2>\n<table>\n");
+    for (label, value) in rows {
+        let _ = writeln!(html, "<tr><th>{}</th><td>{}</td></tr>", label, escape(&value));
+    }
+    html.push_str("</table>\n");
+
+    if !hardware.storage_devices.is_empty() {
+        html.push_str("<h3>Storage</h3>\n<table>\n<tr><th>Device</th><th>Model</th><th>Type</th><th>Size</th><th>Mount point</th></tr>\n");
+        for device in &hardware.storage_devices {
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{:?}</td><td class=\"num\">{:.1} GiB</td><td>{}</td></tr>",
+                escape(&device.name), escape(&device.model), device.device_type, device.size_bytes as f64 / GIB,
+                escape(device.mount_point.as_deref().unwrap_or("")),
+            );
+        }
+        html.push_str("</table>\n");
+    }
+
+    let links: Vec<_> = hardware.pci_devices.iter().filter(|d| d.current_link_speed_gts.is_some()).collect();
+    if !links.is_empty() {
+        html.push_str("<h3>PCIe links</h3>\n<table>\n<tr><th>Address</th><th>Driver</th><th>Link</th><th>Capable of</th></tr>\n");
+        for device in links {
+            let link = |speed: Option<f32>, width: Option<u32>| format!(
+                "{} GT/s x{}",
+                speed.map_or_else(|| "?".to_string(), |s| s.to_string()),
+                width.map_or_else(|| "?".to_string(), |w| w.to_string()),
+            );
+            let _ = writeln!(
+                html,
+                "<tr><td>{}</td><td>{}</td><td>{}{}</td><td>{}</td></tr>",
+                escape(&device.address),
+                escape(device.driver.as_deref().unwrap_or("")),
+                link(device.current_link_speed_gts, device.current_link_width),
+                if device.is_downtrained() { " <span class=\"badge medium\">DOWNTRAINED</span>" } else { "" },
+                link(device.max_link_speed_gts, device.max_link_width),
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</section>\n");
+}
+
+fn write_test(html: &mut String, result: &TestResult, charts: &mut usize) {
+    let _ = writeln!(
+        html,
+        "<div class=\"test\">\n<h3>{} {} <span class=\"muted\">score {}/100, {}</span></h3>",
+        escape(&result.name),
+        status_badge(result.status),
+        result.score,
+        format_duration(result.duration.as_secs()),
+    );
+
+    if !result.deductions.is_empty() {
+        html.push_str("<ul class=\"deductions\">\n");
+        for deduction in &result.deductions {
+            let _ = writeln!(html, "<li>&minus;{} {}</li>", deduction.points, escape(&deduction.reason));
+        }
+        html.push_str("</ul>\n");
+    }
+
+    let mut metrics = Vec::new();
+    flatten_metrics(&result.metrics, String::new(), &mut metrics);
+    if !metrics.is_empty() {
+        html.push_str("<h4>Metrics</h4>\n<table>\n");
+        for (name, value) in metrics {
+            let _ = writeln!(html, "<tr><td>{}</td><td class=\"num\">{}</td></tr>", escape(&name), escape(&value));
+        }
+        html.push_str("</table>\n");
+    }
+
+    for (metric, title, unit) in CHARTS {
+        let series = parse_series(&result.metrics[metric]);
+        if series.iter().any(|(_, points)| !points.is_empty()) {
+            *charts += 1;
+            let _ = writeln!(html, "<h4>{} ({})</h4>", title, unit);
+            html.push_str(&line_chart(*charts, &series));
+        }
+    }
+    html.push_str("</div>\n");
+}
+
+fn write_issues(html: &mut String, suite: &TestSuite) {
+    let mut issues: Vec<_> = suite.results.iter()
+        .flat_map(|result| result.issues.iter().map(move |issue| (result, issue)))
+        .collect();
+    issues.sort_by_key(|(_, issue)| std::cmp::Reverse(issue.severity));
+
+    html.push_str("<section>\n<h2>Issues</h2>\n");
+    if issues.is_empty() {
+        html.push_str("<p class=\"muted\">No issues were reported.</p>\n");
+    } else {
+        html.push_str("<table>\n<tr><th>Severity</th><th>Test</th><th>Component</th><th>Issue</th><th>Recommended action</th></tr>\n");
+        for (result, issue) in issues {
+            let severity = format!("{:?}", issue.severity);
+            let _ = writeln!(
+                html,
+                "<tr><td><span class=\"badge {}\">{}</span></td><td>{}</td><td>{}</td><td>{}</td><td>{}</td></tr>",
+                severity.to_lowercase(),
+                severity.to_uppercase(),
+                escape(&result.name),
+                escape(&issue.component),
+                escape(&issue.message),
+                escape(issue.action.as_deref().unwrap_or("")),
+            );
+        }
+        html.push_str("</table>\n");
+    }
+    html.push_str("</section>\n");
+}
+
+/// Reads a time-series metric: an object of series names mapped to
+/// `[seconds, value]` pairs.
+fn parse_series(value: &Value) -> Vec<(String, Vec<(f64, f64)>)> {
+    let Value::Object(map) = value else {
+        return Vec::new();
+    };
+    map.iter()
+        .map(|(name, points)| {
+            let points = points.as_array()
+                .map(|points| points.iter()
+                    .filter_map(|point| Some((point.get(0)?.as_f64()?, point.get(1)?.as_f64()?)))
+                    .collect())
+                .unwrap_or_default();
+            (name.clone(), points)
+        })
+        .collect()
+}
+
+/// Keeps at most `MAX_POINTS` points, taking the lowest and highest value
+/// of each stretch in time order.
+fn downsample(points: &[(f64, f64)]) -> Vec<(f64, f64)> {
+    if points.len() <= MAX_POINTS {
+        return points.to_vec();
+    }
+    let stretch = points.len().div_ceil(MAX_POINTS / 2);
+    points.chunks(stretch)
+        .flat_map(|chunk| {
+            let low = chunk.iter().enumerate().min_by(|a, b| a.1.1.total_cmp(&b.1.1)).unwrap();
+            let high = chunk.iter().enumerate().max_by(|a, b| a.1.1.total_cmp(&b.1.1)).unwrap();
+            if low.0 <= high.0 { [*low.1, *high.1] } else { [*high.1, *low.1] }
+        })
+        .collect()
+}
+
+/// Draws the series as an inline SVG line chart with a clickable legend.
+fn line_chart(id: usize, series: &[(String, Vec<(f64, f64)>)]) -> String {
+    const WIDTH: f64 = 760.0;
+    const HEIGHT: f64 = 260.0;
+    const LEFT: f64 = 56.0;
+    const RIGHT: f64 = 16.0;
+    const TOP: f64 = 12.0;
+    const BOTTOM: f64 = 28.0;
+    const TICKS: usize = 5;
+
+    let all = series.iter().flat_map(|(_, points)| points);
+    let (mut x_min, mut x_max, mut y_min, mut y_max) = (f64::MAX, f64::MIN, f64::MAX, f64::MIN);
+    for &(x, y) in all {
+        x_min = x_min.min(x);
+        x_max = x_max.max(x);
+        y_min = y_min.min(y);
+        y_max = y_max.max(y);
+    }
+    if x_max <= x_min {
+        x_max = x_min + 1.0;
+    }
+    let padding = ((y_max - y_min) * 0.05).max(0.5);
+    y_min -= padding;
+    y_max += padding;
+
+    let plot_width = WIDTH - LEFT - RIGHT;
+    let plot_height = HEIGHT - TOP - BOTTOM;
+    let x_pos = |x: f64| LEFT + (x - x_min) / (x_max - x_min) * plot_width;
+    let y_pos = |y: f64| TOP + (y_max - y) / (y_max - y_min) * plot_height;
+
+    let mut svg = String::new();
+    let _ = writeln!(svg, "<svg class=\"chart\" viewBox=\"0 0 {} {}\" role=\"img\">", WIDTH, HEIGHT);
+    for tick in 0..=TICKS {
+        let y = y_min + (y_max - y_min) * tick as f64 / TICKS as f64;
+        let x = x_min + (x_max - x_min) * tick as f64 / TICKS as f64;
+        let _ = writeln!(
+            svg,
+            "<line class=\"grid\" x1=\"{:.1}\" y1=\"{:.1}\" x2=\"{:.1}\" y2=\"{:.1}\"/><text class=\"axis\" x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"end\">{}</text>",
+            LEFT, y_pos(y), WIDTH - RIGHT, y_pos(y), LEFT - 6.0, y_pos(y) + 4.0,
+            if y_max - y_min >= 10.0 { format!("{:.0}", y) } else { format!("{:.1}", y) },
+        );
+        let _ = writeln!(
+            svg,
+            "<text class=\"axis\" x=\"{:.1}\" y=\"{:.1}\" text-anchor=\"middle\">{}</text>",
+            x_pos(x), HEIGHT - 8.0, format_elapsed(x),
+        );
+    }
+
+    let mut legend = String::from("<div class=\"legend\">");
+    for (index, (name, points)) in series.iter().enumerate() {
+        if points.is_empty() {
+            continue;
+        }
+        let color = PALETTE[index % PALETTE.len()];
+        let line = format!("chart{}-{}", id, index);
+        let coordinates: Vec<String> = downsample(points).iter()
+            .map(|&(x, y)| format!("{:.1},{:.1}", x_pos(x), y_pos(y)))
+            .collect();
+        let _ = writeln!(
+            svg,
+            "<polyline id=\"{}\" stroke=\"{}\" points=\"{}\"><title>{}</title></polyline>",
+            line, color, coordinates.join(" "), escape(name),
+        );
+        let _ = write!(
+            legend,
+            "<button type=\"button\" data-line=\"{}\"><span class=\"swatch\" style=\"background:{}\"></span>{}</button>",
+            line, color, escape(name),
+        );
+    }
+    svg.push_str("</svg>\n");
+    legend.push_str("</div>\n");
+    svg + &legend
+}
+
+/// Flattens metrics into dotted names, leaving out the time series that are
+/// charted instead. Entries of arrays of objects are named after their
+/// `name` field when they have one.
+fn flatten_metrics(value: &Value, prefix: String, rows: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                if prefix.is_empty() && CHARTS.iter().any(|(metric, _, _)| metric == key) {
+                    continue;
+                }
+                let name = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten_metrics(value, name, rows);
+            }
+        }
+        Value::Array(items) if items.iter().any(Value::is_object) => {
+            for (index, item) in items.iter().enumerate() {
+                let label = item.get("name").and_then(Value::as_str).map_or_else(|| index.to_string(), str::to_string);
+                flatten_metrics(item, format!("{}[{}]", prefix, label), rows);
+            }
+        }
+        Value::Array(items) => {
+            let items: Vec<String> = items.iter().map(format_value).collect();
+            rows.push((prefix, items.join(", ")));
+        }
+        Value::Null => {}
+        _ if prefix.ends_with("_bytes") && value.is_u64() => {
+            let bytes = value.as_u64().unwrap_or(0);
+            rows.push((prefix, format!("{} ({:.1} MiB)", bytes, bytes as f64 / 1024.0 / 1024.0)));
+        }
+        _ => {
+            let row = format_value(value);
+            rows.push((prefix, row));
+        }
+    }
+}
+
+fn format_value(value: &Value) -> String {
+    match value {
+        Value::String(s) => s.clone(),
+        Value::Bool(b) => if *b { "yes" } else { "no" }.to_string(),
+        Value::Number(n) => n.as_f64().map_or_else(|| n.to_string(), format_number),
+        other => other.to_string(),
+    }
+}
+
+fn format_number(n: f64) -> String {
+    if n.fract() == 0.0 && n.abs() < 1e15 {
+        format!("{}", n as i64)
+    } else {
+        format!("{:.2}", n)
+    }
+}
+
+fn format_duration(total_secs: u64) -> String {
+    let hours = total_secs / 3600;
+    let minutes = (total_secs % 3600) / 60;
+    let seconds = total_secs % 60;
+
+    if hours > 0 {
+        format!("{}h {}m {}s", hours, minutes, seconds)
+    } else if minutes > 0 {
+        format!("{}m {}s", minutes, seconds)
+    } else {
+        format!("{}s", seconds)
+    }
+}
+
+/// Formats an axis label in seconds since the start of the test.
+fn format_elapsed(secs: f64) -> String {
+    let secs = secs.max(0.0).round() as u64;
+    if secs >= 3600 {
+        format!("{}h{:02}m", secs / 3600, (secs % 3600) / 60)
+    } else {
+        format!("{}:{:02}", secs / 60, secs % 60)
+    }
+}
+
+fn status_badge(status: TestStatus) -> String {
+    let (class, label) = match status {
+        TestStatus::Completed => ("pass", "PASS"),
+        TestStatus::Failed => ("fail", "FAIL"),
+        TestStatus::Partial => ("partial", "PARTIAL"),
+        TestStatus::Skipped => ("skipped", "SKIPPED"),
+        TestStatus::Pending => ("skipped", "PENDING"),
+        TestStatus::Running => ("skipped", "RUNNING"),
+    };
+    format!("<span class=\"badge {}\">{}</span>", class, label)
+}
+
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl Reporter for HtmlReporter {
+    fn report_start(&self, _config: &TestConfig) {
+        // No-op
+    }
+
+    fn report_test_start(&self, _test_name: &str) {
+        // No-op
+    }
+
+    fn report_test_result(&self, _result: &TestResult) {
+        // No-op
+    }
+
+    fn report_suite_result(&self, suite: &TestSuite) {
+        let html = Self::render(suite, &hardware::detect());
+        match &self.output_file {
+            Some(path) => {
+                if let Err(e) = fs::write(path, html) {
+                    eprintln!("Failed to write HTML report to {}: {}", path, e);
+                }
+            }
+            None => print!("{}", html),
+        }
+    }
+
+    fn report_warning(&self, _message: &str) {
+        // No-op
+    }
+
+    fn report_info(&self, _message: &str) {
+        // No-op
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use serde_json::json;
+    use crate::core::test::TestIssue;
+
+    #[test]
+    fn test_render_html() {
+        let series: Vec<Value> = (0..2000).map(|i| json!([i as f64 * 5.0, 40.0 + (i % 50) as f64])).collect();
+        let mut suite = TestSuite::new();
+        suite.results = vec![TestResult {
+            name: "thermal_monitor".to_string(),
+            status: TestStatus::Completed,
+            score: 90,
+            duration: Duration::from_secs(10_000),
+            metrics: json!({
+                "max_temperature_celsius": 89.0,
+                "sensors": [{ "name": "Package id 0", "readings": 2000 }],
+                "series": { "Package id 0": series, "nvme <0>": [[0.0, 35.0], [5.0, 36.5]] },
+            }),
+            issues: vec![TestIssue {
+                component: "thermal".to_string(),
+                severity: IssueSeverity::High,
+                message: "Temperature > 85°C".to_string(),
+                action: Some("Check the heatsink & fans".to_string()),
+            }],
+            deductions: Vec::new(),
+        }];
+        suite.finalize();
+
+        let html = HtmlReporter::render(&suite, &hardware::detect());
+        assert!(html.starts_with("<!DOCTYPE html>"));
+        assert!(html.contains("<td>sensors[Package id 0].readings</td><td class=\"num\">2000</td>"));
+        assert!(!html.contains("<td>series"));
+        assert!(html.contains("<h4>Temperature (°C)</h4>"));
+        assert!(html.contains("<polyline id=\"chart1-0\""));
+        assert!(html.contains("<title>nvme &lt;0&gt;</title>"));
+        assert!(html.contains("<td>Temperature &gt; 85°C</td><td>Check the heatsink &amp; fans</td>"));
+        assert!(!html.contains(" src=") && !html.contains("<link"));
+
+        let points = html.split("points=\"").nth(1).unwrap().split('"').next().unwrap();
+        assert_eq!(points.split(' ').count(), MAX_POINTS);
+    }
+}
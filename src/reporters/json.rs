@@ -1,11 +1,11 @@
 use std::io::{self, Write};
 use std::fs::File;
 use serde_json::{json, Value};
-use sysinfo::System;
 
 use crate::core::test::{TestResult, TestStatus};
 use crate::core::config::TestConfig;
 use crate::core::runner::TestSuite;
+use crate::core::hardware;
 use crate::reporters::Reporter;
 
 
@@ -48,6 +48,13 @@ impl JsonReporter {
         
         Ok(())
     }
+    
+    
+    /// Writes a verbose event as one line to stderr, keeping stdout a single
+    /// JSON document.
+    fn write_event(&self, event: Value) {
+        eprintln!("{}", event);
+    }
 }
 
 impl Reporter for JsonReporter {
@@ -73,7 +80,7 @@ impl Reporter for JsonReporter {
             
             if self.output_file.is_none() {
                 
-                let _ = self.write_json(start_info);
+                self.write_event(start_info);
             }
         }
     }
@@ -88,7 +95,7 @@ impl Reporter for JsonReporter {
             
             if self.output_file.is_none() {
                 
-                let _ = self.write_json(test_start);
+                self.write_event(test_start);
             }
         }
     }
@@ -101,6 +108,7 @@ impl Reporter for JsonReporter {
                 "test_name": result.name,
                 "status": Self::status_to_string(result.status),
                 "score": result.score,
+                "deductions": result.deductions,
                 "duration_seconds": result.duration.as_secs(),
                 "metrics": result.metrics,
                 "issues": result.issues,
@@ -108,7 +116,7 @@ impl Reporter for JsonReporter {
             
             if self.output_file.is_none() {
                 
-                let _ = self.write_json(test_result);
+                self.write_event(test_result);
             }
         }
     }
@@ -121,6 +129,7 @@ impl Reporter for JsonReporter {
                     "name": result.name,
                     "result": Self::status_to_string(result.status),
                     "score": result.score,
+                    "deductions": result.deductions,
                     "duration_seconds": result.duration.as_secs(),
                     "metrics": result.metrics,
                     "issues": result.issues.iter().map(|issue| {
@@ -136,11 +145,7 @@ impl Reporter for JsonReporter {
             .collect();
         
         
-        let hostname = std::env::var("HOSTNAME").unwrap_or_else(|_| "unknown".to_string());
-        
-        
-        let mut system = System::new_all();
-        system.refresh_all();
+        let hardware = hardware::detect();
         
         
         let recommendations: Vec<Value> = suite.results.iter()
@@ -164,13 +169,21 @@ impl Reporter for JsonReporter {
                     end.signed_duration_since(suite.start_time).num_seconds() as u64
                 }),
                 "timestamp": suite.start_time.to_rfc3339(),
+                "aborted": suite.aborted.as_ref().map(|reason| reason.to_string()),
+                "adjustments": suite.adjustments,
+                "policy": suite.policy.as_ref().map(|policy| json!({
+                    "result": if policy.passed { "PASS" } else { "FAIL" },
+                    "rules": policy.verdicts,
+                })),
                 "system_info": {
-                    "hostname": hostname,
-                    "os": format!("{} {}", System::name().unwrap_or_else(|| "Unknown".to_string()), 
-                                   System::os_version().unwrap_or_else(|| "Unknown".to_string())),
-                    "cpu": system.global_cpu_info().brand().to_string(),
-                    "memory_gb": system.total_memory() / 1024 / 1024,
-                    "virtualization": "Unknown", 
+                    "hostname": hardware.system_info.hostname,
+                    "os": format!("{} {}", hardware.system_info.os_name, hardware.system_info.os_version),
+                    "cpu": hardware.cpu_info.model_name,
+                    "memory_gb": hardware.memory_info.total_bytes / 1024 / 1024 / 1024,
+                    "virtualization": hardware.virtualization
+                        .map_or_else(|| "Unknown".to_string(), |virt| format!("{:?}", virt)),
+                    "machine": hardware.dmi,
+                    "memory_modules": hardware.memory_info.modules,
                 }
             },
             "tests": test_results,
@@ -193,7 +206,7 @@ impl Reporter for JsonReporter {
             
             if self.output_file.is_none() {
                 
-                let _ = self.write_json(warning);
+                self.write_event(warning);
             }
         }
     }
@@ -208,7 +221,7 @@ impl Reporter for JsonReporter {
             
             if self.output_file.is_none() {
                 
-                let _ = self.write_json(info);
+                self.write_event(info);
             }
         }
     }
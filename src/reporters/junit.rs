@@ -0,0 +1,330 @@
+use std::fmt::Write as _;
+use std::fs;
+use serde_json::Value;
+
+use crate::core::test::{TestResult, TestStatus, IssueSeverity};
+use crate::core::config::TestConfig;
+use crate::core::runner::TestSuite;
+use crate::core::policy::Verdict;
+use crate::core::hardware;
+use crate::reporters::Reporter;
+
+/// A reporter that outputs the suite as JUnit XML for CI systems.
+///
+/// Every test becomes a testcase with its score and metrics as properties.
+/// A test fails if it failed, was cut short or reported a critical issue,
+/// and is skipped if it was skipped. Each acceptance policy rule becomes a
+/// testcase of its own, and a failed run with no failing testcase gets one
+/// for the suite so that CI never reads it as green.
+pub struct JunitReporter {
+    output_file: Option<String>,
+}
+
+impl JunitReporter {
+    /// Creates a new `JunitReporter` instance.
+    pub fn new(output_file: Option<String>) -> Self {
+        Self { output_file }
+    }
+
+    /// Renders the suite as a JUnit XML document.
+    pub fn render(suite: &TestSuite) -> String {
+        let mut cases = String::new();
+        let (mut tests, mut failures, mut skipped) = (0, 0, 0);
+
+        for result in &suite.results {
+            tests += 1;
+            match Self::outcome(result) {
+                Outcome::Failure => failures += 1,
+                Outcome::Skipped => skipped += 1,
+                Outcome::Passed => {}
+            }
+            Self::write_testcase(&mut cases, result);
+        }
+
+        if let Some(policy) = &suite.policy {
+            for verdict in &policy.verdicts {
+                tests += 1;
+                let name = escape(&verdict.check);
+                let actual = verdict.actual.map_or_else(|| "not reported".to_string(), |actual| format!("actual {}", actual));
+                let _ = writeln!(cases, "    <testcase name=\"{}\" classname=\"burnin.policy\" time=\"0\">", name);
+                if verdict.verdict != Verdict::Pass {
+                    failures += 1;
+                    let message = match &verdict.description {
+                        Some(description) => format!("{}: {} ({})", description, verdict.check, actual),
+                        None => format!("{} ({})", verdict.check, actual),
+                    };
+                    let _ = writeln!(cases, "      <failure message=\"{}\" type=\"{}\"/>", escape(&message), verdict.verdict);
+                }
+                cases.push_str("    </testcase>\n");
+            }
+        }
+
+        if suite.overall_status == TestStatus::Failed && failures == 0 {
+            tests += 1;
+            failures += 1;
+            let message = match &suite.aborted {
+                Some(reason) => format!("Run stopped early: {}", reason),
+                None => "Run failed".to_string(),
+            };
+            cases.push_str("    <testcase name=\"overall\" classname=\"burnin.suite\" time=\"0\">\n");
+            let _ = writeln!(cases, "      <failure message=\"{}\" type=\"failed\"/>", escape(&message));
+            cases.push_str("    </testcase>\n");
+        }
+
+        let mut properties = vec![
+            ("overall_score".to_string(), suite.overall_score.to_string()),
+            ("overall_status".to_string(), format!("{:?}", suite.overall_status)),
+        ];
+        if let Some(reason) = &suite.aborted {
+            properties.push(("aborted".to_string(), reason.to_string()));
+        }
+        let dmi = hardware::detect().dmi;
+        if let Some(machine) = dmi.machine() {
+            properties.push(("machine".to_string(), machine));
+        }
+        if let Some(serial) = dmi.system_serial {
+            properties.push(("serial_number".to_string(), serial));
+        }
+        if let Some(policy) = &suite.policy {
+            properties.push(("policy".to_string(), if policy.passed { "PASS" } else { "FAIL" }.to_string()));
+        }
+
+        let hostname = suite.system_info.as_ref().map_or("unknown", |info| info.hostname.as_str());
+        let timestamp = suite.start_time.format("%Y-%m-%dT%H:%M:%S");
+        let time = suite.duration.as_secs_f64();
+
+        let mut xml = String::from("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n");
+        let _ = writeln!(
+            xml,
+            "<testsuites name=\"burnin\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\" timestamp=\"{}\">",
+            tests, failures, skipped, time, timestamp,
+        );
+        let _ = writeln!(
+            xml,
+            "  <testsuite name=\"burnin\" hostname=\"{}\" tests=\"{}\" failures=\"{}\" errors=\"0\" skipped=\"{}\" time=\"{:.3}\" timestamp=\"{}\">",
+            escape(hostname), tests, failures, skipped, time, timestamp,
+        );
+        write_properties(&mut xml, "    ", &properties);
+        xml.push_str(&cases);
+        xml.push_str("  </testsuite>\n</testsuites>\n");
+        xml
+    }
+
+    fn outcome(result: &TestResult) -> Outcome {
+        if matches!(result.status, TestStatus::Failed | TestStatus::Partial)
+            || result.issues.iter().any(|i| i.severity == IssueSeverity::Critical)
+        {
+            Outcome::Failure
+        } else if result.status == TestStatus::Skipped {
+            Outcome::Skipped
+        } else {
+            Outcome::Passed
+        }
+    }
+
+    fn write_testcase(xml: &mut String, result: &TestResult) {
+        let _ = writeln!(
+            xml,
+            "    <testcase name=\"{}\" classname=\"burnin.{}\" time=\"{:.3}\">",
+            escape(&result.name), escape(&result.name), result.duration.as_secs_f64(),
+        );
+
+        let mut properties = vec![("score".to_string(), result.score.to_string())];
+        flatten_metrics(&result.metrics, String::new(), &mut properties);
+        write_properties(xml, "      ", &properties);
+
+        // The most severe issue names the failure or skip; every issue is
+        // listed in its body.
+        let worst = result.issues.iter().max_by_key(|issue| issue.severity);
+        let details: String = result.issues.iter()
+            .map(|issue| match &issue.action {
+                Some(action) => format!("[{:?}] {}: {} ({})\n", issue.severity, issue.component, issue.message, action),
+                None => format!("[{:?}] {}: {}\n", issue.severity, issue.component, issue.message),
+            })
+            .collect();
+
+        match Self::outcome(result) {
+            Outcome::Failure => {
+                let message = worst.map_or_else(|| format!("Test {:?}", result.status), |issue| issue.message.clone());
+                let kind = worst.map_or_else(|| "failed".to_string(), |issue| format!("{:?}", issue.severity).to_lowercase());
+                let _ = writeln!(xml, "      <failure message=\"{}\" type=\"{}\">{}</failure>", escape(&message), kind, escape(&details));
+            }
+            Outcome::Skipped => {
+                let message = worst.map_or_else(|| format!("Test {:?}", result.status), |issue| issue.message.clone());
+                let _ = writeln!(xml, "      <skipped message=\"{}\"/>", escape(&message));
+                if !details.is_empty() {
+                    let _ = writeln!(xml, "      <system-out>{}</system-out>", escape(&details));
+                }
+            }
+            Outcome::Passed => {
+                if !details.is_empty() {
+                    let _ = writeln!(xml, "      <system-out>{}</system-out>", escape(&details));
+                }
+            }
+        }
+
+        xml.push_str("    </testcase>\n");
+    }
+}
+
+enum Outcome {
+    Passed,
+    Failure,
+    Skipped,
+}
+
+/// Flattens nested metric objects into dotted property names. Arrays are
+/// kept as JSON.
+fn flatten_metrics(value: &Value, prefix: String, properties: &mut Vec<(String, String)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let name = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten_metrics(value, name, properties);
+            }
+        }
+        Value::Null => {}
+        Value::String(s) if !prefix.is_empty() => properties.push((prefix, s.clone())),
+        _ if !prefix.is_empty() => properties.push((prefix, value.to_string())),
+        _ => {}
+    }
+}
+
+fn write_properties(xml: &mut String, indent: &str, properties: &[(String, String)]) {
+    if properties.is_empty() {
+        return;
+    }
+    let _ = writeln!(xml, "{}<properties>", indent);
+    for (name, value) in properties {
+        let _ = writeln!(xml, "{}  <property name=\"{}\" value=\"{}\"/>", indent, escape(name), escape(value));
+    }
+    let _ = writeln!(xml, "{}</properties>", indent);
+}
+
+/// Escapes text for use in XML attributes and content, dropping control
+/// characters XML 1.0 cannot represent.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\t' | '\r' => escaped.push(c),
+            c if (c as u32) < 0x20 => {}
+            c => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+impl Reporter for JunitReporter {
+    fn report_start(&self, _config: &TestConfig) {
+        // No-op
+    }
+
+    fn report_test_start(&self, _test_name: &str) {
+        // No-op
+    }
+
+    fn report_test_result(&self, _result: &TestResult) {
+        // No-op
+    }
+
+    fn report_suite_result(&self, suite: &TestSuite) {
+        let xml = Self::render(suite);
+        match &self.output_file {
+            Some(path) => {
+                if let Err(e) = fs::write(path, xml) {
+                    eprintln!("Failed to write JUnit report to {}: {}", path, e);
+                }
+            }
+            None => print!("{}", xml),
+        }
+    }
+
+    fn report_warning(&self, _message: &str) {
+        // No-op
+    }
+
+    fn report_info(&self, _message: &str) {
+        // No-op
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+    use serde_json::json;
+    use crate::core::policy::Policy;
+    use crate::core::test::TestIssue;
+
+    fn result(name: &str, status: TestStatus, metrics: Value, issues: Vec<TestIssue>) -> TestResult {
+        TestResult {
+            name: name.to_string(),
+            status,
+            score: 80,
+            duration: Duration::from_millis(1500),
+            metrics,
+            issues,
+            deductions: Vec::new(),
+        }
+    }
+
+    fn issue(severity: IssueSeverity, message: &str) -> TestIssue {
+        TestIssue {
+            component: "storage".to_string(),
+            severity,
+            message: message.to_string(),
+            action: None,
+        }
+    }
+
+    #[test]
+    fn test_render_junit() {
+        let mut suite = TestSuite::new();
+        suite.results = vec![
+            result("cpu_stress", TestStatus::Completed, json!({ "gflops": 12.5, "simd": { "avx2": true } }), Vec::new()),
+            result("storage_io", TestStatus::Completed, json!({}), vec![
+                issue(IssueSeverity::Medium, "Latency <high> & rising"),
+                issue(IssueSeverity::Critical, "5 I/O errors on \"nvme0n1\""),
+            ]),
+            result("network", TestStatus::Skipped, json!({}), vec![issue(IssueSeverity::Low, "No endpoints\u{7}")]),
+        ];
+        suite.finalize();
+        suite.apply_policy(&Policy::from_toml("[[rule]]\ncheck = \"cpu.gflops >= 10\"\n\n[[rule]]\ncheck = \"overall_score >= 95\"\n").unwrap());
+
+        let xml = JunitReporter::render(&suite);
+        assert!(xml.starts_with("<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<testsuites name=\"burnin\" tests=\"5\" failures=\"2\" errors=\"0\" skipped=\"1\""));
+        assert!(xml.contains("<testcase name=\"cpu_stress\" classname=\"burnin.cpu_stress\" time=\"1.500\">"));
+        assert!(xml.contains("<property name=\"simd.avx2\" value=\"true\"/>"));
+        assert!(xml.contains("<property name=\"gflops\" value=\"12.5\"/>"));
+        assert!(xml.contains("<failure message=\"5 I/O errors on &quot;nvme0n1&quot;\" type=\"critical\">[Medium] storage: Latency &lt;high&gt; &amp; rising&#10;"));
+        assert!(xml.contains("<skipped message=\"No endpoints\"/>"));
+        assert!(xml.contains("<testcase name=\"overall_score &gt;= 95\" classname=\"burnin.policy\" time=\"0\">\n      <failure message=\"overall_score &gt;= 95 (actual 80)\" type=\"FAIL\"/>"));
+        assert!(xml.ends_with("  </testsuite>\n</testsuites>\n"));
+    }
+
+    #[test]
+    fn test_render_junit_aborted() {
+        let mut suite = TestSuite::new();
+        suite.results = vec![
+            result("cpu_stress", TestStatus::Partial, json!({}), vec![issue(IssueSeverity::High, "Test stopped early")]),
+        ];
+        suite.finalize();
+        let xml = JunitReporter::render(&suite);
+        assert!(xml.contains("tests=\"1\" failures=\"1\" errors=\"0\" skipped=\"0\""));
+        assert!(xml.contains("<failure message=\"Test stopped early\" type=\"high\">"));
+
+        // A failed run whose tests all passed still fails in CI
+        let mut suite = TestSuite::new();
+        suite.finalize();
+        let xml = JunitReporter::render(&suite);
+        assert!(xml.contains("tests=\"1\" failures=\"1\""));
+        assert!(xml.contains("<testcase name=\"overall\" classname=\"burnin.suite\" time=\"0\">\n      <failure message=\"Run failed\" type=\"failed\"/>"));
+    }
+}
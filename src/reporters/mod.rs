@@ -1,6 +1,10 @@
 pub mod text;
 pub mod json;
 pub mod csv;
+pub mod junit;
+pub mod html;
+pub mod prometheus;
+pub mod ndjson;
 
 use crate::core::test::TestResult;
 use crate::core::config::TestConfig;
@@ -26,3 +30,40 @@ pub trait Reporter {
     
     fn report_info(&self, message: &str);
 }
+
+/// A reporter that forwards every event to several reporters in turn.
+pub struct MultiReporter {
+    reporters: Vec<Box<dyn Reporter + Send + Sync>>,
+}
+
+impl MultiReporter {
+    pub fn new(reporters: Vec<Box<dyn Reporter + Send + Sync>>) -> Self {
+        Self { reporters }
+    }
+}
+
+impl Reporter for MultiReporter {
+    fn report_start(&self, config: &TestConfig) {
+        self.reporters.iter().for_each(|r| r.report_start(config));
+    }
+    
+    fn report_test_start(&self, test_name: &str) {
+        self.reporters.iter().for_each(|r| r.report_test_start(test_name));
+    }
+    
+    fn report_test_result(&self, result: &TestResult) {
+        self.reporters.iter().for_each(|r| r.report_test_result(result));
+    }
+    
+    fn report_suite_result(&self, suite: &TestSuite) {
+        self.reporters.iter().for_each(|r| r.report_suite_result(suite));
+    }
+    
+    fn report_warning(&self, message: &str) {
+        self.reporters.iter().for_each(|r| r.report_warning(message));
+    }
+    
+    fn report_info(&self, message: &str) {
+        self.reporters.iter().for_each(|r| r.report_info(message));
+    }
+}
@@ -0,0 +1,327 @@
+use std::collections::BTreeMap;
+use std::fs::OpenOptions;
+use std::io::{self, Write};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde_json::{json, Map, Value};
+
+use crate::core::test::{TestResult, TestStatus, IssueSeverity};
+use crate::core::config::TestConfig;
+use crate::core::runner::TestSuite;
+use crate::core::hardware;
+use crate::reporters::Reporter;
+
+/// Version of the event schema, written into every event. Fields may be
+/// added within a version; renaming or removing one bumps it.
+pub const SCHEMA: &str = "burnin.events/v1";
+
+/// Time between progress events of a running test.
+const PROGRESS_INTERVAL: Duration = Duration::from_secs(10);
+
+struct Stream {
+    writer: Box<dyn Write + Send>,
+    seq: u64,
+    /// Running tests and when they started
+    running: BTreeMap<String, Instant>,
+    /// Issues already written for each test that reported a result
+    issues_written: BTreeMap<String, usize>,
+    finished: bool,
+}
+
+/// A reporter that streams the run as newline-delimited JSON, one event per
+/// line, so that other tools can follow it while it runs.
+///
+/// Every event has `schema`, `seq`, `timestamp` and `event` fields; `event`
+/// is one of `start`, `test_start`, `progress`, `issue`, `result`, `suite`,
+/// `warning` and `info`. A result reported again, after the health monitors
+/// added issues to it, only brings `issue` events for the new issues.
+#[derive(Clone)]
+pub struct EventLog {
+    stream: Arc<Mutex<Stream>>,
+    progress_interval: Duration,
+}
+
+impl EventLog {
+    /// Streams to `writer`, flushing after every event.
+    pub fn new(writer: Box<dyn Write + Send>) -> Self {
+        Self {
+            stream: Arc::new(Mutex::new(Stream {
+                writer,
+                seq: 0,
+                running: BTreeMap::new(),
+                issues_written: BTreeMap::new(),
+                finished: false,
+            })),
+            progress_interval: PROGRESS_INTERVAL,
+        }
+    }
+
+    /// Opens a destination: `-` for stdout, `fd:N` for an inherited file
+    /// descriptor, or a file path. Files are appended to, so a log can be
+    /// followed across runs. Stdout is only usable when nothing else writes
+    /// the report there.
+    pub fn open(destination: &str) -> io::Result<Self> {
+        let writer: Box<dyn Write + Send> = if destination == "-" {
+            Box::new(io::stdout())
+        } else {
+            let path = match destination.strip_prefix("fd:") {
+                Some(fd) => {
+                    let fd: u32 = fd.parse()
+                        .map_err(|_| io::Error::new(io::ErrorKind::InvalidInput, format!("invalid file descriptor '{}'", fd)))?;
+                    format!("/dev/fd/{}", fd)
+                }
+                None => destination.to_string(),
+            };
+            Box::new(OpenOptions::new().create(true).append(true).open(path)?)
+        };
+        Ok(Self::new(writer))
+    }
+
+    /// Sets the time between progress events of a running test.
+    pub fn with_progress_interval(mut self, interval: Duration) -> Self {
+        self.progress_interval = interval;
+        self
+    }
+
+    fn emit(&self, event: &str, fields: Value) {
+        let mut stream = self.stream.lock().unwrap();
+        Self::write(&mut stream, event, fields);
+    }
+
+    fn write(stream: &mut Stream, event: &str, fields: Value) {
+        stream.seq += 1;
+        let mut line = Map::new();
+        line.insert("schema".to_string(), json!(SCHEMA));
+        line.insert("seq".to_string(), json!(stream.seq));
+        line.insert("timestamp".to_string(), json!(chrono::Utc::now().to_rfc3339()));
+        line.insert("event".to_string(), json!(event));
+        if let Value::Object(fields) = fields {
+            line.extend(fields);
+        }
+
+        let result = serde_json::to_writer(&mut stream.writer, &line)
+            .map_err(io::Error::from)
+            .and_then(|_| stream.writer.write_all(b"\n"))
+            .and_then(|_| stream.writer.flush());
+        if let Err(e) = result {
+            eprintln!("Failed to write event log: {}", e);
+        }
+    }
+
+    /// Emits progress for every running test until the run is over.
+    fn start_progress(&self) {
+        let log = self.clone();
+        thread::spawn(move || loop {
+            thread::sleep(log.progress_interval);
+            let mut stream = log.stream.lock().unwrap();
+            if stream.finished {
+                break;
+            }
+            let running: Vec<(String, f64)> = stream.running.iter()
+                .map(|(test, started)| (test.clone(), started.elapsed().as_secs_f64()))
+                .collect();
+            for (test, elapsed) in running {
+                Self::write(&mut stream, "progress", json!({
+                    "test": test,
+                    "elapsed_seconds": elapsed,
+                }));
+            }
+        });
+    }
+}
+
+fn status_name(status: TestStatus) -> &'static str {
+    match status {
+        TestStatus::Completed => "pass",
+        TestStatus::Failed => "fail",
+        TestStatus::Partial => "partial",
+        TestStatus::Skipped => "skipped",
+        TestStatus::Pending => "pending",
+        TestStatus::Running => "running",
+    }
+}
+
+fn severity_name(severity: IssueSeverity) -> &'static str {
+    match severity {
+        IssueSeverity::Low => "low",
+        IssueSeverity::Medium => "medium",
+        IssueSeverity::High => "high",
+        IssueSeverity::Critical => "critical",
+    }
+}
+
+impl Reporter for EventLog {
+    fn report_start(&self, config: &TestConfig) {
+        self.emit("start", json!({
+            "version": env!("CARGO_PKG_VERSION"),
+            "hostname": hardware::detect().system_info.hostname,
+            "config": {
+                "duration_seconds": config.duration.as_secs(),
+                "stress_level": config.stress_level,
+                "threads": config.threads,
+                "components": {
+                    "cpu": config.cpu_enabled,
+                    "memory": config.memory_enabled,
+                    "storage": config.storage_enabled,
+                    "network": config.network_enabled,
+                    "thermal": config.thermal_enabled,
+                },
+            },
+        }));
+        self.start_progress();
+    }
+
+    fn report_test_start(&self, test_name: &str) {
+        let mut stream = self.stream.lock().unwrap();
+        stream.running.insert(test_name.to_string(), Instant::now());
+        Self::write(&mut stream, "test_start", json!({ "test": test_name }));
+    }
+
+    fn report_test_result(&self, result: &TestResult) {
+        let mut stream = self.stream.lock().unwrap();
+        stream.running.remove(&result.name);
+        let written = stream.issues_written.insert(result.name.clone(), result.issues.len()).unwrap_or(0);
+        for issue in result.issues.iter().skip(written) {
+            Self::write(&mut stream, "issue", json!({
+                "test": result.name,
+                "component": issue.component,
+                "severity": severity_name(issue.severity),
+                "message": issue.message,
+                "action": issue.action,
+            }));
+        }
+        Self::write(&mut stream, "result", json!({
+            "test": result.name,
+            "status": status_name(result.status),
+            "score": result.score,
+            "duration_seconds": result.duration.as_secs_f64(),
+            "issue_count": result.issues.len(),
+            "deductions": result.deductions,
+            "metrics": result.metrics,
+        }));
+    }
+
+    fn report_suite_result(&self, suite: &TestSuite) {
+        let mut stream = self.stream.lock().unwrap();
+        stream.running.clear();
+        stream.finished = true;
+        Self::write(&mut stream, "suite", json!({
+            "status": status_name(suite.overall_status),
+            "overall_score": suite.overall_score,
+            "duration_seconds": suite.duration.as_secs_f64(),
+            "aborted": suite.aborted.as_ref().map(|reason| reason.to_string()),
+            "policy": suite.policy.as_ref().map(|policy| json!({
+                "passed": policy.passed,
+                "rules": policy.verdicts,
+            })),
+            "tests": suite.results.iter()
+                .map(|result| json!({ "test": result.name, "status": status_name(result.status), "score": result.score }))
+                .collect::<Vec<_>>(),
+        }));
+    }
+
+    fn report_warning(&self, message: &str) {
+        self.emit("warning", json!({ "message": message }));
+    }
+
+    fn report_info(&self, message: &str) {
+        self.emit("info", json!({ "message": message }));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::test::TestIssue;
+
+    /// Collects what the log writes.
+    #[derive(Clone, Default)]
+    struct Buffer(Arc<Mutex<Vec<u8>>>);
+
+    impl Write for Buffer {
+        fn write(&mut self, data: &[u8]) -> io::Result<usize> {
+            self.0.lock().unwrap().write(data)
+        }
+
+        fn flush(&mut self) -> io::Result<()> {
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn test_event_stream() {
+        let buffer = Buffer::default();
+        let log = EventLog::new(Box::new(buffer.clone())).with_progress_interval(Duration::from_millis(20));
+        let config = TestConfig { duration: Duration::from_secs(60), ..TestConfig::default() };
+
+        log.report_start(&config);
+        log.report_test_start("storage_io");
+        thread::sleep(Duration::from_millis(70));
+        let result = TestResult {
+            name: "storage_io".to_string(),
+            status: TestStatus::Completed,
+            score: 90,
+            duration: Duration::from_millis(70),
+            metrics: json!({ "sequential_write_mbps": 512.5 }),
+            issues: vec![TestIssue {
+                component: "storage".to_string(),
+                severity: IssueSeverity::Medium,
+                message: "Slow\nwrites".to_string(),
+                action: None,
+            }],
+            deductions: Vec::new(),
+        };
+        log.report_test_result(&result);
+        let mut suite = TestSuite::new();
+        suite.results.push(result);
+        suite.finalize();
+        log.report_suite_result(&suite);
+        thread::sleep(Duration::from_millis(50));
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let events: Vec<Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        let names: Vec<&str> = events.iter().map(|e| e["event"].as_str().unwrap()).collect();
+
+        assert_eq!(names[..2], ["start", "test_start"]);
+        assert!(names[2..names.len() - 3].iter().all(|name| *name == "progress") && names.len() > 5, "{:?}", names);
+        assert_eq!(names[names.len() - 3..], ["issue", "result", "suite"]);
+        assert!(events.iter().enumerate().all(|(i, e)| e["schema"] == SCHEMA && e["seq"] == json!(i + 1)));
+        assert_eq!(events[2]["test"], "storage_io");
+        assert_eq!(events[names.len() - 3]["severity"], "medium");
+        assert_eq!(events[names.len() - 2]["metrics"]["sequential_write_mbps"], 512.5);
+        assert_eq!(events[names.len() - 1]["status"], "pass");
+    }
+
+    #[test]
+    fn test_result_reported_again() {
+        let buffer = Buffer::default();
+        let log = EventLog::new(Box::new(buffer.clone()));
+        let issue = |message: &str| TestIssue {
+            component: "memory".to_string(),
+            severity: IssueSeverity::Critical,
+            message: message.to_string(),
+            action: None,
+        };
+        let mut result = TestResult {
+            name: "memory_validation".to_string(),
+            status: TestStatus::Completed,
+            score: 95,
+            duration: Duration::from_secs(60),
+            metrics: json!({}),
+            issues: vec![issue("Slow")],
+            deductions: Vec::new(),
+        };
+        log.report_test_result(&result);
+        result.status = TestStatus::Failed;
+        result.issues.push(issue("Uncorrectable ECC error"));
+        log.report_test_result(&result);
+
+        let output = String::from_utf8(buffer.0.lock().unwrap().clone()).unwrap();
+        let events: Vec<Value> = output.lines().map(|line| serde_json::from_str(line).unwrap()).collect();
+        let summary: Vec<(&str, &str)> = events.iter()
+            .map(|e| (e["event"].as_str().unwrap(), e["message"].as_str().or(e["status"].as_str()).unwrap()))
+            .collect();
+        assert_eq!(summary, [("issue", "Slow"), ("result", "pass"), ("issue", "Uncorrectable ECC error"), ("result", "fail")]);
+    }
+}
@@ -0,0 +1,470 @@
+use std::collections::BTreeMap;
+use std::fmt::Write as _;
+use std::fs;
+use std::io::{self, BufRead, BufReader, Write};
+use std::net::{SocketAddr, TcpListener, TcpStream};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+use serde_json::Value;
+
+use crate::core::test::{TestResult, TestStatus, IssueSeverity};
+use crate::core::config::TestConfig;
+use crate::core::runner::TestSuite;
+use crate::core::hardware;
+use crate::core::hwmon::{self, SensorReadings};
+use crate::reporters::Reporter;
+
+/// Content type of the OpenMetrics text format.
+pub const CONTENT_TYPE: &str = "application/openmetrics-text; version=1.0.0; charset=utf-8";
+
+/// How often the textfile is rewritten while a test runs, so elapsed times
+/// stay current between events.
+const TEXTFILE_REFRESH: Duration = Duration::from_secs(15);
+
+/// Live state of the run, shared with the HTTP endpoint.
+#[derive(Debug, Default)]
+struct State {
+    started: Option<(Instant, chrono::DateTime<chrono::Utc>)>,
+    running: BTreeMap<String, Instant>,
+    finished: BTreeMap<String, Finished>,
+    warnings: u64,
+    /// Overall score and whether the run passed, once it is over
+    outcome: Option<(u8, bool)>,
+}
+
+#[derive(Debug)]
+struct Finished {
+    status: TestStatus,
+    score: u8,
+    duration_secs: f64,
+    issues: [u64; 4],
+    metrics: Vec<(String, f64)>,
+}
+
+/// A reporter that exposes the progress of a run as OpenMetrics gauges and
+/// counters, over HTTP for Prometheus or as a file for node_exporter's
+/// textfile collector.
+///
+/// Run it alongside the reporter for the chosen output format with a
+/// `MultiReporter`. Sensor readings are taken afresh for every scrape and
+/// textfile write, whether or not a thermal test is running.
+#[derive(Clone)]
+pub struct PrometheusReporter {
+    state: Arc<Mutex<State>>,
+    textfile: Option<PathBuf>,
+    sensors: fn() -> SensorReadings,
+}
+
+impl Default for PrometheusReporter {
+    fn default() -> Self {
+        Self {
+            state: Arc::default(),
+            textfile: None,
+            sensors: read_sensors,
+        }
+    }
+}
+
+impl PrometheusReporter {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Also writes the metrics to `path` after every event. The file is
+    /// replaced atomically, so it should end in `.prom` and live in the
+    /// collector's directory.
+    pub fn with_textfile(mut self, path: PathBuf) -> Self {
+        self.textfile = Some(path);
+        self
+    }
+
+    /// Serves the metrics at `/metrics` on `address` from a background
+    /// thread, returning the address actually bound.
+    pub fn serve(&self, address: &str) -> io::Result<SocketAddr> {
+        let listener = TcpListener::bind(address)?;
+        let bound = listener.local_addr()?;
+        let exporter = self.clone();
+        thread::spawn(move || {
+            for stream in listener.incoming().flatten() {
+                if let Err(e) = exporter.respond(stream) {
+                    log::debug!("Metrics request failed: {}", e);
+                }
+            }
+        });
+        Ok(bound)
+    }
+
+    fn respond(&self, mut stream: TcpStream) -> io::Result<()> {
+        stream.set_read_timeout(Some(Duration::from_secs(5)))?;
+        let mut request_line = String::new();
+        BufReader::new(&stream).read_line(&mut request_line)?;
+        let path = request_line.split_whitespace().nth(1).unwrap_or("");
+
+        let (status, content_type, body) = match path.split('?').next() {
+            Some("/metrics") => ("200 OK", CONTENT_TYPE, self.render()),
+            Some("/") => ("200 OK", "text/html; charset=utf-8", "<a href=\"/metrics\">Metrics</a>\n".to_string()),
+            _ => ("404 Not Found", "text/plain; charset=utf-8", "Not found\n".to_string()),
+        };
+        write!(
+            stream,
+            "HTTP/1.1 {}\r\nContent-Type: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+            status, content_type, body.len(), body,
+        )?;
+        stream.flush()
+    }
+
+    /// Renders the current state in the OpenMetrics text format.
+    pub fn render(&self) -> String {
+        self.render_as(Format::OpenMetrics)
+    }
+
+    fn render_as(&self, format: Format) -> String {
+        let sensors = (self.sensors)();
+        let state = self.state.lock().unwrap();
+        let mut out = String::new();
+        let family = |out: &mut String, name: &str, kind: &str, help: &str| family(out, format, name, kind, help);
+
+        let hostname = hardware::detect().system_info.hostname;
+        family(&mut out, "burnin", "info", "Burnin version and host");
+        sample(&mut out, "burnin_info", &[("version", env!("CARGO_PKG_VERSION")), ("hostname", &hostname)], 1.0);
+
+        if let Some((started, start_time)) = state.started {
+            family(&mut out, "burnin_run_start_timestamp_seconds", "gauge", "Time the run started");
+            sample(&mut out, "burnin_run_start_timestamp_seconds", &[], start_time.timestamp() as f64);
+            family(&mut out, "burnin_run_elapsed_seconds", "gauge", "Time since the run started");
+            sample(&mut out, "burnin_run_elapsed_seconds", &[], started.elapsed().as_secs_f64());
+        }
+        family(&mut out, "burnin_run_completed", "gauge", "Whether the run is over");
+        sample(&mut out, "burnin_run_completed", &[], bool_value(state.outcome.is_some()));
+        if let Some((score, passed)) = state.outcome {
+            family(&mut out, "burnin_run_score", "gauge", "Overall score of the run");
+            sample(&mut out, "burnin_run_score", &[], score as f64);
+            family(&mut out, "burnin_run_passed", "gauge", "Whether the run passed");
+            sample(&mut out, "burnin_run_passed", &[], bool_value(passed));
+        }
+        family(&mut out, "burnin_warnings", "counter", "Warnings reported during the run");
+        sample(&mut out, "burnin_warnings_total", &[], state.warnings as f64);
+
+        let tests: Vec<&String> = state.running.keys().chain(state.finished.keys()).collect();
+        family(&mut out, "burnin_sensor_temperature_celsius", "gauge", "Current temperature of the sensor");
+        for sensor in &sensors.temperatures {
+            sample(&mut out, "burnin_sensor_temperature_celsius", &[("sensor", &sensor.name)], sensor.current_temp_celsius as f64);
+        }
+        family(&mut out, "burnin_sensor_fan_rpm", "gauge", "Current speed of the fan");
+        for fan in &sensors.fans {
+            sample(&mut out, "burnin_sensor_fan_rpm", &[("sensor", &fan.name)], fan.rpm as f64);
+        }
+
+        family(&mut out, "burnin_test_running", "gauge", "Whether the test is running");
+        for test in &tests {
+            sample(&mut out, "burnin_test_running", &[("test", test)], bool_value(state.running.contains_key(*test)));
+        }
+        family(&mut out, "burnin_test_duration_seconds", "gauge", "Time the test has run, or took");
+        for (test, started) in &state.running {
+            sample(&mut out, "burnin_test_duration_seconds", &[("test", test)], started.elapsed().as_secs_f64());
+        }
+        for (test, finished) in &state.finished {
+            sample(&mut out, "burnin_test_duration_seconds", &[("test", test)], finished.duration_secs);
+        }
+
+        family(&mut out, "burnin_test_score", "gauge", "Score of the finished test");
+        for (test, finished) in &state.finished {
+            sample(&mut out, "burnin_test_score", &[("test", test)], finished.score as f64);
+        }
+        family(&mut out, "burnin_test_passed", "gauge", "Whether the finished test passed");
+        for (test, finished) in &state.finished {
+            sample(&mut out, "burnin_test_passed", &[("test", test)], bool_value(finished.status == TestStatus::Completed));
+        }
+        family(&mut out, "burnin_test_issues", "counter", "Issues reported by the test");
+        for (test, finished) in &state.finished {
+            for (severity, count) in ["low", "medium", "high", "critical"].iter().zip(finished.issues) {
+                sample(&mut out, "burnin_test_issues_total", &[("test", test), ("severity", severity)], count as f64);
+            }
+        }
+        family(&mut out, "burnin_test_metric", "gauge", "Numeric metrics reported by the test");
+        for (test, finished) in &state.finished {
+            for (metric, value) in &finished.metrics {
+                sample(&mut out, "burnin_test_metric", &[("test", test), ("metric", metric)], *value);
+            }
+        }
+
+        if format == Format::OpenMetrics {
+            out.push_str("# EOF\n");
+        }
+        out
+    }
+
+    /// Records an event and refreshes the textfile.
+    fn update(&self, change: impl FnOnce(&mut State)) {
+        change(&mut self.state.lock().unwrap());
+        if let Some(path) = &self.textfile {
+            if let Err(e) = write_atomically(path, &self.render_as(Format::Prometheus)) {
+                eprintln!("Failed to write metrics to {}: {}", path.display(), e);
+            }
+        }
+    }
+
+    /// Rewrites the textfile periodically until the run is over.
+    fn start_textfile_refresh(&self) {
+        if self.textfile.is_none() {
+            return;
+        }
+        let exporter = self.clone();
+        thread::spawn(move || loop {
+            thread::sleep(TEXTFILE_REFRESH);
+            if exporter.state.lock().unwrap().outcome.is_some() {
+                break;
+            }
+            exporter.update(|_| {});
+        });
+    }
+}
+
+impl Reporter for PrometheusReporter {
+    fn report_start(&self, _config: &TestConfig) {
+        self.update(|state| state.started = Some((Instant::now(), chrono::Utc::now())));
+        self.start_textfile_refresh();
+    }
+
+    fn report_test_start(&self, test_name: &str) {
+        self.update(|state| {
+            state.finished.remove(test_name);
+            state.running.insert(test_name.to_string(), Instant::now());
+        });
+    }
+
+    fn report_test_result(&self, result: &TestResult) {
+        let mut issues = [0; 4];
+        for issue in &result.issues {
+            issues[match issue.severity {
+                IssueSeverity::Low => 0,
+                IssueSeverity::Medium => 1,
+                IssueSeverity::High => 2,
+                IssueSeverity::Critical => 3,
+            }] += 1;
+        }
+        let mut metrics = Vec::new();
+        flatten_metrics(&result.metrics, String::new(), &mut metrics);
+
+        self.update(|state| {
+            state.running.remove(&result.name);
+            state.finished.insert(result.name.clone(), Finished {
+                status: result.status,
+                score: result.score,
+                duration_secs: result.duration.as_secs_f64(),
+                issues,
+                metrics,
+            });
+        });
+    }
+
+    fn report_suite_result(&self, suite: &TestSuite) {
+        self.update(|state| {
+            state.running.clear();
+            state.outcome = Some((suite.overall_score, suite.overall_status == TestStatus::Completed));
+        });
+    }
+
+    fn report_warning(&self, _message: &str) {
+        self.update(|state| state.warnings += 1);
+    }
+
+    fn report_info(&self, _message: &str) {
+        // No-op
+    }
+}
+
+/// The exposition formats written.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Format {
+    OpenMetrics,
+    /// The classic Prometheus text format, which node_exporter's textfile
+    /// collector reads. It has no info type and names counter families
+    /// after their `_total` samples.
+    Prometheus,
+}
+
+fn family(out: &mut String, format: Format, name: &str, kind: &str, help: &str) {
+    let (name, kind) = match (format, kind) {
+        (Format::Prometheus, "counter") => (format!("{}_total", name), kind),
+        (Format::Prometheus, "info") => (format!("{}_info", name), "gauge"),
+        _ => (name.to_string(), kind),
+    };
+    let _ = writeln!(out, "# TYPE {} {}\n# HELP {} {}", name, kind, name, help);
+}
+
+fn sample(out: &mut String, name: &str, labels: &[(&str, &str)], value: f64) {
+    out.push_str(name);
+    if !labels.is_empty() {
+        let labels: Vec<String> = labels.iter()
+            .map(|(label, value)| format!("{}=\"{}\"", label, escape_label(value)))
+            .collect();
+        let _ = write!(out, "{{{}}}", labels.join(","));
+    }
+    let _ = writeln!(out, " {}", value);
+}
+
+fn bool_value(value: bool) -> f64 {
+    if value { 1.0 } else { 0.0 }
+}
+
+fn escape_label(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"").replace('\n', "\\n")
+}
+
+/// Collects the numeric metrics of a result under dotted names. Booleans
+/// count as 1 and 0; arrays, such as time series, are left out.
+fn flatten_metrics(value: &Value, prefix: String, metrics: &mut Vec<(String, f64)>) {
+    match value {
+        Value::Object(map) => {
+            for (key, value) in map {
+                let name = if prefix.is_empty() { key.clone() } else { format!("{}.{}", prefix, key) };
+                flatten_metrics(value, name, metrics);
+            }
+        }
+        Value::Number(n) => {
+            if let Some(n) = n.as_f64().filter(|n| n.is_finite()) {
+                metrics.push((prefix, n));
+            }
+        }
+        Value::Bool(b) => metrics.push((prefix, bool_value(*b))),
+        _ => {}
+    }
+}
+
+/// Reads the temperatures and fans of the running system.
+fn read_sensors() -> SensorReadings {
+    let mut readings = hwmon::read_sensors();
+    readings.temperatures = hwmon::read_temperatures(&readings);
+    readings
+}
+
+/// Writes through a temporary file in the same directory so that a
+/// collector never reads a partial file. Every write gets its own temporary
+/// file, as events and the periodic refresh may write at the same time.
+fn write_atomically(path: &Path, contents: &str) -> io::Result<()> {
+    static WRITES: AtomicU64 = AtomicU64::new(0);
+    let mut temporary = path.as_os_str().to_owned();
+    temporary.push(format!(".{}.{}.tmp", std::process::id(), WRITES.fetch_add(1, Ordering::Relaxed)));
+    fs::write(&temporary, contents)?;
+    fs::rename(&temporary, path)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Read;
+    use serde_json::json;
+    use crate::core::test::TestIssue;
+    use crate::core::hardware::{ThermalSensor, FanSensor};
+
+    fn sensors() -> SensorReadings {
+        SensorReadings {
+            temperatures: vec![ThermalSensor {
+                name: "coretemp/Package id 0".to_string(),
+                location: "coretemp".to_string(),
+                current_temp_celsius: 61.5,
+                critical_temp_celsius: Some(100.0),
+                max_temp_celsius: Some(82.0),
+            }],
+            fans: vec![FanSensor {
+                name: "nct6798/fan2".to_string(),
+                location: "nct6798".to_string(),
+                rpm: 1200,
+                min_rpm: None,
+            }],
+            voltages: Vec::new(),
+        }
+    }
+
+    fn result() -> TestResult {
+        TestResult {
+            name: "storage_io".to_string(),
+            status: TestStatus::Completed,
+            score: 88,
+            duration: Duration::from_secs(120),
+            metrics: json!({
+                "sequential_write_mbps": 512.5,
+                "throughput_series": { "write /data": [[1.0, 500.0]] },
+                "smart": { "healthy": true, "model": "QEMU HARDDISK" },
+            }),
+            issues: vec![TestIssue {
+                component: "storage".to_string(),
+                severity: IssueSeverity::Medium,
+                message: "Slow".to_string(),
+                action: None,
+            }],
+            deductions: Vec::new(),
+        }
+    }
+
+    #[test]
+    fn test_render_progress() {
+        let reporter = PrometheusReporter { sensors, ..PrometheusReporter::new() };
+        reporter.report_start(&TestConfig::default());
+        reporter.report_test_start("cpu_stress");
+        reporter.report_test_start("storage_io");
+        reporter.report_test_result(&result());
+        reporter.report_warning("Failed to clean up");
+
+        let text = reporter.render();
+        assert!(text.contains("# TYPE burnin_test_running gauge\n"));
+        assert!(text.contains("burnin_test_running{test=\"cpu_stress\"} 1\n"));
+        assert!(text.contains("burnin_test_running{test=\"storage_io\"} 0\n"));
+        assert!(text.contains("burnin_test_score{test=\"storage_io\"} 88\n"));
+        assert!(text.contains("burnin_test_issues_total{test=\"storage_io\",severity=\"medium\"} 1\n"));
+        assert!(text.contains("burnin_test_metric{test=\"storage_io\",metric=\"sequential_write_mbps\"} 512.5\n"));
+        assert!(text.contains("burnin_test_metric{test=\"storage_io\",metric=\"smart.healthy\"} 1\n"));
+        assert!(!text.contains("throughput_series"));
+        assert!(text.contains("burnin_warnings_total 1\n"));
+        assert!(text.contains("burnin_sensor_temperature_celsius{sensor=\"coretemp/Package id 0\"} 61.5\n"));
+        assert!(text.contains("burnin_sensor_fan_rpm{sensor=\"nct6798/fan2\"} 1200\n"));
+        assert!(text.contains("burnin_run_completed 0\n"));
+        assert!(!text.contains("burnin_run_score"));
+        assert!(text.ends_with("# EOF\n"));
+
+        let mut suite = TestSuite::new();
+        suite.results.push(result());
+        suite.finalize();
+        reporter.report_suite_result(&suite);
+        let text = reporter.render();
+        assert!(text.contains("burnin_run_completed 1\n"));
+        assert!(text.contains("burnin_run_score 88\n"));
+        assert!(text.contains("burnin_test_running{test=\"storage_io\"} 0\n"));
+        assert!(!text.contains("cpu_stress"));
+        assert_eq!(escape_label("a\"b\\c\nd"), "a\\\"b\\\\c\\nd");
+    }
+
+    #[test]
+    fn test_serve_and_textfile() {
+        let path = std::env::temp_dir().join(format!("burnin-metrics-{}.prom", std::process::id()));
+        let reporter = PrometheusReporter { sensors, ..PrometheusReporter::new() }.with_textfile(path.clone());
+        let address = reporter.serve("127.0.0.1:0").unwrap();
+        reporter.report_test_result(&result());
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(b"GET /metrics HTTP/1.1\r\nHost: localhost\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 200 OK\r\n"));
+        assert!(response.contains(&format!("Content-Type: {}\r\n", CONTENT_TYPE)));
+        assert!(response.ends_with("# EOF\n"));
+
+        let mut stream = TcpStream::connect(address).unwrap();
+        stream.write_all(b"GET /other HTTP/1.1\r\n\r\n").unwrap();
+        let mut response = String::new();
+        stream.read_to_string(&mut response).unwrap();
+        assert!(response.starts_with("HTTP/1.1 404 Not Found\r\n"));
+
+        let contents = fs::read_to_string(&path).unwrap();
+        assert!(contents.contains("burnin_test_score{test=\"storage_io\"} 88\n"));
+        assert!(contents.contains("# TYPE burnin_info gauge\n"));
+        assert!(contents.contains("# TYPE burnin_test_issues_total counter\n"));
+        assert!(!contents.contains("# EOF"));
+        assert!(contents.contains("# TYPE burnin_sensor_temperature_celsius gauge\n"));
+        fs::remove_file(&path).unwrap();
+    }
+}
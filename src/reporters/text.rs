@@ -5,6 +5,8 @@ use chrono::Local;
 use crate::core::test::{TestResult, TestStatus, IssueSeverity};
 use crate::core::config::TestConfig;
 use crate::core::runner::TestSuite;
+use crate::core::hardware;
+use crate::core::policy::Verdict;
 use crate::reporters::Reporter;
 
 
@@ -102,6 +104,9 @@ impl Reporter for TextReporter {
                 result.name.cyan(),
                 self.format_status(result.status));
             println!("  Score: {}/100", result.score);
+            for deduction in &result.deductions {
+                println!("    -{} {}", deduction.points, deduction.reason);
+            }
             println!("  Duration: {}", self.format_duration(result.duration));
             
             
@@ -146,14 +151,38 @@ impl Reporter for TextReporter {
             println!("{} {}/100", 
                 self.format_status(suite.overall_status),
                 suite.overall_score);
+            if let Some(reason) = &suite.aborted {
+                println!("{} {}", "ABORTED:".red().bold(), reason);
+            }
+            if let Some(policy) = suite.policy.as_ref().filter(|policy| !policy.passed) {
+                println!("{} {}/{} rules passed",
+                    "POLICY FAILED:".red().bold(),
+                    policy.passed_count(),
+                    policy.verdicts.len());
+            }
             return;
         }
         
         println!("\n{}", "BURN-IN TEST RESULTS".bold());
         println!("====================");
         println!("System: {}", suite.system_info.as_ref().map(|s| s.hostname.as_str()).unwrap_or("Unknown"));
+        let dmi = hardware::detect().dmi;
+        if let Some(machine) = dmi.machine() {
+            match &dmi.system_serial {
+                Some(serial) => println!("Machine: {} (serial {})", machine, serial),
+                None => println!("Machine: {}", machine),
+            }
+        }
         println!("Started: {}", suite.start_time.format("%Y-%m-%d %H:%M:%S UTC"));
         println!("Duration: {:?}", suite.duration);
+        
+        if !suite.adjustments.is_empty() {
+            println!("\nAuto-adjusted parameters:");
+            for adjustment in &suite.adjustments {
+                println!("  {}: {} → {} ({})",
+                    adjustment.parameter, adjustment.from, adjustment.to, adjustment.reason);
+            }
+        }
         println!();
         
         
@@ -168,6 +197,9 @@ impl Reporter for TextReporter {
                 " ".repeat(max_name_len - result.name.len() + 2),
                 self.format_status(result.status),
                 result.score);
+            for deduction in &result.deductions {
+                println!("  {} {}", format!("-{}", deduction.points).yellow(), deduction.reason);
+            }
         }
         
         println!("\n{}: {} (Score: {}/100)",
@@ -175,6 +207,30 @@ impl Reporter for TextReporter {
             self.format_status(suite.overall_status),
             suite.overall_score);
         
+        if let Some(reason) = &suite.aborted {
+            println!("{} {}", "RUN ABORTED:".red().bold(), reason);
+        }
+        
+        if let Some(policy) = &suite.policy {
+            println!("\n{}: {} ({}/{} rules passed)",
+                "ACCEPTANCE POLICY".bold(),
+                if policy.passed { "✓ PASS".green().bold() } else { "✗ FAIL".red().bold() },
+                policy.passed_count(),
+                policy.verdicts.len());
+            for verdict in &policy.verdicts {
+                let label = match verdict.verdict {
+                    Verdict::Pass => "PASS".green(),
+                    Verdict::Fail => "FAIL".red().bold(),
+                    Verdict::Missing => "MISSING".yellow().bold(),
+                };
+                let actual = verdict.actual.map_or_else(|| "not reported".to_string(), |actual| format!("actual {}", actual));
+                match &verdict.description {
+                    Some(description) => println!("  [{}] {} — {} ({})", label, verdict.check, description, actual),
+                    None => println!("  [{}] {} ({})", label, verdict.check, actual),
+                }
+            }
+        }
+        
         
         let all_issues: Vec<_> = suite.results.iter()
             .flat_map(|r| r.issues.iter())
@@ -0,0 +1,212 @@
+use std::collections::HashMap;
+use std::hint::black_box;
+use std::time::Instant;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use serde::{Serialize, Deserialize};
+
+use crate::core::cache::data_cache_size;
+use crate::core::test::{TestIssue, IssueSeverity};
+
+/// Number of `usize` slots in one 64-byte cache line.
+const LINE_WORDS: usize = 64 / std::mem::size_of::<usize>();
+
+/// Number of dependent loads timed per pointer-chasing run.
+const CHASE_STEPS: usize = 1 << 20;
+
+/// Number of bytes read per streaming run.
+const STREAM_BYTES: u64 = 256 * 1024 * 1024;
+
+const MIN_DRAM_WORKING_SET: u64 = 64 * 1024 * 1024;
+const MAX_DRAM_WORKING_SET: u64 = 512 * 1024 * 1024;
+
+/// Latency and bandwidth measured with a working set sized for one level of
+/// the memory hierarchy.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelMeasurement {
+    pub level: String,
+    pub working_set_bytes: u64,
+    pub latency_ns: f64,
+    pub bandwidth_mbps: f64,
+}
+
+/// Chooses a working set for each cache level and for DRAM.
+///
+/// Cache working sets use half of the level's capacity so they fit even with
+/// other data resident; levels that are unknown or not larger than the level
+/// below are left out. The DRAM working set is twice the largest cache,
+/// bounded to keep the run short.
+pub fn working_sets(cache_sizes: &HashMap<String, u64>) -> Vec<(String, u64)> {
+    let mut sets = Vec::new();
+    let mut largest = 0;
+
+    for level in 1..=3u8 {
+        if let Some(size) = data_cache_size(cache_sizes, level) {
+            if size > largest {
+                sets.push((format!("L{}", level), size / 2));
+                largest = size;
+            }
+        }
+    }
+
+    let dram = (largest * 2).clamp(MIN_DRAM_WORKING_SET, MAX_DRAM_WORKING_SET);
+    sets.push(("DRAM".to_string(), dram));
+    sets
+}
+
+/// Measures every level of the memory hierarchy.
+pub fn measure_hierarchy(cache_sizes: &HashMap<String, u64>) -> Vec<LevelMeasurement> {
+    working_sets(cache_sizes)
+        .into_iter()
+        .map(|(level, bytes)| measure_level(&level, bytes))
+        .collect()
+}
+
+/// Measures load-to-use latency with a pointer chase and read bandwidth with
+/// a streaming pass over a working set of `bytes`.
+pub fn measure_level(level: &str, bytes: u64) -> LevelMeasurement {
+    let lines = (bytes as usize / 64).max(2);
+    let chain = build_chain(lines, 0x5EED);
+
+    // Warm the working set into the level being measured.
+    black_box(chase(&chain, lines));
+
+    let start = Instant::now();
+    black_box(chase(&chain, CHASE_STEPS));
+    let latency_ns = start.elapsed().as_nanos() as f64 / CHASE_STEPS as f64;
+
+    let set_bytes = (chain.len() * std::mem::size_of::<usize>()) as u64;
+    let passes = (STREAM_BYTES / set_bytes).max(1);
+    let start = Instant::now();
+    for _ in 0..passes {
+        black_box(stream(&chain));
+    }
+    let elapsed = start.elapsed().as_secs_f64();
+    let bandwidth_mbps = if elapsed > 0.0 {
+        (set_bytes * passes) as f64 / 1_000_000.0 / elapsed
+    } else {
+        0.0
+    };
+
+    LevelMeasurement {
+        level: level.to_string(),
+        working_set_bytes: set_bytes,
+        latency_ns,
+        bandwidth_mbps,
+    }
+}
+
+/// Builds a single random cycle through `lines` cache lines, so every load
+/// depends on the previous one and hardware prefetchers cannot help.
+fn build_chain(lines: usize, seed: u64) -> Vec<usize> {
+    let mut order: Vec<usize> = (0..lines).collect();
+    let mut rng = StdRng::seed_from_u64(seed);
+    for i in (1..lines).rev() {
+        let j = rng.gen_range(0..=i);
+        order.swap(i, j);
+    }
+
+    let mut chain = vec![0usize; lines * LINE_WORDS];
+    for (k, &line) in order.iter().enumerate() {
+        chain[line * LINE_WORDS] = order[(k + 1) % lines] * LINE_WORDS;
+    }
+    chain
+}
+
+fn chase(chain: &[usize], steps: usize) -> usize {
+    let mut index = 0;
+    for _ in 0..steps {
+        index = chain[index];
+    }
+    index
+}
+
+fn stream(chain: &[usize]) -> usize {
+    chain.iter().fold(0usize, |acc, &value| acc.wrapping_add(value))
+}
+
+/// Flags cache levels that are not measurably faster than the next level
+/// out, which points at disabled, misconfigured or failing caches.
+pub fn analyze_hierarchy(measurements: &[LevelMeasurement]) -> Vec<TestIssue> {
+    let mut issues = Vec::new();
+
+    for pair in measurements.windows(2) {
+        let (inner, outer) = (&pair[0], &pair[1]);
+        if inner.latency_ns >= outer.latency_ns * 0.9 {
+            issues.push(TestIssue {
+                component: "cpu".to_string(),
+                severity: IssueSeverity::Medium,
+                message: format!(
+                    "{} cache latency ({:.1} ns) is not lower than {} latency ({:.1} ns)",
+                    inner.level, inner.latency_ns, outer.level, outer.latency_ns,
+                ),
+                action: Some("Check BIOS cache settings and CPU health".to_string()),
+            });
+        }
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(level: &str, latency_ns: f64) -> LevelMeasurement {
+        LevelMeasurement {
+            level: level.to_string(),
+            working_set_bytes: 0,
+            latency_ns,
+            bandwidth_mbps: 0.0,
+        }
+    }
+
+    #[test]
+    fn test_working_sets() {
+        let sizes = HashMap::from([
+            ("L1d".to_string(), 32 * 1024),
+            ("L1i".to_string(), 32 * 1024),
+            ("L2".to_string(), 1024 * 1024),
+            ("L3".to_string(), 16 * 1024 * 1024),
+        ]);
+        let sets = working_sets(&sizes);
+        assert_eq!(sets, vec![
+            ("L1".to_string(), 16 * 1024),
+            ("L2".to_string(), 512 * 1024),
+            ("L3".to_string(), 8 * 1024 * 1024),
+            ("DRAM".to_string(), MIN_DRAM_WORKING_SET),
+        ]);
+    }
+
+    #[test]
+    fn test_working_sets_without_cache_info() {
+        let sets = working_sets(&HashMap::new());
+        assert_eq!(sets, vec![("DRAM".to_string(), MIN_DRAM_WORKING_SET)]);
+    }
+
+    #[test]
+    fn test_chain_is_single_cycle() {
+        let lines = 1000;
+        let chain = build_chain(lines, 7);
+        let mut index = 0;
+        let mut visited = vec![false; lines];
+        for _ in 0..lines {
+            assert!(!visited[index / LINE_WORDS]);
+            visited[index / LINE_WORDS] = true;
+            index = chain[index];
+        }
+        assert_eq!(index, 0);
+        assert!(visited.iter().all(|v| *v));
+    }
+
+    #[test]
+    fn test_analyze_hierarchy() {
+        let healthy = [measurement("L1", 1.0), measurement("L2", 4.0), measurement("DRAM", 90.0)];
+        assert!(analyze_hierarchy(&healthy).is_empty());
+
+        let broken = [measurement("L1", 1.0), measurement("L2", 85.0), measurement("DRAM", 90.0)];
+        let issues = analyze_hierarchy(&broken);
+        assert_eq!(issues.len(), 1);
+        assert!(issues[0].message.starts_with("L2 cache latency"));
+    }
+}
@@ -1,62 +1,32 @@
 use std::time::{Duration, Instant};
-use std::sync::{Arc, Mutex};
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, Ordering};
 use std::thread;
+use std::hint::black_box;
 use serde_json::json;
-use sysinfo::System;
 
 use crate::core::test::{BurnInTest, TestResult, TestStatus, TestIssue, IssueSeverity};
 use crate::core::config::TestConfig;
-use crate::core::hardware::{HardwareInfo, CpuInfo};
+use crate::core::hardware::{self, HardwareInfo};
 use crate::core::error::Result;
+use crate::core::cgroup;
+use crate::core::scoring::Scorecard;
+use crate::tests::cache;
+use crate::tests::simd::{self, SimdWorkload};
 
 
 pub struct CpuStressTest;
 
+/// Number of scalar workloads a stress worker can be assigned.
+const SCALAR_WORKLOADS: usize = 6;
+
 impl BurnInTest for CpuStressTest {
     fn name(&self) -> &'static str {
         "cpu_stress"
     }
     
     fn detect_hardware(&self) -> Result<HardwareInfo> {
-        
-        let mut system = System::new_all();
-        system.refresh_cpu();
-        
-        let cpu_vendor = system.global_cpu_info().vendor_id().to_string();
-        let cpu_name = system.global_cpu_info().brand().to_string();
-        
-        
-        let cpu_info = CpuInfo {
-            model_name: cpu_name,
-            vendor: cpu_vendor,
-            physical_cores: num_cpus::get_physical() as u32,
-            logical_cores: num_cpus::get() as u32,
-            frequency_mhz: system.global_cpu_info().frequency() as f64,
-            cache_sizes: std::collections::HashMap::new(), 
-            features: Vec::new(), 
-        };
-        
-        
-        let hardware_info = HardwareInfo {
-            system_info: crate::core::hardware::SystemInfo {
-                hostname: System::host_name().unwrap_or_else(|| "unknown".to_string()),
-                os_name: System::name().unwrap_or_else(|| "unknown".to_string()),
-                os_version: System::os_version().unwrap_or_else(|| "unknown".to_string()),
-                kernel_version: System::kernel_version().unwrap_or_else(|| "unknown".to_string()),
-            },
-            cpu_info,
-            memory_info: crate::core::hardware::MemoryInfo {
-                total_bytes: system.total_memory(),
-                available_bytes: system.available_memory(),
-                speed_mhz: None,
-                ecc_enabled: None,
-            },
-            storage_devices: Vec::new(),
-            virtualization: None, 
-            thermal_sensors: Vec::new(),
-        };
-        
-        Ok(hardware_info)
+        Ok(hardware::detect())
     }
     
     fn estimate_duration(&self, config: &TestConfig) -> Duration {
@@ -66,28 +36,42 @@ impl BurnInTest for CpuStressTest {
     fn execute(&self, config: &TestConfig) -> Result<TestResult> {
         let start_time = Instant::now();
         let thread_count = if config.threads == 0 {
-            num_cpus::get() as u32
+            cgroup::available_cpus() as u32
         } else {
             config.threads
         };
         
-        println!("Starting CPU stress test with {} threads for {:?}", thread_count, config.duration);
+        log::info!("Starting CPU stress test with {} threads for {:?}", thread_count, config.duration);
         
         
-        let utilization = Arc::new(Mutex::new(0.0));
-        let throttling_events = Arc::new(Mutex::new(0));
-        let instructions_per_sec = Arc::new(Mutex::new(0u64));
+        // Measure the memory hierarchy before the workers start so the
+        // stress load does not skew latencies.
+        let cache_sizes = hardware::detect().cpu_info.cache_sizes;
+        let hierarchy = cache::measure_hierarchy(&cache_sizes);
+        let hierarchy_issues = cache::analyze_hierarchy(&hierarchy);
         
         
-        let running = Arc::new(Mutex::new(true));
+        let (simd_kernels, skipped_kernels) = simd::select_kernels();
+        let workload_count = simd_kernels.len() + SCALAR_WORKLOADS;
+        
+        
+        // Workers only touch these once per reporting interval, and never
+        // take a lock, so they do not serialise each other.
+        let utilization = Arc::new(AtomicU32::new(0f32.to_bits()));
+        let throttling_events = Arc::new(AtomicU64::new(0));
+        let instructions_per_sec = Arc::new(AtomicU64::new(0));
+        let simd_errors = Arc::new(AtomicU64::new(0));
+        
+        
+        let running = Arc::new(AtomicBool::new(true));
         let running_clone = running.clone();
         
         
         let test_duration = config.duration;
+        let abort = config.abort.clone();
         let timer_thread = thread::spawn(move || {
-            thread::sleep(test_duration);
-            let mut running = running_clone.lock().unwrap();
-            *running = false;
+            abort.sleep(test_duration);
+            running_clone.store(false, Ordering::Relaxed);
         });
         
         
@@ -97,55 +81,64 @@ impl BurnInTest for CpuStressTest {
                 let utilization = utilization.clone();
                 let throttling_events = throttling_events.clone();
                 let instructions_per_sec = instructions_per_sec.clone();
+                let simd_errors = simd_errors.clone();
+                
+                // Vector kernels come first so that even a single worker
+                // exercises the widest SIMD unit the CPU supports.
+                let workload_type = id as usize % workload_count;
+                let mut simd_workload = simd_kernels.get(workload_type)
+                    .and_then(|kernel| SimdWorkload::new(*kernel).ok());
+                let workload_type = workload_type.saturating_sub(simd_kernels.len());
                 
                 thread::spawn(move || {
                     
-                    let workload_type = id % 6;
-                    
                     let mut local_instructions = 0u64;
-                    let start = Instant::now();
+                    let mut last_report = Instant::now();
                     
-                    while *running.lock().unwrap() {
-                        match workload_type {
-                            0 => {
+                    while running.load(Ordering::Relaxed) {
+                        if let Some(workload) = simd_workload.as_mut() {
+                            if !workload.run() {
+                                simd_errors.fetch_add(1, Ordering::Relaxed);
+                            }
+                            local_instructions += 1000;
+                        } else {
+                            match workload_type {
+                                0 => {
                                 
-                                for n in 2..10000 {
-                                    if is_prime(n) {
-                                        local_instructions += 1;
-                                    }
-                                }
-                            },
-                            1 => {
+                                    local_instructions += black_box(prime_search());
+                                },
+                                1 => {
                                 
-                                matrix_operations();
-                                local_instructions += 1000;
-                            },
-                            2 => {
+                                    black_box(matrix_operations());
+                                    local_instructions += 1000;
+                                },
+                                2 => {
                                 
-                                floating_point_ops();
-                                local_instructions += 1000;
-                            },
-                            3 => {
+                                    black_box(floating_point_ops());
+                                    local_instructions += 1000;
+                                },
+                                3 => {
                                 
-                                integer_arithmetic();
-                                local_instructions += 1000;
-                            },
-                            4 => {
+                                    black_box(integer_arithmetic());
+                                    local_instructions += 1000;
+                                },
+                                4 => {
                                 
-                                branch_prediction();
-                                local_instructions += 1000;
-                            },
-                            _ => {
+                                    black_box(branch_prediction());
+                                    local_instructions += 1000;
+                                },
+                                _ => {
                                 
-                                mixed_workload();
-                                local_instructions += 1000;
+                                    black_box(mixed_workload());
+                                    local_instructions += 1000;
+                                }
                             }
                         }
                         
                         
-                        if start.elapsed().as_secs() >= 1 {
-                            let mut instr = instructions_per_sec.lock().unwrap();
-                            *instr += local_instructions;
+                        if last_report.elapsed().as_secs() >= 1 {
+                            last_report = Instant::now();
+                            instructions_per_sec.fetch_add(local_instructions, Ordering::Relaxed);
                             local_instructions = 0;
                             
                             
@@ -155,15 +148,15 @@ impl BurnInTest for CpuStressTest {
                             let max_freq = system.global_cpu_info().frequency() as f64;
                             
                             if current_freq < max_freq * 0.9 {
-                                let mut throttle = throttling_events.lock().unwrap();
-                                *throttle += 1;
+                                throttling_events.fetch_add(1, Ordering::Relaxed);
                             }
                             
                             
-                            let mut util = utilization.lock().unwrap();
-                            *util = system.global_cpu_info().cpu_usage();
+                            utilization.store(system.global_cpu_info().cpu_usage().to_bits(), Ordering::Relaxed);
                         }
                     }
+                    
+                    instructions_per_sec.fetch_add(local_instructions, Ordering::Relaxed);
                 })
             })
             .collect();
@@ -177,25 +170,49 @@ impl BurnInTest for CpuStressTest {
         let _ = timer_thread.join();
         
         
-        let final_utilization = *utilization.lock().unwrap();
-        let final_throttling_events = *throttling_events.lock().unwrap();
-        let final_instructions = *instructions_per_sec.lock().unwrap();
+        let final_utilization = f32::from_bits(utilization.load(Ordering::Relaxed));
+        let final_throttling_events = throttling_events.load(Ordering::Relaxed);
+        let final_instructions = instructions_per_sec.load(Ordering::Relaxed);
+        let final_simd_errors = simd_errors.load(Ordering::Relaxed);
         
         
-        let mut score = 100;
+        let mut scorecard = Scorecard::new();
         
         
-        if final_throttling_events > 0 {
-            score -= (final_throttling_events as u8).min(20);
-        }
+        scorecard.deduct(format!("{} thermal throttling events", final_throttling_events), final_throttling_events as f64, 20);
         
         
-        if final_utilization < 90.0 {
-            score -= ((90.0 - final_utilization) / 2.0) as u8;
+        scorecard.below("avg_cpu_utilization", final_utilization as f64, 90.0, 0.5, 45);
+        
+        
+        scorecard.deduct(format!("{} cache hierarchy issues", hierarchy_issues.len()), hierarchy_issues.len() as f64 * 5.0, 10);
+        
+        
+        if final_simd_errors > 0 {
+            scorecard.zero(format!("{} vector unit computation errors", final_simd_errors));
         }
+        let (score, deductions) = scorecard.finish();
+        
         
+        let mut issues = hierarchy_issues;
         
-        let mut issues = Vec::new();
+        if cache_sizes.is_empty() {
+            issues.push(TestIssue {
+                component: "cpu".to_string(),
+                severity: IssueSeverity::Low,
+                message: "CPU cache topology is not reported by the system".to_string(),
+                action: Some("Check that sysfs is mounted and the CPU cache driver is loaded".to_string()),
+            });
+        }
+        
+        if final_simd_errors > 0 {
+            issues.push(TestIssue {
+                component: "cpu".to_string(),
+                severity: IssueSeverity::Critical,
+                message: format!("Vector unit computation errors detected ({} errors)", final_simd_errors),
+                action: Some("Check CPU core voltage and cooling, and consider replacing the CPU".to_string()),
+            });
+        }
         
         if final_throttling_events > 5 {
             issues.push(TestIssue {
@@ -229,8 +246,16 @@ impl BurnInTest for CpuStressTest {
                 "avg_cpu_utilization": final_utilization,
                 "instructions_per_second": final_instructions,
                 "thermal_throttling_events": final_throttling_events,
+                "simd_errors": final_simd_errors,
+                "cache_hierarchy": hierarchy,
+                "simd_kernels_active": simd_kernels.iter().map(|k| k.name()).collect::<Vec<_>>(),
+                "simd_kernels_skipped": skipped_kernels.iter().map(|(kernel, reason)| json!({
+                    "kernel": kernel.name(),
+                    "reason": reason,
+                })).collect::<Vec<_>>(),
             }),
             issues,
+            deductions,
         };
         
         Ok(result)
@@ -244,20 +269,21 @@ impl BurnInTest for CpuStressTest {
 
 
 
-fn is_prime(n: u32) -> bool {
+/// Trial-division primality check used by the prime workload.
+pub fn is_prime(n: u32) -> bool {
     if n <= 1 {
         return false;
     }
     if n <= 3 {
         return true;
     }
-    if n % 2 == 0 || n % 3 == 0 {
+    if n.is_multiple_of(2) || n.is_multiple_of(3) {
         return false;
     }
     
     let mut i = 5;
     while i * i <= n {
-        if n % i == 0 || n % (i + 2) == 0 {
+        if n.is_multiple_of(i) || n.is_multiple_of(i + 2) {
             return false;
         }
         i += 6;
@@ -266,7 +292,13 @@ fn is_prime(n: u32) -> bool {
     true
 }
 
-fn matrix_operations() {
+/// Counts the primes below 10000.
+pub fn prime_search() -> u64 {
+    (2..10000).filter(|&n| is_prime(n)).count() as u64
+}
+
+/// Multiplies two 100x100 matrices and returns the sum of the product.
+pub fn matrix_operations() -> f64 {
     
     const SIZE: usize = 100;
     let a = vec![vec![1.0; SIZE]; SIZE];
@@ -280,17 +312,22 @@ fn matrix_operations() {
             }
         }
     }
+    
+    c.iter().flatten().sum()
 }
 
-fn floating_point_ops() {
+/// Runs a chain of dependent transcendental operations.
+pub fn floating_point_ops() -> f64 {
     
     let mut x: f64 = 1.0;
     for _ in 0..100000 {
-        x = x.sin().cos().tan().exp().ln().sqrt();
+        x = black_box(x).sin().cos().tan().exp().ln().sqrt();
     }
+    x
 }
 
-fn integer_arithmetic() {
+/// Sums a vector of alternating signs computed with integer arithmetic.
+pub fn integer_arithmetic() -> i64 {
     // Integer operations
     let mut v = vec![0; 10000];
     
@@ -300,19 +337,21 @@ fn integer_arithmetic() {
     }
     
     // Process vector
-    let mut _sum = 0i64;
+    let mut sum = 0i64;
     for (i, &val) in v.iter().enumerate() {
         if val == 1 {
-            _sum += i as i64; // Using i64 to avoid overflow
+            sum += i as i64; // Using i64 to avoid overflow
         } else {
-            _sum -= i as i64; // Using i64 to avoid overflow
+            sum -= i as i64; // Using i64 to avoid overflow
         }
     }
+    sum
 }
 
-fn branch_prediction() {
+/// Sums a vector through a data-dependent branch.
+pub fn branch_prediction() -> i64 {
     // Test branch prediction effects
-    let mut _sum: i64 = 0; // Using i64 to avoid overflow
+    let mut sum: i64 = 0; // Using i64 to avoid overflow
     let mut v = vec![0; 10000];
     
     // Initialize vector with pattern
@@ -321,18 +360,33 @@ fn branch_prediction() {
     }
     
     // Process vector
-    for (i, &val) in v.iter().enumerate() {
+    for (i, &val) in black_box(&v).iter().enumerate() {
         if val == 1 {
-            _sum += i as i64; // Using i64 to avoid overflow
+            sum += i as i64; // Using i64 to avoid overflow
         } else {
-            _sum -= i as i64; // Using i64 to avoid overflow
+            sum -= i as i64; // Using i64 to avoid overflow
         }
     }
+    sum
 }
 
-fn mixed_workload() {
+/// Runs a little of each scalar workload.
+pub fn mixed_workload() -> f64 {
     
-    is_prime(9973);
-    floating_point_ops();
-    integer_arithmetic();
+    let prime = is_prime(black_box(9973)) as u8 as f64;
+    prime + floating_point_ops() + integer_arithmetic() as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_scalar_kernels() {
+        assert!(is_prime(9973));
+        assert!(!is_prime(9975));
+        assert_eq!(prime_search(), 1229);
+        assert_eq!(matrix_operations(), 100.0 * 100.0 * 200.0);
+        assert_eq!(integer_arithmetic(), branch_prediction());
+    }
 }
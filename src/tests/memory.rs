@@ -1,14 +1,19 @@
 use std::time::{Duration, Instant};
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
 use std::thread;
-use rand::{Rng, SeedableRng};
+use std::hint::black_box;
+use rand::{RngCore, SeedableRng};
 use rand::rngs::StdRng;
 use serde_json::json;
 
 use crate::core::test::{BurnInTest, TestResult, TestStatus, TestIssue, IssueSeverity};
 use crate::core::config::TestConfig;
-use crate::core::hardware::HardwareInfo;
+use crate::core::hardware::{self, HardwareInfo};
 use crate::core::error::Result;
+use crate::core::abort::AbortSignal;
+use crate::core::cgroup;
+use crate::core::scoring::Scorecard;
 
 /// Memory validation test
 pub struct MemoryValidationTest;
@@ -19,9 +24,7 @@ impl BurnInTest for MemoryValidationTest {
     }
     
     fn detect_hardware(&self) -> Result<HardwareInfo> {
-        
-        let cpu_test = crate::tests::cpu::CpuStressTest;
-        cpu_test.detect_hardware()
+        Ok(hardware::detect())
     }
     
     fn estimate_duration(&self, config: &TestConfig) -> Duration {
@@ -35,13 +38,13 @@ impl BurnInTest for MemoryValidationTest {
         let mut system = sysinfo::System::new();
         system.refresh_memory();
         
-        let available_memory = system.available_memory();
+        let available_memory = cgroup::available_memory(system.available_memory());
         let test_size = (available_memory as f64 * (config.memory_test_size_percent as f64 / 100.0)) as usize;
         
-        println!("Starting memory validation test using {} bytes", test_size);
+        log::info!("Starting memory validation test using {} bytes", test_size);
         
         
-        let error_count = Arc::new(Mutex::new(0));
+        let error_count = Arc::new(AtomicUsize::new(0));
         let bandwidth_mbps = Arc::new(Mutex::new(0.0));
         let latency_ns = Arc::new(Mutex::new(0.0));
         
@@ -54,35 +57,36 @@ impl BurnInTest for MemoryValidationTest {
         ];
         
         
-        let seq_result = test_sequential_access(test_size, &patterns, bandwidth_mbps.clone())?;
+        let seq_result = test_sequential_access(test_size, &patterns, &config.abort, bandwidth_mbps.clone())?;
         
         
-        let random_result = test_random_access(test_size, &patterns, latency_ns.clone())?;
+        let random_result = test_random_access(test_size, &patterns, &config.abort, latency_ns.clone())?;
         
         
-        let walking_result = test_walking_bits(test_size, error_count.clone())?;
+        let walking_result = test_walking_bits(test_size, &config.abort, error_count.clone())?;
         
         
         let thread_result = test_multithreaded_access(test_size, config, error_count.clone())?;
         
         
-        let final_error_count = *error_count.lock().unwrap();
+        let final_error_count = error_count.load(Ordering::Relaxed);
         let final_bandwidth = *bandwidth_mbps.lock().unwrap();
         let final_latency = *latency_ns.lock().unwrap();
         
         
-        let mut score = 100;
+        let mut scorecard = Scorecard::new();
         
         
         if final_error_count > 0 {
-            score = 0; 
+            scorecard.zero(format!("{} memory errors", final_error_count));
         }
         
         
         
-        if final_bandwidth < 1000.0 {
-            score -= ((1000.0 - final_bandwidth) / 100.0).min(20.0) as u8;
+        if config.expected_range(self.name(), "bandwidth_mbps").is_none() {
+            scorecard.below("bandwidth_mbps", final_bandwidth, 1000.0, 0.01, 20);
         }
+        let (score, deductions) = scorecard.finish();
         
         
         let mut issues = Vec::new();
@@ -149,6 +153,7 @@ impl BurnInTest for MemoryValidationTest {
                 "test_size_bytes": test_size,
             }),
             issues,
+            deductions,
         };
         
         Ok(result)
@@ -166,6 +171,7 @@ impl BurnInTest for MemoryValidationTest {
 fn test_sequential_access(
     size: usize,
     patterns: &[u8],
+    abort: &AbortSignal,
     bandwidth: Arc<Mutex<f64>>,
 ) -> Result<bool> {
     
@@ -174,26 +180,16 @@ fn test_sequential_access(
     let mut success = true;
     
     for &pattern in patterns {
-        
-        let write_start = Instant::now();
-        for val in memory.iter_mut() {
-            *val = pattern;
+        if abort.is_triggered() {
+            break;
         }
-        let write_time = write_start.elapsed();
         
-        
-        let read_start = Instant::now();
-        for val in &memory {
-            if *val != pattern {
-                success = false;
-                break;
-            }
-        }
-        let read_time = read_start.elapsed();
+        let start = Instant::now();
+        success &= pattern_pass(&mut memory, pattern) == 0;
+        let total_time = start.elapsed();
         
         
         let total_bytes = size * 2; 
-        let total_time = write_time + read_time;
         let mbps = (total_bytes as f64 / 1_000_000.0) / total_time.as_secs_f64();
         
         let mut bw = bandwidth.lock().unwrap();
@@ -206,39 +202,25 @@ fn test_sequential_access(
 fn test_random_access(
     size: usize,
     patterns: &[u8],
+    abort: &AbortSignal,
     latency: Arc<Mutex<f64>>,
 ) -> Result<bool> {
     
     let mut memory = vec![0; size];
     
-    
-    let mut rng = StdRng::seed_from_u64(42); 
-    let mut indices: Vec<usize> = (0..size).collect();
-    indices.shuffle(&mut rng);
-    
     let mut success = true;
     
     for &pattern in patterns {
-        
-        let write_start = Instant::now();
-        for &i in &indices {
-            memory[i] = pattern;
+        if abort.is_triggered() {
+            break;
         }
-        let write_time = write_start.elapsed();
         
-        
-        let read_start = Instant::now();
-        for &i in &indices {
-            if memory[i] != pattern {
-                success = false;
-                break;
-            }
-        }
-        let read_time = read_start.elapsed();
+        let start = Instant::now();
+        success &= random_pattern_pass(&mut memory, 42, pattern) == 0;
+        let total_time = start.elapsed();
         
         
-        let total_ops = indices.len() * 2; 
-        let total_time = write_time + read_time;
+        let total_ops = size * 2; 
         let ns_per_op = (total_time.as_nanos() as f64) / (total_ops as f64);
         
         let mut lat = latency.lock().unwrap();
@@ -250,145 +232,189 @@ fn test_random_access(
 
 fn test_walking_bits(
     size: usize,
-    error_count: Arc<Mutex<usize>>,
+    abort: &AbortSignal,
+    error_count: Arc<AtomicUsize>,
 ) -> Result<bool> {
     
     let mut memory = vec![0; size];
     
-    let mut success = true;
+    let mut errors = 0;
     
     
-    for bit in 0..8 {
-        let pattern = 1 << bit;
-        
-        
-        let write_start = Instant::now();
-        for val in memory.iter_mut() {
-            *val = pattern;
-        }
-        let _write_duration = write_start.elapsed();
-        
-        let _read_start = Instant::now();
-        for val in &memory {
-            if *val != pattern {
-                let mut errors = error_count.lock().unwrap();
-                *errors += 1;
-                success = false;
-            }
-        }
+    for bit in (0..8).take_while(|_| !abort.is_triggered()) {
+        errors += pattern_pass(&mut memory, 1 << bit);
     }
     
     
-    for bit in 0..8 {
-        let pattern = !(1 << bit) & 0xFF;
-        
-        
-        let write_start = Instant::now();
-        for val in memory.iter_mut() {
-            *val = pattern;
-        }
-        let _write_duration = write_start.elapsed();
-        
-        let _read_start = Instant::now();
-        let mut checksum = 0u64;
-        for val in &memory {
-            checksum = checksum.wrapping_add(*val as u64);
-        }
+    for bit in (0..8).take_while(|_| !abort.is_triggered()) {
+        errors += pattern_pass(&mut memory, !(1u8 << bit));
     }
     
-    Ok(success)
+    error_count.fetch_add(errors, Ordering::Relaxed);
+    
+    Ok(errors == 0)
+}
+
+/// Fills `memory` with `pattern` and reads it back.
+///
+/// Returns the number of bytes that did not hold the pattern.
+pub fn pattern_pass(memory: &mut [u8], pattern: u8) -> usize {
+    memory.fill(pattern);
+    
+    // Keep the compiler from assuming the fill is still what memory holds.
+    let memory = black_box(memory);
+    memory.iter().filter(|&&val| val != pattern).count()
+}
+
+/// Writes `pattern` to `memory.len()` pseudo-random locations and reads the
+/// same locations back in the same order.
+///
+/// The locations are derived from `seed`, so no index table is needed and
+/// the pass works on buffers as large as available memory. Returns the number
+/// of locations that did not hold the pattern.
+pub fn random_pattern_pass(memory: &mut [u8], seed: u64, pattern: u8) -> usize {
+    let len = memory.len() as u64;
+    if len == 0 {
+        return 0;
+    }
+    
+    let next = |state: &mut u64| {
+        *state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+        ((*state as u128 * len as u128) >> 64) as usize
+    };
+    
+    let mut state = seed;
+    for _ in 0..len {
+        memory[next(&mut state)] = pattern;
+    }
+    
+    let memory = black_box(memory);
+    let mut state = seed;
+    let mut errors = 0;
+    for _ in 0..len {
+        if memory[next(&mut state)] != pattern {
+            errors += 1;
+        }
+    }
+    errors
 }
 
 fn test_multithreaded_access(
     size: usize,
     config: &TestConfig,
-    error_count: Arc<Mutex<usize>>,
+    error_count: Arc<AtomicUsize>,
 ) -> Result<bool> {
     let thread_count = if config.threads == 0 {
-        num_cpus::get() as u32
+        cgroup::available_cpus()
     } else {
-        config.threads
+        config.threads as usize
     };
     
     
-    let memory = Arc::new(Mutex::new(vec![0; size]));
+    let mut memory = vec![0; size];
     
     
-    let running = Arc::new(Mutex::new(true));
+    let running = Arc::new(AtomicBool::new(true));
     let running_clone = running.clone();
     
     
     let test_duration = config.duration / 4; 
+    let abort = config.abort.clone();
     let timer_thread = thread::spawn(move || {
-        thread::sleep(test_duration);
-        let mut running = running_clone.lock().unwrap();
-        *running = false;
+        abort.sleep(test_duration);
+        running_clone.store(false, Ordering::Relaxed);
     });
     
     
-    let handles: Vec<_> = (0..thread_count)
-        .map(|id| {
-            let memory = memory.clone();
-            let running = running.clone();
+    let errors = sharded_fill_verify(&mut memory, thread_count, |_| running.load(Ordering::Relaxed));
+    error_count.fetch_add(errors, Ordering::Relaxed);
+    
+    
+    let _ = timer_thread.join();
+    
+    
+    Ok(errors == 0)
+}
+
+/// Splits `memory` into one shard per thread and has every thread
+/// repeatedly fill its own shard with a seeded random stream and verify it.
+///
+/// Shards are disjoint, so threads never contend for a lock. Each thread
+/// keeps going while `keep_going(pass)` returns `true`. Returns the number of
+/// bytes that did not read back as written.
+pub fn sharded_fill_verify<F>(memory: &mut [u8], threads: usize, keep_going: F) -> usize
+where
+    F: Fn(usize) -> bool + Sync,
+{
+    let shard_size = memory.len().div_ceil(threads.max(1)).max(1);
+    let errors = AtomicUsize::new(0);
+    
+    thread::scope(|scope| {
+        for (id, shard) in memory.chunks_mut(shard_size).enumerate() {
+            let errors = &errors;
+            let keep_going = &keep_going;
             
-            thread::spawn(move || {
-                let mut rng = StdRng::seed_from_u64(id as u64);
-                let chunk_size = size / thread_count as usize;
-                let start = id as usize * chunk_size;
-                let end = if id == thread_count - 1 {
-                    size
-                } else {
-                    (id as usize + 1) * chunk_size
-                };
+            scope.spawn(move || {
+                let mut expected = [0u8; 4096];
+                let mut pass = 0;
                 
-                while *running.lock().unwrap() {
+                while keep_going(pass) {
+                    let seed = ((id as u64) << 32) | pass as u64;
+                    StdRng::seed_from_u64(seed).fill_bytes(shard);
                     
-                    {
-                        let mut mem = memory.lock().unwrap();
-                        for val in mem[start..end].iter_mut() {
-                            *val = rng.gen();
+                    let mut rng = StdRng::seed_from_u64(seed);
+                    for chunk in shard.chunks(expected.len()) {
+                        let expected = &mut expected[..chunk.len()];
+                        rng.fill_bytes(expected);
+                        let mismatches = chunk.iter().zip(expected.iter()).filter(|(a, b)| a != b).count();
+                        if mismatches > 0 {
+                            errors.fetch_add(mismatches, Ordering::Relaxed);
                         }
                     }
                     
-                    
-                    thread::sleep(Duration::from_micros(10));
-                    
-                    
-                    {
-                        let mem = memory.lock().unwrap();
-                        for val in &mem[start..end] {
-                            
-                            let _ = val;
-                        }
-                    }
+                    pass += 1;
                 }
-            })
-        })
-        .collect();
-    
-    
-    for handle in handles {
-        let _ = handle.join();
-    }
-    
-    
-    let _ = timer_thread.join();
-    
+            });
+        }
+    });
     
-    let errors = *error_count.lock().unwrap();
-    Ok(errors == 0)
+    errors.into_inner()
 }
 
 
-trait SliceExt {
-    fn shuffle<R: Rng>(&mut self, rng: &mut R);
-}
+#[cfg(test)]
+mod tests {
+    use super::*;
 
-impl<T> SliceExt for [T] {
-    fn shuffle<R: Rng>(&mut self, rng: &mut R) {
-        for i in (1..self.len()).rev() {
-            let j = rng.gen_range(0..=i);
-            self.swap(i, j);
+    #[test]
+    fn test_sharded_fill_verify_covers_every_shard() {
+        let mut memory = vec![0u8; 1024 * 1024 + 3];
+        let errors = sharded_fill_verify(&mut memory, 4, |pass| pass < 2);
+        assert_eq!(errors, 0);
+        
+        for shard in memory.chunks(memory.len().div_ceil(4)) {
+            assert!(shard.iter().any(|b| *b != 0));
         }
     }
+
+    #[test]
+    fn test_pattern_pass() {
+        let mut memory = vec![0u8; 4096];
+        assert_eq!(pattern_pass(&mut memory, 0xA5), 0);
+        assert!(memory.iter().all(|b| *b == 0xA5));
+    }
+
+    #[test]
+    fn test_random_pattern_pass() {
+        let mut memory = vec![0u8; 4096];
+        assert_eq!(random_pattern_pass(&mut memory, 42, 0x5A), 0);
+        assert!(memory.contains(&0x5A));
+        assert_eq!(random_pattern_pass(&mut [], 42, 0x5A), 0);
+    }
+
+    #[test]
+    fn test_sharded_fill_verify_more_threads_than_bytes() {
+        let mut memory = vec![0u8; 3];
+        assert_eq!(sharded_fill_verify(&mut memory, 8, |pass| pass < 1), 0);
+    }
 }
@@ -3,3 +3,5 @@ pub mod memory;
 pub mod storage;
 pub mod thermal;
 pub mod network;
+pub mod simd;
+pub mod cache;
@@ -3,10 +3,12 @@ use std::net::TcpStream;
 use std::sync::{Arc, Mutex};
 use serde_json::json;
 
+use crate::core::abort::AbortSignal;
 use crate::core::test::{BurnInTest, TestResult, TestStatus, TestIssue, IssueSeverity};
 use crate::core::config::TestConfig;
-use crate::core::hardware::HardwareInfo;
+use crate::core::hardware::{self, HardwareInfo};
 use crate::core::error::{Result, BurnInError};
+use crate::core::scoring::Scorecard;
 
 
 pub struct NetworkTest;
@@ -17,8 +19,7 @@ impl BurnInTest for NetworkTest {
     }
     
     fn detect_hardware(&self) -> Result<HardwareInfo> {
-        
-        crate::tests::cpu::CpuStressTest.detect_hardware()
+        Ok(hardware::detect())
     }
     
     fn estimate_duration(&self, config: &TestConfig) -> Duration {
@@ -38,10 +39,11 @@ impl BurnInTest for NetworkTest {
                 duration: Duration::from_secs(0),
                 metrics: json!({}),
                 issues: Vec::new(),
+                deductions: Vec::new(),
             });
         }
         
-        println!("Starting network test");
+        log::info!("Starting network test");
         
         
         let latency_ms = Arc::new(Mutex::new(0.0));
@@ -53,16 +55,22 @@ impl BurnInTest for NetworkTest {
         
         
         
-        let _latency_result = test_latency(latency_ms.clone())?;
+        let _latency_result = test_latency(latency_ms.clone(), &config.abort)?;
         
         
-        let _download_result = test_download_speed(download_mbps.clone())?;
+        if !config.abort.is_triggered() {
+            let _download_result = test_download_speed(download_mbps.clone())?;
+        }
         
         
-        let _upload_result = test_upload_speed(upload_mbps.clone())?;
+        if !config.abort.is_triggered() {
+            let _upload_result = test_upload_speed(upload_mbps.clone())?;
+        }
         
         
-        let _packet_loss_result = test_packet_loss(packet_loss.clone())?;
+        if !config.abort.is_triggered() {
+            let _packet_loss_result = test_packet_loss(packet_loss.clone())?;
+        }
         
         
         let final_latency = *latency_ms.lock().unwrap();
@@ -72,29 +80,28 @@ impl BurnInTest for NetworkTest {
         let final_error_count = *error_count.lock().unwrap();
         
         
-        let mut score = 100;
+        let mut scorecard = Scorecard::new();
         
         
-        if final_latency > 100.0 {
-            score -= ((final_latency - 100.0) / 10.0).min(20.0) as u8;
+        if config.expected_range(self.name(), "latency_ms").is_none() {
+            scorecard.above("latency_ms", final_latency, 100.0, 0.1, 20);
         }
         
         
-        if final_download < 10.0 {
-            score -= ((10.0 - final_download) / 1.0).min(20.0) as u8;
+        if config.expected_range(self.name(), "download_mbps").is_none() {
+            scorecard.below("download_mbps", final_download, 10.0, 1.0, 20);
         }
         
-        if final_upload < 5.0 {
-            score -= ((5.0 - final_upload) / 0.5).min(10.0) as u8;
+        if config.expected_range(self.name(), "upload_mbps").is_none() {
+            scorecard.below("upload_mbps", final_upload, 5.0, 2.0, 10);
         }
         
         
-        if final_packet_loss > 1.0 {
-            score -= ((final_packet_loss - 1.0) * 5.0).min(30.0) as u8;
-        }
+        scorecard.above("packet_loss_percent", final_packet_loss, 1.0, 5.0, 30);
         
         
-        score -= (final_error_count as u8 * 5).min(20);
+        scorecard.deduct(format!("{} network errors", final_error_count), final_error_count as f64 * 5.0, 20);
+        let (score, deductions) = scorecard.finish();
         
         
         let mut issues = Vec::new();
@@ -144,6 +151,7 @@ impl BurnInTest for NetworkTest {
                 "error_count": final_error_count,
             }),
             issues,
+            deductions,
         };
         
         Ok(result)
@@ -157,7 +165,7 @@ impl BurnInTest for NetworkTest {
 
 
 
-fn test_latency(latency_ms: Arc<Mutex<f64>>) -> Result<bool> {
+fn test_latency(latency_ms: Arc<Mutex<f64>>, abort: &AbortSignal) -> Result<bool> {
     
     let hosts = [
         "8.8.8.8:443",   
@@ -169,6 +177,9 @@ fn test_latency(latency_ms: Arc<Mutex<f64>>) -> Result<bool> {
     let mut successful_pings = 0;
     
     for host in &hosts {
+        if abort.is_triggered() {
+            break;
+        }
         
         let start = Instant::now();
         match TcpStream::connect(host) {
@@ -189,6 +200,8 @@ fn test_latency(latency_ms: Arc<Mutex<f64>>) -> Result<bool> {
         let mut latency = latency_ms.lock().unwrap();
         *latency = avg_latency;
         Ok(true)
+    } else if abort.is_triggered() {
+        Ok(false)
     } else {
         Err(BurnInError::TestExecutionError("Failed to connect to any hosts for latency test".to_string()))
     }
@@ -0,0 +1,425 @@
+/// Dimension of the square matrices used by the GEMM kernels.
+const GEMM_SIZE: usize = 64;
+
+/// Size of the input buffer hashed by the hashing kernels.
+const HASH_INPUT_BYTES: usize = 16 * 1024;
+
+/// Number of passes over the input buffer per hashing run.
+const HASH_ROUNDS: usize = 16;
+
+const HASH_SEED: u64 = 0x243F_6A88_85A3_08D3;
+const HASH_PRIME: u64 = 0x9E37_79B1;
+
+/// A vector-unit stress kernel.
+///
+/// The SIMD units draw the most power and are the first to fail under
+/// marginal voltage, so every run is checked against a scalar reference.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SimdKernel {
+    /// Fused multiply-add matrix multiplication on 512-bit vectors.
+    Avx512FmaGemm,
+    /// Fused multiply-add matrix multiplication on 256-bit vectors.
+    Avx2FmaGemm,
+    /// Fused multiply-add matrix multiplication on 128-bit NEON vectors.
+    NeonFmaGemm,
+    /// Multiply/xor-shift hashing on 512-bit vectors.
+    Avx512Hash,
+    /// Multiply/xor-shift hashing on 256-bit vectors.
+    Avx2Hash,
+    /// Multiply/xor-shift hashing on 128-bit NEON vectors.
+    NeonHash,
+}
+
+impl SimdKernel {
+    /// All kernels, widest first.
+    pub const ALL: [SimdKernel; 6] = [
+        SimdKernel::Avx512FmaGemm,
+        SimdKernel::Avx2FmaGemm,
+        SimdKernel::NeonFmaGemm,
+        SimdKernel::Avx512Hash,
+        SimdKernel::Avx2Hash,
+        SimdKernel::NeonHash,
+    ];
+
+    /// Returns the name of the kernel as reported in metrics.
+    pub fn name(&self) -> &'static str {
+        match self {
+            SimdKernel::Avx512FmaGemm => "avx512_fma_gemm",
+            SimdKernel::Avx2FmaGemm => "avx2_fma_gemm",
+            SimdKernel::NeonFmaGemm => "neon_fma_gemm",
+            SimdKernel::Avx512Hash => "avx512_hash",
+            SimdKernel::Avx2Hash => "avx2_hash",
+            SimdKernel::NeonHash => "neon_hash",
+        }
+    }
+
+    /// Checks whether the running CPU can execute this kernel.
+    ///
+    /// Returns the reason the kernel has to be skipped when it cannot.
+    pub fn check_support(&self) -> std::result::Result<(), String> {
+        match self {
+            SimdKernel::Avx512FmaGemm | SimdKernel::Avx512Hash => x86_support(&["avx512f"]),
+            SimdKernel::Avx2FmaGemm => x86_support(&["avx2", "fma"]),
+            SimdKernel::Avx2Hash => x86_support(&["avx2"]),
+            SimdKernel::NeonFmaGemm | SimdKernel::NeonHash => neon_support(),
+        }
+    }
+
+    fn hash_lanes(&self) -> usize {
+        match self {
+            SimdKernel::Avx512Hash => 8,
+            SimdKernel::Avx2Hash => 4,
+            _ => 2,
+        }
+    }
+
+    fn is_gemm(&self) -> bool {
+        matches!(self, SimdKernel::Avx512FmaGemm | SimdKernel::Avx2FmaGemm | SimdKernel::NeonFmaGemm)
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+fn x86_support(features: &[&str]) -> std::result::Result<(), String> {
+    let missing: Vec<&str> = features.iter()
+        .copied()
+        .filter(|feature| !crate::core::cpu_features::is_x86_feature_enabled(feature))
+        .collect();
+
+    if missing.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("CPU does not support {}", missing.join(", ")))
+    }
+}
+
+#[cfg(not(target_arch = "x86_64"))]
+fn x86_support(_features: &[&str]) -> std::result::Result<(), String> {
+    Err("requires an x86_64 CPU".to_string())
+}
+
+#[cfg(target_arch = "aarch64")]
+fn neon_support() -> std::result::Result<(), String> {
+    if std::arch::is_aarch64_feature_detected!("neon") {
+        Ok(())
+    } else {
+        Err("CPU does not support neon".to_string())
+    }
+}
+
+#[cfg(not(target_arch = "aarch64"))]
+fn neon_support() -> std::result::Result<(), String> {
+    Err("requires an aarch64 CPU".to_string())
+}
+
+/// Splits all kernels into those supported by this CPU and those that have
+/// to be skipped, together with the reason for skipping.
+pub fn select_kernels() -> (Vec<SimdKernel>, Vec<(SimdKernel, String)>) {
+    let mut supported = Vec::new();
+    let mut skipped = Vec::new();
+
+    for kernel in SimdKernel::ALL {
+        match kernel.check_support() {
+            Ok(()) => supported.push(kernel),
+            Err(reason) => skipped.push((kernel, reason)),
+        }
+    }
+
+    (supported, skipped)
+}
+
+/// A kernel together with its working buffers and expected output.
+pub struct SimdWorkload {
+    kernel: SimdKernel,
+    a: Vec<f64>,
+    b: Vec<f64>,
+    c: Vec<f64>,
+    expected_c: Vec<f64>,
+    data: Vec<u8>,
+    expected_hash: u64,
+}
+
+impl SimdWorkload {
+    /// Prepares a workload for `kernel`, computing the expected result with
+    /// the scalar reference implementation.
+    pub fn new(kernel: SimdKernel) -> std::result::Result<Self, String> {
+        kernel.check_support()?;
+
+        let mut workload = Self {
+            kernel,
+            a: Vec::new(),
+            b: Vec::new(),
+            c: Vec::new(),
+            expected_c: Vec::new(),
+            data: Vec::new(),
+            expected_hash: 0,
+        };
+
+        if kernel.is_gemm() {
+            let n = GEMM_SIZE;
+            // Small integers keep every product and sum exact in f64, so the
+            // vector result must match the scalar one bit for bit.
+            workload.a = (0..n * n).map(|i| ((i / n + i % n) % 7) as f64).collect();
+            workload.b = (0..n * n).map(|i| ((i / n * 3 + i % n) % 5) as f64).collect();
+            workload.c = vec![0.0; n * n];
+            workload.expected_c = vec![0.0; n * n];
+            gemm_scalar(&workload.a, &workload.b, &mut workload.expected_c, n);
+        } else {
+            let mut state = HASH_SEED;
+            workload.data = (0..HASH_INPUT_BYTES)
+                .map(|_| {
+                    state = state.wrapping_mul(6_364_136_223_846_793_005).wrapping_add(1_442_695_040_888_963_407);
+                    (state >> 56) as u8
+                })
+                .collect();
+            workload.expected_hash = hash_scalar(&workload.data, kernel.hash_lanes(), HASH_ROUNDS);
+        }
+
+        Ok(workload)
+    }
+
+    /// Returns the kernel driven by this workload.
+    pub fn kernel(&self) -> SimdKernel {
+        self.kernel
+    }
+
+    /// Runs the kernel once and returns `true` if the result matched the
+    /// scalar reference.
+    pub fn run(&mut self) -> bool {
+        if self.kernel.is_gemm() {
+            self.c.iter_mut().for_each(|v| *v = 0.0);
+            self.gemm();
+            self.c == self.expected_c
+        } else {
+            self.hash() == self.expected_hash
+        }
+    }
+
+    fn gemm(&mut self) {
+        let n = GEMM_SIZE;
+        // SAFETY: `new` only builds workloads for kernels whose CPU features
+        // were detected at runtime, and all buffers hold `n * n` elements
+        // with `n` a multiple of every kernel's block width.
+        match self.kernel {
+            #[cfg(target_arch = "x86_64")]
+            SimdKernel::Avx512FmaGemm => unsafe { gemm_avx512(&self.a, &self.b, &mut self.c, n) },
+            #[cfg(target_arch = "x86_64")]
+            SimdKernel::Avx2FmaGemm => unsafe { gemm_avx2(&self.a, &self.b, &mut self.c, n) },
+            #[cfg(target_arch = "aarch64")]
+            SimdKernel::NeonFmaGemm => unsafe { gemm_neon(&self.a, &self.b, &mut self.c, n) },
+            _ => gemm_scalar(&self.a, &self.b, &mut self.c, n),
+        }
+    }
+
+    fn hash(&self) -> u64 {
+        // SAFETY: see `gemm`; the input length is a multiple of 64 bytes.
+        match self.kernel {
+            #[cfg(target_arch = "x86_64")]
+            SimdKernel::Avx512Hash => unsafe { hash_avx512(&self.data, HASH_ROUNDS) },
+            #[cfg(target_arch = "x86_64")]
+            SimdKernel::Avx2Hash => unsafe { hash_avx2(&self.data, HASH_ROUNDS) },
+            #[cfg(target_arch = "aarch64")]
+            SimdKernel::NeonHash => unsafe { hash_neon(&self.data, HASH_ROUNDS) },
+            _ => hash_scalar(&self.data, self.kernel.hash_lanes(), HASH_ROUNDS),
+        }
+    }
+}
+
+/// Reference row-major matrix multiplication, `c += a * b`.
+fn gemm_scalar(a: &[f64], b: &[f64], c: &mut [f64], n: usize) {
+    for i in 0..n {
+        for k in 0..n {
+            let a_ik = a[i * n + k];
+            for j in 0..n {
+                c[i * n + j] += a_ik * b[k * n + j];
+            }
+        }
+    }
+}
+
+/// Reference implementation of the lane-parallel hash computed by the
+/// vector kernels.
+fn hash_scalar(data: &[u8], lanes: usize, rounds: usize) -> u64 {
+    let mut state: Vec<u64> = (0..lanes).map(|i| HASH_SEED ^ i as u64).collect();
+
+    for _ in 0..rounds {
+        for chunk in data.chunks_exact(lanes * 8) {
+            for (lane, word) in state.iter_mut().zip(chunk.chunks_exact(8)) {
+                let x = u64::from_le_bytes(word.try_into().unwrap());
+                let s = *lane ^ x;
+                *lane = ((s & 0xFFFF_FFFF) * HASH_PRIME) ^ (s >> 29);
+            }
+        }
+    }
+
+    fold_lanes(&state)
+}
+
+fn fold_lanes(lanes: &[u64]) -> u64 {
+    lanes.iter().fold(0u64, |acc, lane| acc.rotate_left(7) ^ lane)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2,fma")]
+unsafe fn gemm_avx2(a: &[f64], b: &[f64], c: &mut [f64], n: usize) {
+    use std::arch::x86_64::*;
+
+    for i in 0..n {
+        for j in (0..n).step_by(16) {
+            let mut acc = [_mm256_setzero_pd(); 4];
+            for k in 0..n {
+                let a_ik = _mm256_set1_pd(a[i * n + k]);
+                let row = b.as_ptr().add(k * n + j);
+                for (m, acc) in acc.iter_mut().enumerate() {
+                    *acc = _mm256_fmadd_pd(a_ik, _mm256_loadu_pd(row.add(m * 4)), *acc);
+                }
+            }
+            let out = c.as_mut_ptr().add(i * n + j);
+            for (m, acc) in acc.iter().enumerate() {
+                _mm256_storeu_pd(out.add(m * 4), *acc);
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+unsafe fn gemm_avx512(a: &[f64], b: &[f64], c: &mut [f64], n: usize) {
+    use std::arch::x86_64::*;
+
+    for i in 0..n {
+        for j in (0..n).step_by(32) {
+            let mut acc = [_mm512_setzero_pd(); 4];
+            for k in 0..n {
+                let a_ik = _mm512_set1_pd(a[i * n + k]);
+                let row = b.as_ptr().add(k * n + j);
+                for (m, acc) in acc.iter_mut().enumerate() {
+                    *acc = _mm512_fmadd_pd(a_ik, _mm512_loadu_pd(row.add(m * 8)), *acc);
+                }
+            }
+            let out = c.as_mut_ptr().add(i * n + j);
+            for (m, acc) in acc.iter().enumerate() {
+                _mm512_storeu_pd(out.add(m * 8), *acc);
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn gemm_neon(a: &[f64], b: &[f64], c: &mut [f64], n: usize) {
+    use std::arch::aarch64::*;
+
+    for i in 0..n {
+        for j in (0..n).step_by(8) {
+            let mut acc = [vdupq_n_f64(0.0); 4];
+            for k in 0..n {
+                let a_ik = vdupq_n_f64(a[i * n + k]);
+                let row = b.as_ptr().add(k * n + j);
+                for (m, acc) in acc.iter_mut().enumerate() {
+                    *acc = vfmaq_f64(*acc, vld1q_f64(row.add(m * 2)), a_ik);
+                }
+            }
+            let out = c.as_mut_ptr().add(i * n + j);
+            for (m, acc) in acc.iter().enumerate() {
+                vst1q_f64(out.add(m * 2), *acc);
+            }
+        }
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx2")]
+unsafe fn hash_avx2(data: &[u8], rounds: usize) -> u64 {
+    use std::arch::x86_64::*;
+
+    let init: [u64; 4] = std::array::from_fn(|i| HASH_SEED ^ i as u64);
+    let mut state = _mm256_loadu_si256(init.as_ptr() as *const __m256i);
+    let prime = _mm256_set1_epi64x(HASH_PRIME as i64);
+
+    for _ in 0..rounds {
+        for chunk in data.chunks_exact(32) {
+            let x = _mm256_loadu_si256(chunk.as_ptr() as *const __m256i);
+            let s = _mm256_xor_si256(state, x);
+            state = _mm256_xor_si256(_mm256_mul_epu32(s, prime), _mm256_srli_epi64::<29>(s));
+        }
+    }
+
+    let mut lanes = [0u64; 4];
+    _mm256_storeu_si256(lanes.as_mut_ptr() as *mut __m256i, state);
+    fold_lanes(&lanes)
+}
+
+#[cfg(target_arch = "x86_64")]
+#[target_feature(enable = "avx512f")]
+unsafe fn hash_avx512(data: &[u8], rounds: usize) -> u64 {
+    use std::arch::x86_64::*;
+
+    let init: [u64; 8] = std::array::from_fn(|i| HASH_SEED ^ i as u64);
+    let mut state = _mm512_loadu_si512(init.as_ptr() as *const __m512i);
+    let prime = _mm512_set1_epi64(HASH_PRIME as i64);
+
+    for _ in 0..rounds {
+        for chunk in data.chunks_exact(64) {
+            let x = _mm512_loadu_si512(chunk.as_ptr() as *const __m512i);
+            let s = _mm512_xor_si512(state, x);
+            state = _mm512_xor_si512(_mm512_mul_epu32(s, prime), _mm512_srli_epi64::<29>(s));
+        }
+    }
+
+    let mut lanes = [0u64; 8];
+    _mm512_storeu_si512(lanes.as_mut_ptr() as *mut __m512i, state);
+    fold_lanes(&lanes)
+}
+
+#[cfg(target_arch = "aarch64")]
+#[target_feature(enable = "neon")]
+unsafe fn hash_neon(data: &[u8], rounds: usize) -> u64 {
+    use std::arch::aarch64::*;
+
+    let init: [u64; 2] = [HASH_SEED, HASH_SEED ^ 1];
+    let mut state = vld1q_u64(init.as_ptr());
+    let prime = vdup_n_u32(HASH_PRIME as u32);
+
+    for _ in 0..rounds {
+        for chunk in data.chunks_exact(16) {
+            let x = vreinterpretq_u64_u8(vld1q_u8(chunk.as_ptr()));
+            let s = veorq_u64(state, x);
+            state = veorq_u64(vmull_u32(vmovn_u64(s), prime), vshrq_n_u64::<29>(s));
+        }
+    }
+
+    let mut lanes = [0u64; 2];
+    vst1q_u64(lanes.as_mut_ptr(), state);
+    fold_lanes(&lanes)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_supported_kernels_match_reference() {
+        let (supported, _) = select_kernels();
+        for kernel in supported {
+            let mut workload = SimdWorkload::new(kernel).unwrap();
+            assert!(workload.run(), "{} disagreed with the scalar reference", kernel.name());
+            assert!(workload.run(), "{} is not repeatable", kernel.name());
+        }
+    }
+
+    #[test]
+    fn test_every_kernel_is_either_supported_or_skipped_with_reason() {
+        let (supported, skipped) = select_kernels();
+        assert_eq!(supported.len() + skipped.len(), SimdKernel::ALL.len());
+        for (kernel, reason) in &skipped {
+            assert!(!reason.is_empty());
+            assert!(SimdWorkload::new(*kernel).is_err());
+        }
+    }
+
+    #[test]
+    fn test_hash_scalar_depends_on_lane_count() {
+        let data = vec![0xA5u8; 256];
+        assert_ne!(hash_scalar(&data, 2, 1), hash_scalar(&data, 4, 1));
+    }
+}
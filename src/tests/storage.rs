@@ -7,12 +7,13 @@ use rand::Rng;
 use rand::SeedableRng;
 use rand::rngs::StdRng;
 use serde_json::json;
-use sysinfo::{System, DiskKind, Disks};
 
-use crate::core::hardware::{HardwareInfo, StorageDevice, DiskType};
+use crate::core::abort::AbortSignal;
+use crate::core::hardware::{self, HardwareInfo};
 use crate::core::test::{BurnInTest, TestResult, TestStatus, TestIssue, IssueSeverity};
 use crate::core::config::TestConfig;
 use crate::core::error::{Result, BurnInError};
+use crate::core::scoring::Scorecard;
 
 /// Storage I/O test
 pub struct StorageIoTest;
@@ -23,39 +24,7 @@ impl BurnInTest for StorageIoTest {
     }
     
     fn detect_hardware(&self) -> Result<HardwareInfo> {
-        
-        let mut hardware_info = crate::tests::cpu::CpuStressTest.detect_hardware()?;
-        
-        
-        let _system = System::new_all();
-        
-        
-        let mut storage_devices = Vec::new();
-        
-        
-        
-        let disks = Disks::new_with_refreshed_list();
-        for disk in &disks {
-            let device_type = match disk.kind() {
-                DiskKind::SSD => DiskType::Ssd,
-                DiskKind::HDD => DiskType::Hdd,
-                _ => DiskType::Unknown,
-            };
-            
-            storage_devices.push(StorageDevice {
-                name: disk.name().to_string_lossy().to_string(),
-                model: "Unknown".to_string(), 
-                device_type,
-                size_bytes: disk.total_space(),
-                mount_point: Some(disk.mount_point().to_string_lossy().to_string()),
-                filesystem: Some(disk.file_system().to_string_lossy().to_string()),
-                smart_supported: false, 
-            });
-        }
-        
-        hardware_info.storage_devices = storage_devices;
-        
-        Ok(hardware_info)
+        Ok(hardware::detect())
     }
     
     fn estimate_duration(&self, config: &TestConfig) -> Duration {
@@ -79,7 +48,7 @@ impl BurnInTest for StorageIoTest {
             ));
         }
         
-        println!("Starting storage I/O test on paths: {:?}", test_paths);
+        log::info!("Starting storage I/O test on paths: {:?}", test_paths);
         
         
         let seq_read_mbps = Arc::new(Mutex::new(0.0));
@@ -93,30 +62,41 @@ impl BurnInTest for StorageIoTest {
         
         
         let mut _all_successful = true;
+        let mut throughput_series = serde_json::Map::new();
         
         for path in &test_paths {
+            if config.abort.is_triggered() {
+                break;
+            }
             
             let test_file = path.join("burnin_storage_test.tmp");
+            let abort = &config.abort;
             
             
-            let seq_write_result = test_sequential_write(&test_file, file_size, seq_write_mbps.clone())?;
-            _all_successful &= seq_write_result;
-            
-            
-            let seq_read_result = test_sequential_read(&test_file, file_size, seq_read_mbps.clone())?;
-            _all_successful &= seq_read_result;
-            
-            
-            let rand_read_result = test_random_read(&test_file, file_size, random_read_iops.clone())?;
-            _all_successful &= rand_read_result;
+            let mut write_samples = Vec::new();
+            *seq_write_mbps.lock().unwrap() = sequential_write_sampled(&test_file, file_size, start_time, abort, &mut write_samples)?;
+            throughput_series.insert(format!("write {}", path.display()), json!(write_samples));
             
+            // Each phase is skipped once the run is aborted, so that the
+            // drive is idle as soon as possible.
+            if !abort.is_triggered() {
+                let mut read_samples = Vec::new();
+                *seq_read_mbps.lock().unwrap() = sequential_read_sampled(&test_file, file_size, start_time, abort, &mut read_samples)?;
+                throughput_series.insert(format!("read {}", path.display()), json!(read_samples));
+            }
             
-            let rand_write_result = test_random_write(&test_file, file_size, random_write_iops.clone())?;
-            _all_successful &= rand_write_result;
+            if !abort.is_triggered() {
+                *random_read_iops.lock().unwrap() = random_read_with_abort(&test_file, file_size, abort)?;
+            }
             
+            if !abort.is_triggered() {
+                *random_write_iops.lock().unwrap() = random_write_with_abort(&test_file, file_size, abort)?;
+            }
             
-            let meta_result = test_metadata_operations(test_file.parent().unwrap())?;
-            _all_successful &= meta_result;
+            if !abort.is_triggered() {
+                let meta_result = test_metadata_operations(test_file.parent().unwrap())?;
+                _all_successful &= meta_result;
+            }
             
             
             if test_file.exists() {
@@ -135,27 +115,28 @@ impl BurnInTest for StorageIoTest {
         let final_error_count = *error_count.lock().unwrap();
         
         
-        let mut score = 100;
+        let mut scorecard = Scorecard::new();
         
         
-        score -= (final_error_count as u8 * 5).min(50);
+        scorecard.deduct(format!("{} storage errors", final_error_count), final_error_count as f64 * 5.0, 50);
         
         
-        if final_seq_read < 50.0 {
-            score -= ((50.0 - final_seq_read) / 5.0).min(10.0) as u8;
+        if config.expected_range(self.name(), "sequential_read_mbps").is_none() {
+            scorecard.below("sequential_read_mbps", final_seq_read, 50.0, 0.2, 10);
         }
         
-        if final_seq_write < 20.0 {
-            score -= ((20.0 - final_seq_write) / 2.0).min(10.0) as u8;
+        if config.expected_range(self.name(), "sequential_write_mbps").is_none() {
+            scorecard.below("sequential_write_mbps", final_seq_write, 20.0, 0.5, 10);
         }
         
-        if final_rand_read < 1000.0 {
-            score -= ((1000.0 - final_rand_read) / 100.0).min(10.0) as u8;
+        if config.expected_range(self.name(), "random_read_iops").is_none() {
+            scorecard.below("random_read_iops", final_rand_read, 1000.0, 0.01, 10);
         }
         
-        if final_rand_write < 500.0 {
-            score -= ((500.0 - final_rand_write) / 50.0).min(10.0) as u8;
+        if config.expected_range(self.name(), "random_write_iops").is_none() {
+            scorecard.below("random_write_iops", final_rand_write, 500.0, 0.02, 10);
         }
+        let (score, deductions) = scorecard.finish();
         
         
         let mut issues = Vec::new();
@@ -208,8 +189,10 @@ impl BurnInTest for StorageIoTest {
                 "random_write_iops": final_rand_write,
                 "error_count": final_error_count,
                 "test_file_size_bytes": file_size,
+                "throughput_series": throughput_series,
             }),
             issues,
+            deductions,
         };
         
         Ok(result)
@@ -280,55 +263,52 @@ fn is_writable(path: &Path) -> bool {
     }
 }
 
-/// Tests sequential write performance
-fn test_sequential_write(
-    path: &Path,
-    size: u64,
-    mbps: Arc<Mutex<f64>>,
-) -> Result<bool> {
+/// Bytes transferred between two throughput samples.
+const SAMPLE_BYTES: u64 = 64 * 1024 * 1024;
+
+/// Writes `size` bytes to `path` sequentially and returns the throughput in MB/s.
+pub fn sequential_write(path: &Path, size: u64) -> Result<f64> {
+    sequential_write_sampled(path, size, Instant::now(), &AbortSignal::new(), &mut Vec::new())
+}
+
+/// Like `sequential_write`, also recording the throughput of every 64 MiB
+/// as `[seconds since origin, MB/s]`. Stops early once `abort` is triggered.
+fn sequential_write_sampled(path: &Path, size: u64, origin: Instant, abort: &AbortSignal, samples: &mut Vec<[f64; 2]>) -> Result<f64> {
     
     let file = File::create(path).map_err(BurnInError::IoError)?;
     
-    let metadata = file.metadata().map_err(BurnInError::IoError)?;
-    let device_size = metadata.len();
-    
-    if size > device_size {
-        return Err(BurnInError::InsufficientResources(
-            "Test file size exceeds device size".to_string(),
-        ));
-    }
-    
     let buffer_size = 1024 * 1024;
     let buffer = vec![0u8; buffer_size];
     
     let start_time = Instant::now();
     let mut writer = io::BufWriter::new(file);
     let mut remaining = size;
+    let mut sampler = Sampler::new(origin);
     
-    while remaining > 0 {
+    while remaining > 0 && !abort.is_triggered() {
         let to_write = buffer_size.min(remaining as usize);
         writer.write_all(&buffer[..to_write])
             .map_err(BurnInError::IoError)?;
         remaining -= to_write as u64;
+        sampler.add(to_write as u64, remaining == 0, samples);
     }
     
     writer.flush().map_err(BurnInError::IoError)?;
     
     let elapsed = start_time.elapsed();
-    let throughput = (size as f64 / 1_000_000.0) / elapsed.as_secs_f64();
-    
-    let mut m = mbps.lock().unwrap();
-    *m = throughput;
+    let throughput = ((size - remaining) as f64 / 1_000_000.0) / elapsed.as_secs_f64();
     
-    Ok(true)
+    Ok(throughput)
+}
+
+/// Reads up to `size` bytes of `path` sequentially and returns the throughput in MB/s.
+pub fn sequential_read(path: &Path, size: u64) -> Result<f64> {
+    sequential_read_sampled(path, size, Instant::now(), &AbortSignal::new(), &mut Vec::new())
 }
 
-/// Tests sequential read performance
-fn test_sequential_read(
-    path: &Path,
-    size: u64,
-    mbps: Arc<Mutex<f64>>,
-) -> Result<bool> {
+/// Like `sequential_read`, also recording the throughput of every 64 MiB
+/// as `[seconds since origin, MB/s]`. Stops early once `abort` is triggered.
+fn sequential_read_sampled(path: &Path, size: u64, origin: Instant, abort: &AbortSignal, samples: &mut Vec<[f64; 2]>) -> Result<f64> {
     
     let file = File::open(path).map_err(BurnInError::IoError)?;
     
@@ -347,8 +327,9 @@ fn test_sequential_read(
     let start_time = Instant::now();
     let mut reader = io::BufReader::new(file);
     let mut remaining = size;
+    let mut sampler = Sampler::new(origin);
     
-    while remaining > 0 {
+    while remaining > 0 && !abort.is_triggered() {
         let to_read = buffer_size.min(remaining as usize);
         match reader.read_exact(&mut buffer[..to_read]) {
             Ok(_) => {}
@@ -356,23 +337,56 @@ fn test_sequential_read(
             Err(e) => return Err(BurnInError::IoError(e)),
         }
         remaining -= to_read as u64;
+        sampler.add(to_read as u64, remaining == 0, samples);
     }
     
     let elapsed = start_time.elapsed();
     let throughput = ((size - remaining) as f64 / 1_000_000.0) / elapsed.as_secs_f64();
     
-    let mut m = mbps.lock().unwrap();
-    *m = throughput;
+    Ok(throughput)
+}
+
+/// Turns a sequential transfer into throughput samples.
+struct Sampler {
+    origin: Instant,
+    window_start: Instant,
+    window_bytes: u64,
+}
+
+impl Sampler {
+    fn new(origin: Instant) -> Self {
+        Self { origin, window_start: Instant::now(), window_bytes: 0 }
+    }
     
-    Ok(true)
+    /// Counts `bytes`, closing the sample window every `SAMPLE_BYTES` and at
+    /// the end of the transfer.
+    fn add(&mut self, bytes: u64, last: bool, samples: &mut Vec<[f64; 2]>) {
+        self.window_bytes += bytes;
+        if self.window_bytes < SAMPLE_BYTES && !last {
+            return;
+        }
+        
+        let now = Instant::now();
+        let secs = now.duration_since(self.window_start).as_secs_f64();
+        if secs > 0.0 {
+            samples.push([
+                now.duration_since(self.origin).as_secs_f64(),
+                self.window_bytes as f64 / 1_000_000.0 / secs,
+            ]);
+        }
+        self.window_start = now;
+        self.window_bytes = 0;
+    }
+}
+
+/// Reads 4 KiB blocks at random offsets within the first `size` bytes of
+/// `path` and returns the achieved IOPS.
+pub fn random_read(path: &Path, size: u64) -> Result<f64> {
+    random_read_with_abort(path, size, &AbortSignal::new())
 }
 
-/// Tests random read performance
-fn test_random_read(
-    path: &Path,
-    size: u64,
-    iops: Arc<Mutex<f64>>,
-) -> Result<bool> {
+/// Like `random_read`, stopping early once `abort` is triggered.
+fn random_read_with_abort(path: &Path, size: u64, abort: &AbortSignal) -> Result<f64> {
     
     let mut file = File::open(path).map_err(BurnInError::IoError)?;
     
@@ -396,6 +410,9 @@ fn test_random_read(
     let mut ops_completed = 0;
     
     for _ in 0..num_ops {
+        if abort.is_triggered() {
+            break;
+        }
         let pos = rng.gen_range(0..=max_pos);
         file.seek(SeekFrom::Start(pos)).map_err(BurnInError::IoError)?;
         
@@ -407,18 +424,17 @@ fn test_random_read(
     let elapsed = start_time.elapsed();
     let ops_per_sec = ops_completed as f64 / elapsed.as_secs_f64();
     
-    let mut i = iops.lock().unwrap();
-    *i = ops_per_sec;
-    
-    Ok(true)
+    Ok(ops_per_sec)
+}
+
+/// Writes 4 KiB blocks at random offsets within the first `size` bytes of
+/// `path` and returns the achieved IOPS.
+pub fn random_write(path: &Path, size: u64) -> Result<f64> {
+    random_write_with_abort(path, size, &AbortSignal::new())
 }
 
-/// Tests random write performance
-fn test_random_write(
-    path: &Path,
-    size: u64,
-    iops: Arc<Mutex<f64>>,
-) -> Result<bool> {
+/// Like `random_write`, stopping early once `abort` is triggered.
+fn random_write_with_abort(path: &Path, size: u64, abort: &AbortSignal) -> Result<f64> {
     
     let mut file = OpenOptions::new()
         .write(true)
@@ -445,6 +461,9 @@ fn test_random_write(
     let mut ops_completed = 0;
     
     for _ in 0..num_ops {
+        if abort.is_triggered() {
+            break;
+        }
         let pos = rng.gen_range(0..=max_pos);
         file.seek(SeekFrom::Start(pos)).map_err(BurnInError::IoError)?;
         
@@ -458,10 +477,7 @@ fn test_random_write(
     let elapsed = start_time.elapsed();
     let ops_per_sec = ops_completed as f64 / elapsed.as_secs_f64();
     
-    let mut i = iops.lock().unwrap();
-    *i = ops_per_sec;
-    
-    Ok(true)
+    Ok(ops_per_sec)
 }
 
 /// Tests metadata operations
@@ -535,3 +551,28 @@ fn test_file_ops(count: usize, cleanup: bool) -> Result<(usize, usize)> {
     
     Ok((start_count, final_count))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::core::abort::AbortReason;
+
+    #[test]
+    fn test_transfers_stop_on_abort() {
+        let path = std::env::temp_dir().join(format!("burnin-storage-abort-{}.tmp", std::process::id()));
+        let abort = AbortSignal::new();
+        abort.trigger(AbortReason::Interrupted);
+
+        let mut samples = Vec::new();
+        sequential_write_sampled(&path, 1 << 30, Instant::now(), &abort, &mut samples).unwrap();
+        assert_eq!(fs::metadata(&path).unwrap().len(), 0);
+        assert!(samples.is_empty());
+
+        fs::write(&path, vec![0u8; 1 << 20]).unwrap();
+        sequential_read_sampled(&path, 1 << 20, Instant::now(), &abort, &mut samples).unwrap();
+        assert!(samples.is_empty());
+        assert_eq!(random_read_with_abort(&path, 1 << 20, &abort).unwrap(), 0.0);
+        assert_eq!(random_write_with_abort(&path, 1 << 20, &abort).unwrap(), 0.0);
+        fs::remove_file(&path).unwrap();
+    }
+}
@@ -1,13 +1,22 @@
+use std::collections::{BTreeMap, BTreeSet};
 use std::time::{Duration, Instant};
-use std::thread;
+use std::thread::{self, JoinHandle};
 use std::sync::{Arc, Mutex};
+use std::sync::atomic::{AtomicBool, Ordering};
+use serde::Serialize;
 use serde_json::json;
-use sysinfo::{System, Components};
 
 use crate::core::test::{BurnInTest, TestResult, TestStatus, TestIssue, IssueSeverity};
-use crate::core::config::TestConfig;
-use crate::core::hardware::{HardwareInfo, ThermalSensor};
+use crate::core::config::{TestConfig, SensorThresholds};
+use crate::core::hardware::{self, HardwareInfo, FanSensor, VoltageSensor};
 use crate::core::error::Result;
+use crate::core::hwmon;
+use crate::core::scoring::Scorecard;
+use crate::core::abort::{AbortSignal, AbortReason};
+
+/// Shortest time between two thermal samples, so that a zero interval
+/// cannot make the observer spin.
+pub const MIN_MONITOR_INTERVAL: Duration = Duration::from_millis(100);
 
 
 pub struct ThermalMonitorTest;
@@ -18,30 +27,7 @@ impl BurnInTest for ThermalMonitorTest {
     }
     
     fn detect_hardware(&self) -> Result<HardwareInfo> {
-        
-        let mut hardware_info = crate::tests::cpu::CpuStressTest.detect_hardware()?;
-        
-        
-        let _system = System::new_all();
-        
-        let mut thermal_sensors = Vec::new();
-        
-        
-        let components = Components::new_with_refreshed_list();
-        for component in &components {
-            if component.label().contains("temp") || component.label().contains("cpu") {
-                thermal_sensors.push(ThermalSensor {
-                    name: component.label().to_string(),
-                    location: "Unknown".to_string(), 
-                    current_temp_celsius: component.temperature(),
-                    critical_temp_celsius: component.critical(),
-                });
-            }
-        }
-        
-        hardware_info.thermal_sensors = thermal_sensors;
-        
-        Ok(hardware_info)
+        Ok(hardware::detect())
     }
     
     fn estimate_duration(&self, config: &TestConfig) -> Duration {
@@ -60,6 +46,7 @@ impl BurnInTest for ThermalMonitorTest {
                 duration: Duration::from_secs(0),
                 metrics: json!({}),
                 issues: Vec::new(),
+                deductions: Vec::new(),
             });
         }
         
@@ -67,7 +54,7 @@ impl BurnInTest for ThermalMonitorTest {
         let hardware_info = self.detect_hardware()?;
         let sensors = &hardware_info.thermal_sensors;
         
-        if sensors.is_empty() {
+        if sensors.is_empty() && hardware_info.fans.is_empty() && hardware_info.voltage_sensors.is_empty() {
             return Ok(TestResult {
                 name: self.name().to_string(),
                 status: TestStatus::Skipped,
@@ -82,194 +69,789 @@ impl BurnInTest for ThermalMonitorTest {
                     message: "No thermal sensors detected".to_string(),
                     action: Some("Check if your system supports thermal monitoring".to_string()),
                 }],
+                deductions: Vec::new(),
             });
         }
         
-        println!("Starting thermal monitoring with {} sensors", sensors.len());
-        
+        log::info!(
+            "Starting thermal monitoring with {} sensors, {} fans and {} voltage rails",
+            sensors.len(),
+            hardware_info.fans.len(),
+            hardware_info.voltage_sensors.len(),
+        );
         
-        let max_temp = Arc::new(Mutex::new(0.0f32));
-        let min_temp = Arc::new(Mutex::new(100.0f32));
-        let avg_temp = Arc::new(Mutex::new(0.0f32));
-        let temp_readings = Arc::new(Mutex::new(0usize));
-        let _throttling_events = Arc::new(Mutex::new(0usize));
-        let warning_events = Arc::new(Mutex::new(0usize));
-        let critical_events = Arc::new(Mutex::new(0usize));
         
+        let observer = ThermalObserver::start(config);
+        config.abort.sleep(config.duration);
+        let history = observer.stop();
         
-        let running = Arc::new(Mutex::new(true));
-        let running_clone = running.clone();
+        Ok(thermal_result(self.name(), &history, config, "isolated", start_time.elapsed()))
+    }
+    
+    fn cleanup(&self) -> Result<()> {
         
+        Ok(())
+    }
+}
+
+/// One temperature reading of a sensor.
+#[derive(Debug, Clone, Serialize)]
+pub struct ThermalSample {
+    /// Seconds since monitoring started
+    pub elapsed_secs: f64,
+    pub temp_celsius: f32,
+    /// Tests running when the sample was taken, or `idle`
+    pub workload: String,
+}
+
+/// One fan speed reading.
+#[derive(Debug, Clone, Serialize)]
+pub struct FanSample {
+    pub elapsed_secs: f64,
+    pub rpm: u32,
+    pub workload: String,
+}
+
+/// One voltage rail reading.
+#[derive(Debug, Clone, Serialize)]
+pub struct VoltageSample {
+    pub elapsed_secs: f64,
+    pub volts: f32,
+    pub workload: String,
+}
+
+/// Temperature, fan and voltage readings of every sensor over time.
+#[derive(Debug, Clone, Default)]
+pub struct ThermalHistory {
+    pub sensors: BTreeMap<String, Vec<ThermalSample>>,
+    pub fans: BTreeMap<String, Vec<FanSample>>,
+    pub voltages: BTreeMap<String, Vec<VoltageSample>>,
+    /// Thresholds resolved for each temperature sensor when it was first seen
+    pub thresholds: BTreeMap<String, SensorThresholds>,
+    /// Minimum speed of each fan that reports one (`fanN_min`)
+    pub fan_limits: BTreeMap<String, u32>,
+    /// Lower and upper limits of each rail that reports both (`inN_min` and
+    /// `inN_max`)
+    pub voltage_limits: BTreeMap<String, (f32, f32)>,
+}
+
+/// Statistics of one fan's speed series.
+#[derive(Debug, Clone, Serialize)]
+pub struct FanSummary {
+    pub name: String,
+    pub min_rpm: u32,
+    pub max_rpm: u32,
+    pub avg_rpm: f64,
+    /// Readings of 0 RPM taken while a test was running
+    pub stalled_under_load: usize,
+    /// Minimum speed the chip reports for the fan
+    pub min_limit_rpm: Option<u32>,
+}
+
+/// Statistics of one voltage rail's series.
+#[derive(Debug, Clone, Serialize)]
+pub struct VoltageSummary {
+    pub name: String,
+    /// First reading of the run, which drift is measured against
+    pub reference_volts: f32,
+    pub min_volts: f32,
+    pub max_volts: f32,
+    pub max_drift_percent: f32,
+    pub max_drift_workload: String,
+    /// Limits the chip reports for the rail
+    pub min_limit_volts: Option<f32>,
+    pub max_limit_volts: Option<f32>,
+}
+
+/// Statistics of one sensor's temperature series.
+#[derive(Debug, Clone, Serialize)]
+pub struct SensorSummary {
+    pub name: String,
+    pub readings: usize,
+    pub min_temperature_celsius: f32,
+    pub max_temperature_celsius: f32,
+    pub avg_temperature_celsius: f32,
+    pub peak_time_secs: f64,
+    pub peak_workload: String,
+    pub warning_threshold_celsius: f32,
+    pub critical_threshold_celsius: f32,
+    pub time_above_warning_secs: f64,
+    pub time_above_critical_secs: f64,
+    /// Fastest temperature rise between two samples, in °C/s
+    pub max_heating_rate: f64,
+    /// Fastest temperature drop between two samples, in °C/s
+    pub max_cooling_rate: f64,
+}
+
+/// Temperatures observed while a particular set of tests was running.
+#[derive(Debug, Clone, Serialize)]
+pub struct WorkloadThermal {
+    pub workload: String,
+    pub samples: usize,
+    pub max_temperature_celsius: f32,
+    pub avg_temperature_celsius: f32,
+}
+
+impl ThermalHistory {
+    /// Appends one reading per sensor, tagged with the active workload.
+    pub fn record(&mut self, elapsed_secs: f64, readings: &[(String, f32)], workload: &str) {
+        for (name, temp) in readings {
+            self.sensors.entry(name.clone()).or_default().push(ThermalSample {
+                elapsed_secs,
+                temp_celsius: *temp,
+                workload: workload.to_string(),
+            });
+        }
+    }
+    
+    /// Appends one reading per fan, tagged with the active workload.
+    pub fn record_fans(&mut self, elapsed_secs: f64, fans: &[FanSensor], workload: &str) {
+        for fan in fans {
+            if let Some(min) = fan.min_rpm {
+                self.fan_limits.entry(fan.name.clone()).or_insert(min);
+            }
+            self.fans.entry(fan.name.clone()).or_default().push(FanSample {
+                elapsed_secs,
+                rpm: fan.rpm,
+                workload: workload.to_string(),
+            });
+        }
+    }
+    
+    /// Appends one reading per voltage rail, tagged with the active workload.
+    pub fn record_voltages(&mut self, elapsed_secs: f64, rails: &[VoltageSensor], workload: &str) {
+        for rail in rails {
+            if let (Some(min), Some(max)) = (rail.min_volts, rail.max_volts) {
+                if max > min {
+                    self.voltage_limits.entry(rail.name.clone()).or_insert((min, max));
+                }
+            }
+            self.voltages.entry(rail.name.clone()).or_default().push(VoltageSample {
+                elapsed_secs,
+                volts: rail.volts,
+                workload: workload.to_string(),
+            });
+        }
+    }
+    
+    /// Returns `true` if nothing was recorded.
+    pub fn is_empty(&self) -> bool {
+        self.sensors.is_empty() && self.fans.is_empty() && self.voltages.is_empty()
+    }
+    
+    /// Summarizes every fan's series.
+    pub fn summarize_fans(&self) -> Vec<FanSummary> {
+        self.fans.iter()
+            .filter(|(_, samples)| !samples.is_empty())
+            .map(|(name, samples)| FanSummary {
+                name: name.clone(),
+                min_rpm: samples.iter().map(|s| s.rpm).min().unwrap_or(0),
+                max_rpm: samples.iter().map(|s| s.rpm).max().unwrap_or(0),
+                avg_rpm: samples.iter().map(|s| s.rpm as f64).sum::<f64>() / samples.len() as f64,
+                stalled_under_load: samples.iter().filter(|s| s.rpm == 0 && s.workload != "idle").count(),
+                min_limit_rpm: self.fan_limits.get(name).copied(),
+            })
+            .collect()
+    }
+    
+    /// Summarizes every voltage rail's drift from its first reading.
+    pub fn summarize_voltages(&self) -> Vec<VoltageSummary> {
+        let mut summaries = Vec::new();
+        
+        for (name, samples) in &self.voltages {
+            let Some(first) = samples.first() else {
+                continue;
+            };
+            
+            let reference = first.volts;
+            let drift = |volts: f32| if reference != 0.0 { ((volts - reference) / reference).abs() * 100.0 } else { 0.0 };
+            let worst = samples.iter()
+                .max_by(|a, b| drift(a.volts).total_cmp(&drift(b.volts)))
+                .unwrap_or(first);
+            
+            summaries.push(VoltageSummary {
+                name: name.clone(),
+                reference_volts: reference,
+                min_volts: samples.iter().map(|s| s.volts).fold(f32::MAX, f32::min),
+                max_volts: samples.iter().map(|s| s.volts).fold(f32::MIN, f32::max),
+                max_drift_percent: drift(worst.volts),
+                max_drift_workload: worst.workload.clone(),
+                min_limit_volts: self.voltage_limits.get(name).map(|(min, _)| *min),
+                max_limit_volts: self.voltage_limits.get(name).map(|(_, max)| *max),
+            });
+        }
         
-        let test_duration = config.duration; 
-        let timer_thread = thread::spawn(move || {
-            thread::sleep(test_duration);
-            let mut running = running_clone.lock().unwrap();
-            *running = false;
-        });
+        summaries
+    }
+    
+    /// Returns the thresholds of `sensor`, or `default` if none were resolved.
+    pub fn thresholds(&self, sensor: &str, default: SensorThresholds) -> SensorThresholds {
+        self.thresholds.get(sensor).copied().unwrap_or(default)
+    }
+    
+    /// Summarizes every sensor's series against its own thresholds, using
+    /// `default` for sensors without any.
+    ///
+    /// Time above a threshold counts each interval whose starting sample was
+    /// at or above it.
+    pub fn summarize(&self, default: SensorThresholds) -> Vec<SensorSummary> {
+        let mut summaries = Vec::new();
+        
+        for (name, samples) in &self.sensors {
+            let SensorThresholds { warning, critical } = self.thresholds(name, default);
+            let Some(peak) = samples.iter().max_by(|a, b| a.temp_celsius.total_cmp(&b.temp_celsius)) else {
+                continue;
+            };
+            
+            let mut summary = SensorSummary {
+                name: name.clone(),
+                readings: samples.len(),
+                min_temperature_celsius: samples.iter().map(|s| s.temp_celsius).fold(f32::MAX, f32::min),
+                max_temperature_celsius: peak.temp_celsius,
+                avg_temperature_celsius: samples.iter().map(|s| s.temp_celsius).sum::<f32>() / samples.len() as f32,
+                peak_time_secs: peak.elapsed_secs,
+                peak_workload: peak.workload.clone(),
+                warning_threshold_celsius: warning,
+                critical_threshold_celsius: critical,
+                time_above_warning_secs: 0.0,
+                time_above_critical_secs: 0.0,
+                max_heating_rate: 0.0,
+                max_cooling_rate: 0.0,
+            };
+            
+            for pair in samples.windows(2) {
+                let dt = pair[1].elapsed_secs - pair[0].elapsed_secs;
+                if dt <= 0.0 {
+                    continue;
+                }
+                if pair[0].temp_celsius >= warning {
+                    summary.time_above_warning_secs += dt;
+                }
+                if pair[0].temp_celsius >= critical {
+                    summary.time_above_critical_secs += dt;
+                }
+                let rate = (pair[1].temp_celsius - pair[0].temp_celsius) as f64 / dt;
+                summary.max_heating_rate = summary.max_heating_rate.max(rate);
+                summary.max_cooling_rate = summary.max_cooling_rate.max(-rate);
+            }
+            
+            summaries.push(summary);
+        }
         
+        summaries
+    }
+    
+    /// Groups all readings by the workload that was active when they were taken.
+    pub fn by_workload(&self) -> Vec<WorkloadThermal> {
+        let mut groups: BTreeMap<&str, Vec<f32>> = BTreeMap::new();
+        for sample in self.sensors.values().flatten() {
+            groups.entry(&sample.workload).or_default().push(sample.temp_celsius);
+        }
         
-        let monitor_thread = {
-            let max_temp = max_temp.clone();
-            let min_temp = min_temp.clone();
-            let avg_temp = avg_temp.clone();
-            let temp_readings = temp_readings.clone();
-            let warning_events = warning_events.clone();
-            let critical_events = critical_events.clone();
+        groups.into_iter()
+            .map(|(workload, temps)| WorkloadThermal {
+                workload: workload.to_string(),
+                samples: temps.len(),
+                max_temperature_celsius: temps.iter().copied().fold(f32::MIN, f32::max),
+                avg_temperature_celsius: temps.iter().sum::<f32>() / temps.len() as f32,
+            })
+            .collect()
+    }
+    
+    /// Returns each sensor's series as `[elapsed_secs, temp_celsius]` pairs.
+    pub fn series(&self) -> serde_json::Value {
+        self.sensors.iter()
+            .map(|(name, samples)| {
+                let points: Vec<_> = samples.iter().map(|s| json!([s.elapsed_secs, s.temp_celsius])).collect();
+                (name.clone(), json!(points))
+            })
+            .collect::<serde_json::Map<_, _>>()
+            .into()
+    }
+}
+
+
+/// Triggers `abort` if any reading is at or above its sensor's critical threshold.
+fn check_cutoff(readings: &[(String, f32)], history: &ThermalHistory, default: SensorThresholds, abort: &AbortSignal) {
+    for (sensor, temp) in readings {
+        let critical = history.thresholds(sensor, default).critical;
+        if *temp >= critical {
+            abort.trigger(AbortReason::ThermalCutoff {
+                sensor: sensor.clone(),
+                temp_celsius: *temp,
+                critical_celsius: critical,
+            });
+            return;
+        }
+    }
+}
+
+/// Samples all sensors on a background thread, tagging each reading with the
+/// tests that are running at the time.
+pub struct ThermalObserver {
+    running: Arc<AtomicBool>,
+    active: Arc<Mutex<BTreeSet<String>>>,
+    handle: JoinHandle<ThermalHistory>,
+}
+
+impl ThermalObserver {
+    /// Starts sampling every `config.thermal_monitor_interval`, but no more
+    /// often than every [`MIN_MONITOR_INTERVAL`].
+    ///
+    /// Each sensor's thresholds are resolved with
+    /// [`TestConfig::sensor_thresholds`] the first time it is seen. With
+    /// `config.thermal_cutoff` set, a reading at or above its critical
+    /// threshold triggers `config.abort`.
+    pub fn start(config: &TestConfig) -> Self {
+        let running = Arc::new(AtomicBool::new(true));
+        let active = Arc::new(Mutex::new(BTreeSet::new()));
+        let interval = config.thermal_monitor_interval.max(MIN_MONITOR_INTERVAL);
+        let config = config.clone();
+        
+        let handle = {
             let running = running.clone();
-            
-            
-            let thermal_warning_threshold = config.thermal_warning_threshold;
-            let thermal_critical_threshold = config.thermal_critical_threshold;
-            let thermal_monitor_interval = config.thermal_monitor_interval;
+            let active: Arc<Mutex<BTreeSet<String>>> = active.clone();
             
             thread::spawn(move || {
-                let mut _system = sysinfo::System::new();
-                let mut total_temp = 0.0f32;
-                let mut readings = 0usize;
+                let start = Instant::now();
+                let mut history = ThermalHistory::default();
                 
-                while *running.lock().unwrap() {
-                    
-                    _system.refresh_all();
-                    
-                    
-                    
-                    let components = Components::new_with_refreshed_list();
-                    for component in &components {
-                        let temp = component.temperature();
-                        
-                        
-                        {
-                            let mut max = max_temp.lock().unwrap();
-                            if temp > *max {
-                                *max = temp;
-                            }
-                        }
-                        
-                        {
-                            let mut min = min_temp.lock().unwrap();
-                            if temp < *min {
-                                *min = temp;
-                            }
-                        }
-                        
-                        total_temp += temp;
-                        readings += 1;
-                        
-                        
-                        if temp >= thermal_warning_threshold {
-                            let mut warnings = warning_events.lock().unwrap();
-                            *warnings += 1;
-                            
-                            if temp >= thermal_critical_threshold {
-                                let mut criticals = critical_events.lock().unwrap();
-                                *criticals += 1;
-                            }
+                loop {
+                    let workload = {
+                        let active = active.lock().unwrap();
+                        if active.is_empty() {
+                            "idle".to_string()
+                        } else {
+                            active.iter().cloned().collect::<Vec<_>>().join("+")
                         }
+                    };
+                    let sysfs = hwmon::read_sensors();
+                    let sensors = hwmon::read_temperatures(&sysfs);
+                    for sensor in &sensors {
+                        history.thresholds.entry(sensor.name.clone())
+                            .or_insert_with(|| config.sensor_thresholds(sensor));
                     }
-                    
-                    
-                    if readings > 0 {
-                        let mut avg = avg_temp.lock().unwrap();
-                        *avg = total_temp / readings as f32;
-                        
-                        let mut count = temp_readings.lock().unwrap();
-                        *count = readings;
+                    let readings: Vec<(String, f32)> = sensors.into_iter()
+                        .map(|sensor| (sensor.name, sensor.current_temp_celsius))
+                        .collect();
+                    if config.thermal_cutoff {
+                        check_cutoff(&readings, &history, config.default_thresholds(), &config.abort);
                     }
+                    let elapsed = start.elapsed().as_secs_f64();
+                    history.record(elapsed, &readings, &workload);
+                    history.record_fans(elapsed, &sysfs.fans, &workload);
+                    history.record_voltages(elapsed, &sysfs.voltages, &workload);
                     
+                    if !running.load(Ordering::Relaxed) {
+                        break;
+                    }
                     
-                    thread::sleep(thermal_monitor_interval);
+                    // Sleep in short steps so stopping takes the final sample promptly.
+                    let next = Instant::now() + interval;
+                    while running.load(Ordering::Relaxed) && Instant::now() < next {
+                        thread::sleep(Duration::from_millis(100).min(interval));
+                    }
                 }
+                
+                history
             })
         };
         
-        
-        let _ = timer_thread.join();
-        
-        
-        {
-            let mut running_flag = running.lock().unwrap();
-            *running_flag = false;
-        }
-        let _ = monitor_thread.join();
-        
-        
-        let final_max_temp = *max_temp.lock().unwrap();
-        let final_min_temp = *min_temp.lock().unwrap();
-        let final_avg_temp = *avg_temp.lock().unwrap();
-        let final_readings = *temp_readings.lock().unwrap();
-        let final_warnings = *warning_events.lock().unwrap();
-        let final_criticals = *critical_events.lock().unwrap();
-        
-        
-        let mut score = 100;
-        
-        
-        if final_max_temp > config.thermal_warning_threshold {
-            let over_warning = final_max_temp - config.thermal_warning_threshold;
-            let warning_range = config.thermal_critical_threshold - config.thermal_warning_threshold;
-            let penalty = ((over_warning / warning_range) * 30.0) as u8;
-            score -= penalty;
-        }
-        
-        
-        score -= (final_criticals as u8 * 10).min(50);
-        
-        
-        let mut issues = Vec::new();
-        
-        if final_criticals > 0 {
+        Self { running, active, handle }
+    }
+    
+    /// Marks a test as running.
+    pub fn enter(&self, test: &str) {
+        self.active.lock().unwrap().insert(test.to_string());
+    }
+    
+    /// Marks a test as finished.
+    pub fn leave(&self, test: &str) {
+        self.active.lock().unwrap().remove(test);
+    }
+    
+    /// Stops sampling and returns everything that was recorded.
+    pub fn stop(self) -> ThermalHistory {
+        self.running.store(false, Ordering::Relaxed);
+        self.handle.join().unwrap_or_default()
+    }
+}
+
+/// Builds the thermal test result from a recorded history.
+pub fn thermal_result(
+    name: &str,
+    history: &ThermalHistory,
+    config: &TestConfig,
+    mode: &str,
+    duration: Duration,
+) -> TestResult {
+    if history.is_empty() {
+        return TestResult {
+            name: name.to_string(),
+            status: TestStatus::Skipped,
+            score: 100,
+            duration,
+            metrics: json!({
+                "mode": mode,
+                "sensors_detected": 0,
+            }),
+            issues: vec![TestIssue {
+                component: "thermal".to_string(),
+                severity: IssueSeverity::Low,
+                message: "No thermal sensors detected".to_string(),
+                action: Some("Check if your system supports thermal monitoring".to_string()),
+            }],
+            deductions: Vec::new(),
+        };
+    }
+    
+    let default = config.default_thresholds();
+    let summaries = history.summarize(default);
+    
+    let all_temps: Vec<f32> = history.sensors.values().flatten().map(|s| s.temp_celsius).collect();
+    let final_readings = all_temps.len();
+    let final_max_temp = all_temps.iter().copied().fold(0.0f32, f32::max);
+    let final_min_temp = all_temps.iter().copied().fold(100.0f32, f32::min);
+    let final_avg_temp = if final_readings > 0 {
+        all_temps.iter().sum::<f32>() / final_readings as f32
+    } else {
+        0.0
+    };
+    let count_at_or_above = |threshold: fn(&SensorThresholds) -> f32| {
+        history.sensors.iter()
+            .map(|(name, samples)| {
+                let limit = threshold(&history.thresholds(name, default));
+                samples.iter().filter(|s| s.temp_celsius >= limit).count()
+            })
+            .sum::<usize>()
+    };
+    let final_warnings = count_at_or_above(|t| t.warning);
+    let final_criticals = count_at_or_above(|t| t.critical);
+    
+    
+    let mut scorecard = Scorecard::new();
+    
+    
+    // Only the hottest sensor, relative to its own limits, costs points.
+    let hottest = summaries.iter()
+        .filter(|s| s.max_temperature_celsius > s.warning_threshold_celsius)
+        .map(|s| {
+            let over_warning = s.max_temperature_celsius - s.warning_threshold_celsius;
+            let warning_range = (s.critical_threshold_celsius - s.warning_threshold_celsius).max(1.0);
+            (s, over_warning as f64 / warning_range as f64)
+        })
+        .max_by(|a, b| a.1.total_cmp(&b.1));
+    if let Some((summary, fraction)) = hottest {
+        scorecard.deduct(
+            format!("{} peaked at {:.1}°C, above its warning temperature of {:.1}°C",
+                summary.name, summary.max_temperature_celsius, summary.warning_threshold_celsius),
+            fraction * 30.0,
+            30,
+        );
+    }
+    
+    
+    scorecard.deduct(format!("{} readings at or above a critical temperature", final_criticals), final_criticals as f64 * 10.0, 50);
+    
+    
+    let mut issues = Vec::new();
+    
+    for summary in &summaries {
+        if summary.max_temperature_celsius >= summary.critical_threshold_celsius {
             issues.push(TestIssue {
                 component: "thermal".to_string(),
                 severity: IssueSeverity::Critical,
-                message: format!("Critical temperature threshold exceeded {} times", final_criticals),
+                message: format!(
+                    "{} exceeded its critical temperature of {:.1}°C for {:.0}s (peak {:.1}°C at {:.0}s during {})",
+                    summary.name, summary.critical_threshold_celsius, summary.time_above_critical_secs, summary.max_temperature_celsius,
+                    summary.peak_time_secs, summary.peak_workload,
+                ),
                 action: Some("Check cooling system immediately".to_string()),
             });
-        } else if final_warnings > 0 {
+        } else if summary.max_temperature_celsius >= summary.warning_threshold_celsius {
             issues.push(TestIssue {
                 component: "thermal".to_string(),
                 severity: IssueSeverity::High,
-                message: format!("Warning temperature threshold exceeded {} times", final_warnings),
+                message: format!(
+                    "{} exceeded its warning temperature of {:.1}°C for {:.0}s (peak {:.1}°C at {:.0}s during {})",
+                    summary.name, summary.warning_threshold_celsius, summary.time_above_warning_secs, summary.max_temperature_celsius,
+                    summary.peak_time_secs, summary.peak_workload,
+                ),
                 action: Some("Improve cooling or reduce system load".to_string()),
             });
         }
-        
-        if final_max_temp > 85.0 {
+    }
+    
+    let fans = history.summarize_fans();
+    // Channels that never spun are usually unpopulated headers, unless the
+    // board set a minimum speed for them.
+    let silent: Vec<&str> = fans.iter()
+        .filter(|fan| fan.max_rpm == 0 && fan.min_limit_rpm.is_none_or(|min| min == 0))
+        .map(|fan| fan.name.as_str())
+        .collect();
+    if !silent.is_empty() {
+        issues.push(TestIssue {
+            component: "cooling".to_string(),
+            severity: IssueSeverity::Low,
+            message: format!("{} read 0 RPM for the whole run; unpopulated headers or dead fans", silent.join(", ")),
+            action: Some("Check that a fan is connected to each header that should have one".to_string()),
+        });
+    }
+    for fan in fans.iter().filter(|fan| fan.max_rpm == 0 && fan.min_limit_rpm.is_some_and(|min| min > 0)) {
+        issues.push(TestIssue {
+            component: "cooling".to_string(),
+            severity: IssueSeverity::High,
+            message: format!("{} read 0 RPM for the whole run despite a minimum of {} RPM", fan.name, fan.min_limit_rpm.unwrap_or(0)),
+            action: Some("Check the fan, its cable and its header".to_string()),
+        });
+    }
+    for fan in fans.iter().filter(|fan| fan.max_rpm > 0 && fan.stalled_under_load > 0) {
+        // A minimum of 0 RPM means the fan is allowed to stop.
+        let severity = if fan.min_limit_rpm == Some(0) { IssueSeverity::Low } else { IssueSeverity::High };
+        issues.push(TestIssue {
+            component: "cooling".to_string(),
+            severity,
+            message: format!("{} stopped under load ({} readings at 0 RPM)", fan.name, fan.stalled_under_load),
+            action: Some("Check the fan, its cable and its header".to_string()),
+        });
+    }
+    
+    let voltages = history.summarize_voltages();
+    for rail in &voltages {
+        let message = match (rail.min_limit_volts, rail.max_limit_volts) {
+            (Some(low), Some(high)) => (rail.min_volts < low || rail.max_volts > high).then(|| format!(
+                "{} left its limits of {:.3} V to {:.3} V (range {:.3} V to {:.3} V)",
+                rail.name, low, high, rail.min_volts, rail.max_volts,
+            )),
+            _ => (rail.max_drift_percent > config.voltage_tolerance_percent).then(|| format!(
+                "{} drifted {:.1}% from {:.3} V during {} (range {:.3} V to {:.3} V, tolerance {:.1}%)",
+                rail.name, rail.max_drift_percent, rail.reference_volts, rail.max_drift_workload,
+                rail.min_volts, rail.max_volts, config.voltage_tolerance_percent,
+            )),
+        };
+        if let Some(message) = message {
             issues.push(TestIssue {
-                component: "thermal".to_string(),
-                severity: IssueSeverity::Medium,
-                message: format!("Maximum temperature is very high: {:.1}°C", final_max_temp),
-                action: Some("Check cooling system efficiency".to_string()),
+                component: "power".to_string(),
+                severity: IssueSeverity::High,
+                message,
+                action: Some("Check the power supply and motherboard voltage regulators".to_string()),
             });
         }
-        
-        
-        let result = TestResult {
-            name: self.name().to_string(),
-            status: if issues.iter().any(|i| i.severity == IssueSeverity::Critical) {
-                TestStatus::Failed
-            } else {
-                TestStatus::Completed
-            },
-            score,
-            duration: start_time.elapsed(),
-            metrics: json!({
-                "max_temperature_celsius": final_max_temp,
-                "min_temperature_celsius": final_min_temp,
-                "avg_temperature_celsius": final_avg_temp,
-                "temperature_readings": final_readings,
-                "warning_events": final_warnings,
-                "critical_events": final_criticals,
-                "sensors_detected": sensors.len(),
-            }),
-            issues,
-        };
-        
-        Ok(result)
     }
     
-    fn cleanup(&self) -> Result<()> {
-        
-        Ok(())
+    let (count, penalty) = issues.iter()
+        .filter(|issue| issue.component == "cooling" || issue.component == "power")
+        .filter_map(|issue| match issue.severity {
+            IssueSeverity::Low => None,
+            IssueSeverity::High | IssueSeverity::Critical => Some(15.0),
+            IssueSeverity::Medium => Some(5.0),
+        })
+        .fold((0, 0.0), |(count, total), points| (count + 1, total + points));
+    scorecard.deduct(format!("{} fan or voltage rail issues", count), penalty, 40);
+    let (score, deductions) = scorecard.finish();
+    
+    
+    TestResult {
+        name: name.to_string(),
+        status: if issues.iter().any(|i| i.severity == IssueSeverity::Critical) {
+            TestStatus::Failed
+        } else {
+            TestStatus::Completed
+        },
+        score,
+        duration,
+        metrics: json!({
+            "mode": mode,
+            "max_temperature_celsius": final_max_temp,
+            "min_temperature_celsius": final_min_temp,
+            "avg_temperature_celsius": final_avg_temp,
+            "temperature_readings": final_readings,
+            "warning_events": final_warnings,
+            "critical_events": final_criticals,
+            "sensors_detected": history.sensors.len(),
+            "sensors": summaries,
+            "fans": fans,
+            "voltages": voltages,
+            "workloads": history.by_workload(),
+            "series": history.series(),
+        }),
+        issues,
+        deductions,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn history() -> ThermalHistory {
+        let mut history = ThermalHistory::default();
+        history.record(0.0, &[("cpu".to_string(), 50.0), ("nvme".to_string(), 40.0)], "idle");
+        history.record(5.0, &[("cpu".to_string(), 85.0), ("nvme".to_string(), 41.0)], "cpu_stress");
+        history.record(10.0, &[("cpu".to_string(), 92.0), ("nvme".to_string(), 42.0)], "cpu_stress");
+        history.record(15.0, &[("cpu".to_string(), 62.0), ("nvme".to_string(), 60.0)], "storage_io");
+        history
+    }
+
+    #[test]
+    fn test_summarize_per_sensor() {
+        let summaries = history().summarize(SensorThresholds { warning: 80.0, critical: 90.0 });
+        assert_eq!(summaries.len(), 2);
+        
+        let cpu = &summaries[0];
+        assert_eq!(cpu.name, "cpu");
+        assert_eq!(cpu.readings, 4);
+        assert_eq!(cpu.max_temperature_celsius, 92.0);
+        assert_eq!(cpu.min_temperature_celsius, 50.0);
+        assert_eq!(cpu.peak_time_secs, 10.0);
+        assert_eq!(cpu.peak_workload, "cpu_stress");
+        assert_eq!(cpu.time_above_warning_secs, 10.0);
+        assert_eq!(cpu.time_above_critical_secs, 5.0);
+        assert_eq!(cpu.max_heating_rate, 7.0);
+        assert_eq!(cpu.max_cooling_rate, 6.0);
+        
+        let nvme = &summaries[1];
+        assert_eq!(nvme.peak_workload, "storage_io");
+        assert_eq!(nvme.time_above_warning_secs, 0.0);
+        assert_eq!(nvme.max_cooling_rate, 0.0);
+    }
+
+    #[test]
+    fn test_by_workload() {
+        let workloads = history().by_workload();
+        let names: Vec<_> = workloads.iter().map(|w| w.workload.as_str()).collect();
+        assert_eq!(names, ["cpu_stress", "idle", "storage_io"]);
+        assert_eq!(workloads[0].samples, 4);
+        assert_eq!(workloads[0].max_temperature_celsius, 92.0);
+        assert_eq!(workloads[1].avg_temperature_celsius, 45.0);
+    }
+
+    #[test]
+    fn test_check_cutoff() {
+        let default = SensorThresholds { warning: 80.0, critical: 90.0 };
+        let mut history = ThermalHistory::default();
+        history.thresholds.insert("gpu".to_string(), SensorThresholds { warning: 90.0, critical: 100.0 });
+        history.thresholds.insert("nvme".to_string(), SensorThresholds { warning: 65.0, critical: 70.0 });
+        
+        let abort = AbortSignal::new();
+        check_cutoff(&[("cpu".to_string(), 85.0), ("gpu".to_string(), 95.0)], &history, default, &abort);
+        assert!(!abort.is_triggered());
+        
+        check_cutoff(&[("cpu".to_string(), 85.0), ("nvme".to_string(), 72.0)], &history, default, &abort);
+        assert_eq!(abort.reason(), Some(AbortReason::ThermalCutoff {
+            sensor: "nvme".to_string(),
+            temp_celsius: 72.0,
+            critical_celsius: 70.0,
+        }));
+    }
+
+    fn fan(name: &str, rpm: u32) -> FanSensor {
+        FanSensor { name: name.to_string(), location: "hwmon2".to_string(), rpm, min_rpm: None }
+    }
+
+    fn rail(name: &str, volts: f32) -> VoltageSensor {
+        VoltageSensor { name: name.to_string(), location: "hwmon2".to_string(), volts, min_volts: None, max_volts: None }
+    }
+
+    fn limited_rail(name: &str, volts: f32, min: f32, max: f32) -> VoltageSensor {
+        VoltageSensor { min_volts: Some(min), max_volts: Some(max), ..rail(name, volts) }
+    }
+
+    #[test]
+    fn test_fan_and_voltage_issues() {
+        let mut history = ThermalHistory::default();
+        history.record_fans(0.0, &[fan("cpu_fan", 900), fan("sys_fan", 0), fan("aux_fan", 0)], "idle");
+        history.record_fans(5.0, &[fan("cpu_fan", 0), fan("sys_fan", 0), fan("aux_fan", 0)], "cpu_stress");
+        history.record_voltages(0.0, &[rail("Vcore", 1.20), rail("+12V", 12.0)], "idle");
+        history.record_voltages(5.0, &[rail("Vcore", 1.08), rail("+12V", 11.9)], "cpu_stress");
+        
+        let fans = history.summarize_fans();
+        assert_eq!(fans[1].name, "cpu_fan");
+        assert_eq!((fans[1].min_rpm, fans[1].max_rpm, fans[1].stalled_under_load), (0, 900, 1));
+        
+        let voltages = history.summarize_voltages();
+        assert_eq!(voltages[1].name, "Vcore");
+        assert!((voltages[1].max_drift_percent - 10.0).abs() < 0.01);
+        assert_eq!(voltages[1].max_drift_workload, "cpu_stress");
+        
+        let config = TestConfig::default();
+        let result = thermal_result("thermal_monitor", &history, &config, "background", Duration::from_secs(5));
+        let messages: Vec<_> = result.issues.iter().map(|i| (i.component.as_str(), i.severity)).collect();
+        assert_eq!(messages, [
+            ("cooling", IssueSeverity::Low),
+            ("cooling", IssueSeverity::High),
+            ("power", IssueSeverity::High),
+        ]);
+        assert!(result.issues[0].message.starts_with("aux_fan, sys_fan read 0 RPM for the whole run"));
+        assert!(result.issues[1].message.starts_with("cpu_fan stopped under load"));
+        assert!(result.issues[2].message.starts_with("Vcore drifted 10.0% from 1.200 V during cpu_stress"));
+        assert_eq!(result.score, 70);
+    }
+
+    #[test]
+    fn test_fan_and_voltage_limits() {
+        let mut history = ThermalHistory::default();
+        let zero_rpm = FanSensor { min_rpm: Some(0), ..fan("case_fan", 600) };
+        let dead = FanSensor { min_rpm: Some(300), ..fan("cpu_fan", 0) };
+        let unused = fan("fan5", 0);
+        history.record_fans(0.0, &[zero_rpm.clone(), dead.clone(), unused.clone()], "idle");
+        history.record_fans(5.0, &[FanSensor { rpm: 0, ..zero_rpm }, dead, unused], "cpu_stress");
+        // Vcore drifts 25% but stays within its limits; +5V leaves them.
+        history.record_voltages(0.0, &[limited_rail("Vcore", 1.20, 0.8, 1.55), limited_rail("+5V", 5.0, 4.75, 5.25)], "idle");
+        history.record_voltages(5.0, &[limited_rail("Vcore", 0.90, 0.8, 1.55), limited_rail("+5V", 4.6, 4.75, 5.25)], "cpu_stress");
+        
+        let voltages = history.summarize_voltages();
+        assert_eq!((voltages[1].min_limit_volts, voltages[1].max_limit_volts), (Some(0.8), Some(1.55)));
+        
+        let config = TestConfig::default();
+        let result = thermal_result("thermal_monitor", &history, &config, "background", Duration::from_secs(5));
+        let messages: Vec<_> = result.issues.iter().map(|i| (i.severity, i.message.as_str())).collect();
+        assert_eq!(messages, [
+            (IssueSeverity::Low, "fan5 read 0 RPM for the whole run; unpopulated headers or dead fans"),
+            (IssueSeverity::High, "cpu_fan read 0 RPM for the whole run despite a minimum of 300 RPM"),
+            (IssueSeverity::Low, "case_fan stopped under load (1 readings at 0 RPM)"),
+            (IssueSeverity::High, "+5V left its limits of 4.750 V to 5.250 V (range 4.600 V to 5.000 V)"),
+        ]);
+        assert_eq!(result.score, 70);
+    }
+
+    #[test]
+    fn test_thermal_result_without_sensors() {
+        let config = TestConfig::default();
+        let result = thermal_result("thermal_monitor", &ThermalHistory::default(), &config, "background", Duration::ZERO);
+        assert_eq!(result.status, TestStatus::Skipped);
+        assert_eq!(result.issues.len(), 1);
+    }
+
+    #[test]
+    fn test_thermal_result_names_hot_sensor() {
+        let config = TestConfig::default();
+        let result = thermal_result("thermal_monitor", &history(), &config, "background", Duration::from_secs(15));
+        assert_eq!(result.status, TestStatus::Failed);
+        assert_eq!(result.metrics["sensors_detected"], 2);
+        assert_eq!(result.metrics["critical_events"], 1);
+        assert!(result.issues[0].message.starts_with("cpu exceeded its critical temperature of 90.0°C for 5s"));
+        assert_eq!(result.metrics["series"]["nvme"][3], json!([15.0, 60.0]));
+    }
+    
+    #[test]
+    fn test_thermal_result_uses_sensor_thresholds() {
+        let config = TestConfig::default();
+        let mut history = history();
+        history.thresholds.insert("cpu".to_string(), SensorThresholds { warning: 95.0, critical: 105.0 });
+        history.thresholds.insert("nvme".to_string(), SensorThresholds { warning: 55.0, critical: 70.0 });
+        
+        let result = thermal_result("thermal_monitor", &history, &config, "background", Duration::from_secs(15));
+        assert_ne!(result.status, TestStatus::Failed);
+        assert_eq!(result.metrics["critical_events"], 0);
+        assert_eq!(result.metrics["warning_events"], 1);
+        assert_eq!(result.issues.iter().filter(|i| i.severity == IssueSeverity::High).count(), 1);
+        assert!(result.issues.iter().any(|i| i.message.starts_with("nvme exceeded its warning temperature of 55.0°C")));
+        // The cpu peaked at 92°C, below its own warning threshold.
+        assert_eq!(result.issues.len(), 1);
+        
+        let summaries = history.summarize(config.default_thresholds());
+        assert_eq!(summaries[0].critical_threshold_celsius, 105.0);
+        assert_eq!(summaries[0].time_above_warning_secs, 0.0);
     }
 }
@@ -3,7 +3,7 @@ use std::process::Command;
 #[test]
 fn test_cli_help() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "--help"])
+        .args(["run", "--", "--help"])
         .output()
         .expect("Failed to execute command");
 
@@ -19,7 +19,7 @@ fn test_cli_help() {
 #[test]
 fn test_cli_version() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "--version"])
+        .args(["run", "--", "--version"])
         .output()
         .expect("Failed to execute command");
 
@@ -32,7 +32,7 @@ fn test_cli_version() {
 #[test]
 fn test_hardware_info() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "hardware"])
+        .args(["run", "--", "hardware"])
         .output()
         .expect("Failed to execute command");
 
@@ -41,12 +41,29 @@ fn test_hardware_info() {
     assert!(stdout.contains("System Hardware Information"));
     assert!(stdout.contains("CPU Information"));
     assert!(stdout.contains("Memory Information"));
+    assert!(stdout.contains("Storage Devices"));
+    assert!(stdout.contains("Thermal Sensors"));
+}
+
+#[test]
+fn test_hardware_info_json() {
+    let output = Command::new("cargo")
+        .args(["run", "--", "hardware", "--format", "json"])
+        .output()
+        .expect("Failed to execute command");
+
+    assert!(output.status.success());
+    let hardware: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .expect("hardware output should be JSON");
+    assert!(hardware["cpu_info"]["logical_cores"].as_u64().unwrap() >= 1);
+    assert!(hardware["storage_devices"].is_array());
+    assert!(hardware["resource_limits"].is_object());
 }
 
 #[test]
 fn test_custom_short_duration() {
     let output = Command::new("cargo")
-        .args(&["run", "--release", "--", "custom", "--duration", "1s", "--components", "cpu"])
+        .args(["run", "--release", "--", "custom", "--duration", "1s", "--components", "cpu"])
         .output()
         .expect("Failed to execute command");
 
@@ -60,7 +77,7 @@ fn test_custom_short_duration() {
 #[test]
 fn test_invalid_duration() {
     let output = Command::new("cargo")
-        .args(&["run", "--", "custom", "--duration", "invalid", "--components", "cpu"])
+        .args(["run", "--", "custom", "--duration", "invalid", "--components", "cpu"])
         .output()
         .expect("Failed to execute command");
 
@@ -113,3 +130,181 @@ fn test_memory_size_parsing() {
     assert!(TestConfig::parse_size_str("0%", 100).is_err());
     assert!(TestConfig::parse_size_str("101%", 100).is_err());
 }
+
+#[test]
+fn test_baseline_record() {
+    let path = std::env::temp_dir().join(format!("burnin-baseline-it-{}.json", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--", "--baseline"])
+        .arg(&path)
+        .args(["baseline", "record", "--duration", "1s", "--components", "cpu"])
+        .output()
+        .expect("Failed to execute command");
+    
+    assert!(output.status.success());
+    let contents = std::fs::read_to_string(&path).expect("baseline file was not written");
+    assert!(contents.contains("cpu_stress.instructions_per_second"));
+    
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_policy_verdicts() {
+    let path = std::env::temp_dir().join(format!("burnin-policy-it-{}.toml", std::process::id()));
+    std::fs::write(&path, "[[rule]]\ncheck = \"cpu.score >= 0\"\n\n[[rule]]\ncheck = \"storage.sequential_write_mbps >= 800\"\n").unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--", "--policy"])
+        .arg(&path)
+        .args(["custom", "--duration", "1s", "--components", "cpu"])
+        .output()
+        .expect("Failed to execute command");
+    
+    assert_eq!(output.status.code(), Some(1));
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    assert!(stdout.contains("ACCEPTANCE POLICY"));
+    assert!(stdout.contains("1/2 rules passed"));
+    assert!(stdout.contains("storage.sequential_write_mbps >= 800 (not reported)"));
+    
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_json_stdout_with_event_log() {
+    let path = std::env::temp_dir().join(format!("burnin-events-it-{}.ndjson", std::process::id()));
+    let _ = std::fs::remove_file(&path);
+    
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--", "--verbose", "--format", "json", "--event-log"])
+        .arg(&path)
+        .args(["custom", "--duration", "1s", "--components", "cpu"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .expect("stdout should be a single JSON document");
+    assert_eq!(report["tests"][0]["name"], "cpu_stress");
+    
+    let events: Vec<serde_json::Value> = std::fs::read_to_string(&path).unwrap()
+        .lines()
+        .map(|line| serde_json::from_str(line).unwrap())
+        .collect();
+    assert!(events.iter().all(|event| event["schema"] == "burnin.events/v1"));
+    let position = |name: &str| events.iter().position(|event| event["event"] == name).unwrap();
+    assert!(position("start") < position("test_start"));
+    assert!(position("result") < position("suite"));
+    assert!(events.iter().any(|event| event["event"] == "result" && event["test"] == "cpu_stress"));
+    
+    let _ = std::fs::remove_file(&path);
+}
+
+#[test]
+fn test_example_config_overrides() {
+    use burnin::core::config::{TestConfig, SensorThresholds};
+    use burnin::core::hardware::ThermalSensor;
+    
+    let config = TestConfig::from_file(concat!(env!("CARGO_MANIFEST_DIR"), "/example-config.toml"))
+        .expect("example-config.toml should load");
+    assert_eq!(config.duration, std::time::Duration::from_secs(30 * 60));
+    assert_eq!(config.stress_level, 8);
+    
+    let sensor = |name: &str| ThermalSensor {
+        name: name.to_string(),
+        location: "Unknown".to_string(),
+        current_temp_celsius: 40.0,
+        critical_temp_celsius: None,
+        max_temp_celsius: None,
+    };
+    assert_eq!(config.sensor_thresholds(&sensor("acpitz")), SensorThresholds { warning: 80.0, critical: 95.0 });
+    assert_eq!(config.sensor_thresholds(&sensor("nvme/Composite")), SensorThresholds { warning: 65.0, critical: 70.0 });
+}
+
+#[test]
+fn test_config_file() {
+    let config = std::env::temp_dir().join(format!("burnin-config-it-{}.toml", std::process::id()));
+    let events = std::env::temp_dir().join(format!("burnin-config-it-{}.ndjson", std::process::id()));
+    let _ = std::fs::remove_file(&events);
+    std::fs::write(&config, "stress_level = 3\ncpu_enabled = true\nmemory_enabled = false\nstorage_enabled = false\nthermal_enabled = false\n").unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--", "--config"])
+        .arg(&config)
+        .arg("--event-log")
+        .arg(&events)
+        .args(["custom", "--duration", "1s"])
+        .output()
+        .expect("Failed to execute command");
+    
+    assert!(output.status.code().is_some_and(|code| code <= 1), "{}", String::from_utf8_lossy(&output.stderr));
+    let start: serde_json::Value = std::fs::read_to_string(&events).unwrap()
+        .lines()
+        .map(|line| serde_json::from_str::<serde_json::Value>(line).unwrap())
+        .find(|event| event["event"] == "start")
+        .expect("no start event");
+    assert_eq!(start["config"]["stress_level"], 3);
+    assert_eq!(start["config"]["components"]["cpu"], true);
+    assert_eq!(start["config"]["components"]["memory"], false);
+    assert_eq!(start["config"]["components"]["storage"], false);
+    
+    std::fs::write(&config, "stress_level = 0.8\n").unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--", "--config"])
+        .arg(&config)
+        .args(["custom", "--duration", "1s"])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Failed to load config"));
+    
+    let _ = std::fs::remove_file(&config);
+    let _ = std::fs::remove_file(&events);
+}
+
+#[test]
+fn test_score_weights() {
+    let config = std::env::temp_dir().join(format!("burnin-weights-it-{}.toml", std::process::id()));
+    std::fs::write(&config, "memory_test_size_percent = 1\nstorage_enabled = false\nthermal_enabled = false\n\n[score_weights]\nmemory = 0.0\n").unwrap();
+    
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--", "--format", "json", "--config"])
+        .arg(&config)
+        .args(["custom", "--duration", "1s"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let report: serde_json::Value = serde_json::from_slice(&output.stdout)
+        .expect("stdout should be a JSON report");
+    let score = |name: &str| report["tests"].as_array().unwrap().iter()
+        .find(|test| test["name"] == name)
+        .map(|test| test["score"].clone())
+        .unwrap();
+    assert!(score("memory_validation").is_u64());
+    assert_eq!(report["summary"]["overall_score"], score("cpu_stress"));
+    
+    std::fs::write(&config, "[score_weights]\ncpu = -1.0\n").unwrap();
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--", "--config"])
+        .arg(&config)
+        .args(["custom", "--duration", "1s", "--components", "cpu"])
+        .output()
+        .expect("Failed to execute command");
+    assert_eq!(output.status.code(), Some(1));
+    assert!(String::from_utf8_lossy(&output.stderr).contains("Invalid score weight for cpu"));
+    
+    let _ = std::fs::remove_file(&config);
+}
+
+#[test]
+fn test_alert_flags() {
+    let output = Command::new("cargo")
+        .args(["run", "--release", "--", "--alert-email", "ops@example.com", "--alert-smtp-server", "mail.internal:25"])
+        .args(["--alert-threshold", "100", "--alert-dry-run", "custom", "--duration", "1s", "--components", "cpu"])
+        .output()
+        .expect("Failed to execute command");
+    
+    let stderr = String::from_utf8_lossy(&output.stderr);
+    assert!(stderr.contains("Dry run, not sending alert to email to ops@example.com via mail.internal:25"), "{}", stderr);
+    assert!(stderr.contains("\"score_threshold\":100"));
+}